@@ -1,8 +1,13 @@
 #![warn(clippy::all, clippy::pedantic)]
 
 use ratatui::prelude::*;
+use std::cell::RefCell;
 use std::time::{Duration, Instant};
 
+use crate::charset::CharsetProfile;
+use crate::color::{ColorDepth, Gradient, blend, darken};
+use crate::rng::Rng;
+
 /// A trait for animated patterns that can be rendered to a buffer
 pub trait Pattern {
     /// Update the pattern state
@@ -10,6 +15,32 @@ pub trait Pattern {
 
     /// Render the pattern to a buffer
     fn render(&self, area: Rect, buf: &mut Buffer);
+
+    /// Jump to the state the pattern has `time` after it started, forwards or backwards
+    ///
+    /// Used for scrubbing along a timeline. Patterns that can't reconstruct their state
+    /// ignore this, which is the default.
+    fn seek(&mut self, _time: Duration) {}
+
+    /// Blend the pattern over what is already in the buffer instead of overwriting it
+    ///
+    /// `opacity` runs from 0.0 (invisible) to 1.0 (drawn as normal).
+    fn opacity(self, opacity: f64) -> Translucent<Self>
+    where
+        Self: Sized,
+    {
+        Translucent {
+            inner: self,
+            opacity: opacity.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Render the pattern in its current state into a new buffer of the given size
+    fn snapshot(&self, width: u16, height: u16) -> Buffer {
+        let mut buf = Buffer::empty(Rect::new(0, 0, width, height));
+        self.render(buf.area, &mut buf);
+        buf
+    }
 }
 
 /// A simple animation timer that tracks time and delta time
@@ -58,11 +89,106 @@ impl Default for AnimationTimer {
     }
 }
 
+/// A countdown toward a target duration, measured with an [`AnimationTimer`]
+#[derive(Debug)]
+pub struct CountdownTimer {
+    timer: AnimationTimer,
+    target: Duration,
+}
+
+impl CountdownTimer {
+    /// Create a countdown that starts now and runs for `target`
+    #[must_use]
+    pub fn new(target: Duration) -> Self {
+        Self {
+            timer: AnimationTimer::new(),
+            target,
+        }
+    }
+
+    /// Get the duration the countdown runs for
+    #[must_use]
+    pub fn target(&self) -> Duration {
+        self.target
+    }
+
+    /// Get the time elapsed since the countdown started
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.timer.elapsed()
+    }
+
+    /// Get the time left, which stays at zero once the countdown has run out
+    #[must_use]
+    pub fn remaining(&self) -> Duration {
+        self.remaining_at(Instant::now())
+    }
+
+    /// Get the time left as of `now`
+    #[must_use]
+    pub fn remaining_at(&self, now: Instant) -> Duration {
+        let elapsed = now.saturating_duration_since(self.timer.start_time);
+        self.target.saturating_sub(elapsed)
+    }
+
+    /// Check whether the countdown has reached zero
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.is_finished_at(Instant::now())
+    }
+
+    /// Check whether the countdown has reached zero as of `now`
+    #[must_use]
+    pub fn is_finished_at(&self, now: Instant) -> bool {
+        self.remaining_at(now).is_zero()
+    }
+
+    /// Restart the countdown from the full target duration
+    pub fn reset(&mut self) {
+        self.timer.reset();
+    }
+}
+
+/// Which way the waves of a [`WavePattern`] run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WaveDirection {
+    /// Crests run from left to right
+    #[default]
+    Horizontal,
+    /// Crests run from top to bottom
+    Vertical,
+    /// Crests run from the top-left corner to the bottom-right
+    Diagonal,
+}
+
 /// A wave pattern that creates animated waves using ASCII characters
 pub struct WavePattern {
     time: f64,
     speed: f64,
+    frequency: f64,
+    amplitude: f64,
+    direction: WaveDirection,
     chars: Vec<char>,
+    crossfade: Option<PaletteCrossfade>,
+    rng: RefCell<Rng>,
+}
+
+/// An in-progress blend from a previous character palette to the current one
+struct PaletteCrossfade {
+    from: Vec<char>,
+    elapsed: Duration,
+    duration: Duration,
+}
+
+impl PaletteCrossfade {
+    /// How far the crossfade has progressed, from 0.0 to 1.0
+    fn progress(&self) -> f64 {
+        if self.duration.is_zero() {
+            1.0
+        } else {
+            (self.elapsed.as_secs_f64() / self.duration.as_secs_f64()).min(1.0)
+        }
+    }
 }
 
 impl WavePattern {
@@ -72,7 +198,13 @@ impl WavePattern {
         Self {
             time: 0.0,
             speed: 2.0,
-            chars: vec!['░', '▒', '▓', '█'],
+            frequency: 1.0,
+            amplitude: 1.0,
+            direction: WaveDirection::Horizontal,
+            chars: CharsetProfile::current()
+                .pick(vec!['░', '▒', '▓', '█'], vec!['.', ':', '+', '#']),
+            crossfade: None,
+            rng: RefCell::new(Rng::from_entropy()),
         }
     }
 
@@ -83,38 +215,127 @@ impl WavePattern {
         self
     }
 
+    /// Set how tightly packed the waves are, relative to the default of 1.0
+    #[must_use]
+    pub fn frequency(mut self, frequency: f64) -> Self {
+        self.frequency = frequency;
+        self
+    }
+
+    /// Set how tall the waves are, relative to the default of 1.0
+    ///
+    /// Taller waves sweep through more of the palette; at 0.0 every cell shows the
+    /// middle character.
+    #[must_use]
+    pub fn amplitude(mut self, amplitude: f64) -> Self {
+        self.amplitude = amplitude;
+        self
+    }
+
+    /// Set which way the waves run
+    #[must_use]
+    pub fn direction(mut self, direction: WaveDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
     /// Set the characters used for the wave pattern
+    ///
+    /// An empty palette draws blanks.
     #[must_use]
     pub fn chars(mut self, chars: Vec<char>) -> Self {
         self.chars = chars;
         self
     }
+
+    /// Seed the random number generator used for palette crossfades
+    #[must_use]
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.rng = RefCell::new(Rng::new(seed));
+        self
+    }
+
+    /// Switch to a new character palette, blending from the current one over `duration`
+    ///
+    /// While the crossfade runs, each cell picks the new palette with a probability
+    /// equal to the crossfade's progress, so the new characters gradually take over.
+    pub fn crossfade_palette(&mut self, to: Vec<char>, duration: Duration) {
+        let from = std::mem::replace(&mut self.chars, to);
+        self.crossfade = Some(PaletteCrossfade {
+            from,
+            elapsed: Duration::ZERO,
+            duration,
+        });
+    }
+
+    /// Height of the wave at a cell
+    fn wave_at(&self, x: u16, y: u16) -> f64 {
+        let (x, y) = (f64::from(x), f64::from(y));
+        // Coordinates along and across the direction the crests run
+        let (along, across) = match self.direction {
+            WaveDirection::Horizontal => (x, y),
+            WaveDirection::Vertical => (y, x),
+            WaveDirection::Diagonal => (x + y, x - y),
+        };
+        let frequency = self.frequency;
+        let wave = ((along * 0.2 * frequency - self.time * 2.0).sin() * 5.0)
+            + ((across * 0.1 * frequency + self.time).cos() * 3.0)
+            + ((along + across) * 0.1 * frequency - self.time * 1.5).sin() * 2.0;
+        wave * self.amplitude
+    }
+
+    /// Pick the character for a wave value from a palette, or a blank if it is empty
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn palette_char(chars: &[char], wave: f64) -> char {
+        if chars.is_empty() {
+            return ' ';
+        }
+        let char_index = {
+            let normalized =
+                (wave + 10.0) * (f64::from(u32::try_from(chars.len()).unwrap_or(1)) / 20.0);
+            let index = normalized.abs().floor();
+            if index.is_nan() {
+                0
+            } else {
+                (index as usize) % chars.len()
+            }
+        };
+        chars[char_index]
+    }
 }
 
 impl Pattern for WavePattern {
     fn update(&mut self, delta: Duration) {
         self.time += delta.as_secs_f64() * self.speed;
+
+        if let Some(crossfade) = &mut self.crossfade {
+            crossfade.elapsed += delta;
+            if crossfade.elapsed >= crossfade.duration {
+                self.crossfade = None;
+            }
+        }
+    }
+
+    /// The waves are a function of time alone, so this lands exactly where updates
+    /// adding up to `time` would. A palette crossfade in progress is finished.
+    fn seek(&mut self, time: Duration) {
+        self.time = time.as_secs_f64() * self.speed;
+        self.crossfade = None;
     }
 
-    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
     fn render(&self, area: Rect, buf: &mut Buffer) {
+        let mut rng = self.rng.borrow_mut();
+        let progress = self.crossfade.as_ref().map(PaletteCrossfade::progress);
+
         for y in area.top()..area.bottom() {
             for x in area.left()..area.right() {
-                let wave = ((f64::from(x) * 0.2 - self.time * 2.0).sin() * 5.0)
-                    + ((f64::from(y) * 0.1 + self.time).cos() * 3.0)
-                    + ((f64::from(x) + f64::from(y)) * 0.1 - self.time * 1.5).sin() * 2.0;
-
-                let char_index = {
-                    let normalized = (wave + 10.0)
-                        * (f64::from(u32::try_from(self.chars.len()).unwrap_or(1)) / 20.0);
-                    let index = normalized.abs().floor();
-                    if index.is_nan() {
-                        0
-                    } else {
-                        (index as usize) % self.chars.len()
-                    }
+                let wave = self.wave_at(x, y);
+
+                let chars = match (&self.crossfade, progress) {
+                    (Some(crossfade), Some(progress)) if !rng.chance(progress) => &crossfade.from,
+                    _ => &self.chars,
                 };
-                buf[(x, y)].set_char(self.chars[char_index]);
+                buf[(x, y)].set_char(Self::palette_char(chars, wave));
             }
         }
     }
@@ -133,21 +354,53 @@ pub struct RainPattern {
     drops: Vec<(f64, f64)>, // x, y positions
     chars: Vec<char>,
     drop_chance: f64,
+    splash: bool,
+    splashes: Vec<Splash>,
+    seed: u64,
+    rng: Rng,
+}
+
+/// Update interval that [`RainPattern::seek`] replays the rain at
+const RAIN_SEEK_STEP: Duration = Duration::from_millis(50);
+
+/// Frames a splash lasts after its drop lands
+const SPLASH_FRAMES: u32 = 3;
+
+/// A splash left on the bottom row by a drop that landed
+#[derive(Debug, Clone, Copy)]
+struct Splash {
+    /// Where the drop landed, normalized to the pattern width
+    x: f64,
+    /// Frames since the drop landed
+    age: u32,
 }
 
 impl RainPattern {
     /// Create a new rain pattern with default settings
     #[must_use]
     pub fn new() -> Self {
+        let seed = Rng::from_entropy().next_u64();
         Self {
             time: 0.0,
             speed: 1.0,
             drops: Vec::new(),
-            chars: vec!['│', '╵', '·'],
+            chars: CharsetProfile::current().pick(vec!['│', '╵', '·'], vec!['|', '\'', '.']),
             drop_chance: 0.3,
+            splash: false,
+            splashes: Vec::new(),
+            seed,
+            rng: Rng::new(seed),
         }
     }
 
+    /// Seed the random number generator so the rain is reproducible
+    #[must_use]
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self.rng = Rng::new(seed);
+        self
+    }
+
     /// Set the animation speed
     #[must_use]
     pub fn speed(mut self, speed: f64) -> Self {
@@ -169,6 +422,16 @@ impl RainPattern {
         self
     }
 
+    /// Set whether drops splash when they reach the bottom row
+    ///
+    /// A splash spreads a cell further to each side every frame and fades out after
+    /// a few frames. Off by default.
+    #[must_use]
+    pub fn splash(mut self, splash: bool) -> Self {
+        self.splash = splash;
+        self
+    }
+
     /// Add a drop at a specific position (for testing)
     #[cfg(test)]
     pub fn add_drop(&mut self, x: f64) {
@@ -181,20 +444,58 @@ impl Pattern for RainPattern {
         self.time += delta.as_secs_f64() * self.speed;
 
         // Add new drops
-        if rand::random::<f64>() < self.drop_chance {
-            let x = rand::random::<f64>();
+        if self.rng.chance(self.drop_chance) {
+            let x = self.rng.next_f64();
             self.drops.push((x, 0.0));
         }
 
+        // Age splashes from earlier frames before new ones land
+        self.splashes.retain_mut(|splash| {
+            splash.age += 1;
+            splash.age < SPLASH_FRAMES
+        });
+
         // Update existing drops
-        self.drops.retain_mut(|(_x, y)| {
-            *y += delta.as_secs_f64() * self.speed * 10.0;
-            *y < 1.0 // Remove drops that fall off the bottom
+        let fall = delta.as_secs_f64() * self.speed * 10.0;
+        let splash = self.splash;
+        let splashes = &mut self.splashes;
+        self.drops.retain_mut(|(x, y)| {
+            *y += fall;
+            // Remove drops that fall off the bottom
+            let landed = *y >= 1.0;
+            if landed && splash {
+                splashes.push(Splash { x: *x, age: 0 });
+            }
+            !landed
         });
     }
 
+    /// Rebuilds the rain from its seed by replaying it from the start in 50ms updates,
+    /// so the result matches a pattern stepped at that rate, in far less time than
+    /// running it live. Seeking backwards replays from the start too.
+    fn seek(&mut self, time: Duration) {
+        self.time = 0.0;
+        self.drops.clear();
+        self.splashes.clear();
+        self.rng = Rng::new(self.seed);
+
+        let steps = time.as_nanos() / RAIN_SEEK_STEP.as_nanos();
+        for _ in 0..steps {
+            self.update(RAIN_SEEK_STEP);
+        }
+        let rest = time.saturating_sub(
+            RAIN_SEEK_STEP.saturating_mul(u32::try_from(steps).unwrap_or(u32::MAX)),
+        );
+        if !rest.is_zero() {
+            self.update(rest);
+        }
+    }
+
     #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
     fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.is_empty() {
+            return;
+        }
         for (x, y) in &self.drops {
             let screen_x = {
                 let pos = (x * f64::from(area.width)).clamp(0.0, f64::from(area.width - 1));
@@ -223,6 +524,26 @@ impl Pattern for RainPattern {
                 }
             }
         }
+
+        let profile = CharsetProfile::current();
+        let bottom = area.bottom() - 1;
+        for splash in &self.splashes {
+            let center = (splash.x * f64::from(area.width))
+                .clamp(0.0, f64::from(area.width - 1))
+                .floor() as u16;
+            // Spread wider each frame, thinning out on the last one
+            let reach = u16::try_from(splash.age + 1).unwrap_or(u16::MAX);
+            let glyph = if splash.age + 1 < SPLASH_FRAMES {
+                profile.pick('╌', '-')
+            } else {
+                profile.pick('·', '.')
+            };
+            let left = center.saturating_sub(reach);
+            let right = (center + reach).min(area.width - 1);
+            for x in (left..=right).filter(|&x| x != center) {
+                buf[(area.left() + x, bottom)].set_char(glyph);
+            }
+        }
     }
 }
 
@@ -232,9 +553,642 @@ impl Default for RainPattern {
     }
 }
 
+/// A single snowflake, in coordinates normalized to the pattern area
+#[derive(Debug, Clone, Copy)]
+struct Flake {
+    x: f64,
+    y: f64,
+    /// Fall speed multiplier; faster flakes are drawn larger to appear closer
+    depth: f64,
+    /// Phase offset of the flake's side-to-side sway
+    phase: f64,
+}
+
+/// A snow pattern where flakes drift down and sideways in the wind
+///
+/// Each flake falls at its own speed for a parallax effect, with closer (faster)
+/// flakes drawn larger. The wind pushes all flakes sideways, scaled by their speed,
+/// and optionally gusts over time. Flakes that drift off one side reappear on the
+/// other, and flakes that reach the bottom start again at the top.
+pub struct SnowPattern {
+    time: f64,
+    speed: f64,
+    wind: f64,
+    gust: f64,
+    flake_count: usize,
+    flakes: Vec<Flake>,
+    chars: Vec<char>,
+    rng: Rng,
+}
+
+impl SnowPattern {
+    /// Create a new snow pattern with default settings
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            time: 0.0,
+            speed: 1.0,
+            wind: 0.0,
+            gust: 0.0,
+            flake_count: 40,
+            flakes: Vec::new(),
+            chars: CharsetProfile::current()
+                .pick(vec!['❄', '❅', '*', '·'], vec!['*', '+', '*', '.']),
+            rng: Rng::from_entropy(),
+        }
+    }
+
+    /// Seed the random number generator so the snowfall is reproducible
+    #[must_use]
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.rng = Rng::new(seed);
+        self
+    }
+
+    /// Set the animation speed
+    #[must_use]
+    pub fn speed(mut self, speed: f64) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Set the number of flakes on screen
+    #[must_use]
+    pub fn flake_count(mut self, count: usize) -> Self {
+        self.flake_count = count;
+        self.flakes.truncate(count);
+        self
+    }
+
+    /// Set the wind strength; negative values blow left and positive values blow right
+    #[must_use]
+    pub fn wind(mut self, wind: f64) -> Self {
+        self.wind = wind;
+        self
+    }
+
+    /// Set how far the wind swings around its base strength over time
+    #[must_use]
+    pub fn gust(mut self, gust: f64) -> Self {
+        self.gust = gust;
+        self
+    }
+
+    /// Set the characters used for flakes, from nearest to farthest
+    #[must_use]
+    pub fn chars(mut self, chars: Vec<char>) -> Self {
+        self.chars = chars;
+        self
+    }
+
+    /// Get the wind strength at the current time, including gusts
+    #[must_use]
+    pub fn current_wind(&self) -> f64 {
+        self.wind + self.gust * (self.time * 0.7).sin()
+    }
+
+    fn spawn(&mut self, y: f64) -> Flake {
+        Flake {
+            x: self.rng.next_f64(),
+            y,
+            depth: self.rng.next_range(0.5, 1.5),
+            phase: self.rng.next_range(0.0, std::f64::consts::TAU),
+        }
+    }
+
+    /// Pick the glyph for a flake, with faster flakes using earlier characters
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    fn glyph(&self, flake: &Flake) -> Option<char> {
+        let nearness = ((flake.depth - 0.5).clamp(0.0, 0.999) * self.chars.len() as f64) as usize;
+        self.chars
+            .get(self.chars.len().checked_sub(nearness + 1)?)
+            .copied()
+    }
+}
+
+impl Pattern for SnowPattern {
+    fn update(&mut self, delta: Duration) {
+        let dt = delta.as_secs_f64() * self.speed;
+        self.time += dt;
+
+        // Top up to the flake count, scattered over the whole height at first
+        while self.flakes.len() < self.flake_count {
+            let y = self.rng.next_f64();
+            let flake = self.spawn(y);
+            self.flakes.push(flake);
+        }
+
+        let wind = self.current_wind();
+        for index in 0..self.flakes.len() {
+            let mut flake = self.flakes[index];
+            let sway = 0.05 * (self.time * 2.0 + flake.phase).sin();
+            flake.x = (flake.x + (wind * flake.depth + sway) * dt).rem_euclid(1.0);
+            flake.y += flake.depth * dt * 0.15;
+            if flake.y >= 1.0 {
+                flake = Flake {
+                    y: flake.y.fract(),
+                    ..self.spawn(0.0)
+                };
+            }
+            self.flakes[index] = flake;
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.is_empty() {
+            return;
+        }
+        for flake in &self.flakes {
+            let Some(glyph) = self.glyph(flake) else {
+                continue;
+            };
+            let x = (flake.x * f64::from(area.width)).clamp(0.0, f64::from(area.width - 1));
+            let y = (flake.y * f64::from(area.height)).clamp(0.0, f64::from(area.height - 1));
+            buf[(area.x + x as u16, area.y + y as u16)].set_char(glyph);
+        }
+    }
+}
+
+impl Default for SnowPattern {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A demoscene-style plasma of smoothly shifting colors
+///
+/// Each cell shows two vertically stacked pixels using an upper half block, with the
+/// top pixel in the foreground color and the bottom pixel in the background color.
+/// Pixel values come from summed sine waves over position and time, and index into a
+/// palette that wraps around and cycles as time passes.
+pub struct PlasmaPattern {
+    time: f64,
+    speed: f64,
+    scale: f64,
+    gradient: Gradient,
+}
+
+impl PlasmaPattern {
+    /// Create a new plasma pattern with a rainbow palette
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            time: 0.0,
+            speed: 1.0,
+            scale: 0.15,
+            gradient: Self::cycle(&[
+                Color::Rgb(255, 0, 102),
+                Color::Rgb(255, 153, 0),
+                Color::Rgb(204, 255, 0),
+                Color::Rgb(0, 255, 153),
+                Color::Rgb(0, 102, 255),
+                Color::Rgb(153, 0, 255),
+            ]),
+        }
+    }
+
+    /// Build a gradient through `palette` that wraps back around to its first color
+    fn cycle(palette: &[Color]) -> Gradient {
+        let wrapped: Vec<Color> = palette.iter().chain(palette.first()).copied().collect();
+        Gradient::evenly(&wrapped)
+    }
+
+    /// Set the animation speed
+    #[must_use]
+    pub fn speed(mut self, speed: f64) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Set the spatial frequency; larger values give smaller, busier blobs
+    #[must_use]
+    pub fn scale(mut self, scale: f64) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Set the colors cycled through, blended smoothly from one to the next
+    #[must_use]
+    pub fn palette(mut self, palette: &[Color]) -> Self {
+        self.gradient = Self::cycle(palette);
+        self
+    }
+
+    /// Compute the plasma value at a pixel, in `0.0..=1.0`
+    fn value(&self, x: f64, y: f64) -> f64 {
+        let (x, y, t) = (x * self.scale, y * self.scale, self.time);
+        let sum = (x + t).sin()
+            + y.midpoint(t).sin()
+            + ((x + y + t) / 2.0).sin()
+            + ((x * x + y * y).sqrt() + t).sin();
+        (sum + 4.0) / 8.0
+    }
+
+    /// Look up a palette color, cycling it along with time
+    fn color(&self, value: f64) -> Color {
+        self.gradient
+            .sample((value + self.time * 0.1).rem_euclid(1.0))
+    }
+}
+
+impl Pattern for PlasmaPattern {
+    fn update(&mut self, delta: Duration) {
+        self.time += delta.as_secs_f64() * self.speed;
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if self.gradient.stops().is_empty() {
+            return;
+        }
+        let area = area.intersection(buf.area);
+        // Without half blocks each cell shows a single pixel as its background
+        let ascii = CharsetProfile::current().is_ascii();
+        for Position { x, y } in area.positions() {
+            let px = f64::from(x - area.x);
+            let py = f64::from(y - area.y) * 2.0;
+            let top = self.color(self.value(px, py));
+            if ascii {
+                buf[(x, y)].set_char(' ').set_bg(top);
+                continue;
+            }
+            let bottom = self.color(self.value(px, py + 1.0));
+            buf[(x, y)].set_char('▀').set_fg(top).set_bg(bottom);
+        }
+    }
+}
+
+impl Default for PlasmaPattern {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Brightness below which a trail cell is no longer drawn
+const TRAIL_CUTOFF: f64 = 0.1;
+
+/// Remembered glyphs, their styles, and their brightness for a [`TrailPattern`]
+#[derive(Debug, Default)]
+struct TrailCells {
+    area: Rect,
+    cells: Vec<(char, Style, f64)>,
+}
+
+/// Wraps any pattern so that moving elements leave fading trails
+///
+/// Every update decays the brightness of previously drawn cells, and every render
+/// overlays the wrapped pattern at full brightness. Cells that the wrapped pattern
+/// stopped drawing keep their last glyph and style, dimmed and with RGB colors
+/// darkened by the lost brightness, until they fade out.
+pub struct TrailPattern<P> {
+    inner: P,
+    decay: f64,
+    trail: RefCell<TrailCells>,
+}
+
+impl<P: Pattern> TrailPattern<P> {
+    /// Wrap a pattern with the default decay rate
+    #[must_use]
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            decay: 0.3,
+            trail: RefCell::new(TrailCells::default()),
+        }
+    }
+
+    /// Set the fraction of brightness lost on each update (0.0 to 1.0)
+    #[must_use]
+    pub fn decay(mut self, decay: f64) -> Self {
+        self.decay = decay.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Get a reference to the wrapped pattern
+    #[must_use]
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+}
+
+impl<P: Pattern> Pattern for TrailPattern<P> {
+    fn update(&mut self, delta: Duration) {
+        self.inner.update(delta);
+
+        let retained = 1.0 - self.decay;
+        for (_, _, brightness) in &mut self.trail.get_mut().cells {
+            *brightness *= retained;
+        }
+    }
+
+    /// Seeks the wrapped pattern and clears the trails, which belong to the old time
+    fn seek(&mut self, time: Duration) {
+        self.inner.seek(time);
+        for (_, _, brightness) in &mut self.trail.get_mut().cells {
+            *brightness = 0.0;
+        }
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let mut trail = self.trail.borrow_mut();
+        if trail.area != area {
+            trail.area = area;
+            let len = usize::from(area.width) * usize::from(area.height);
+            trail.cells = vec![(' ', Style::default(), 0.0); len];
+        }
+
+        let mut frame = Buffer::empty(area);
+        self.inner.render(area, &mut frame);
+
+        let positions = area.positions();
+        for (Position { x, y }, (glyph, style, brightness)) in positions.zip(trail.cells.iter_mut())
+        {
+            let cell = &frame[(x, y)];
+            if cell.symbol() != " " {
+                *glyph = cell.symbol().chars().next().unwrap_or(' ');
+                *style = cell.style();
+                *brightness = 1.0;
+                buf[(x, y)] = cell.clone();
+            } else if *brightness >= TRAIL_CUTOFF {
+                let fade =
+                    |color: Option<Color>| color.map(|color| darken(color, 1.0 - *brightness));
+                let faded = Style {
+                    fg: fade(style.fg),
+                    bg: fade(style.bg),
+                    ..*style
+                };
+                buf[(x, y)]
+                    .set_char(*glyph)
+                    .set_style(faded.add_modifier(Modifier::DIM));
+            }
+        }
+    }
+}
+
+/// A pattern drawn with partial opacity, created by [`Pattern::opacity`]
+///
+/// Each cell the wrapped pattern draws has its colors blended over the cell already
+/// in the buffer. Glyphs are taken from the pattern where it draws one. A
+/// [`Color::Reset`] in the pattern is transparent and keeps the buffer's color.
+pub struct Translucent<P> {
+    inner: P,
+    opacity: f64,
+}
+
+impl<P> Translucent<P> {
+    /// Get a reference to the wrapped pattern
+    #[must_use]
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+}
+
+impl<P: Pattern> Pattern for Translucent<P> {
+    fn update(&mut self, delta: Duration) {
+        self.inner.update(delta);
+    }
+
+    fn seek(&mut self, time: Duration) {
+        self.inner.seek(time);
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let area = area.intersection(buf.area);
+        let mut layer = Buffer::empty(area);
+        self.inner.render(area, &mut layer);
+
+        let blank = ratatui::buffer::Cell::EMPTY;
+        for position in area.positions() {
+            let src = &layer[position];
+            if *src == blank {
+                continue;
+            }
+            let dst = &mut buf[position];
+            let depth = ColorDepth::current();
+            let mix = |src: Color, dst: Color| match src {
+                Color::Reset => dst,
+                _ => depth.apply(blend(src, dst, self.opacity)),
+            };
+            let (fg, bg) = (mix(src.fg, dst.fg), mix(src.bg, dst.bg));
+            if src.symbol() != " " {
+                dst.set_symbol(src.symbol());
+            }
+            dst.set_fg(fg).set_bg(bg);
+        }
+    }
+}
+
+/// Draw an animated background across the whole frame, then the UI on top of it
+///
+/// The background pattern is stepped by `elapsed` and rendered first, so any cell the
+/// UI closure leaves untouched shows the background. Pass [`Duration::ZERO`] if the
+/// pattern is already updated elsewhere, such as in `TerminalApp::on_tick`.
+pub fn compose<F>(frame: &mut Frame, background: Option<&mut dyn Pattern>, elapsed: Duration, ui: F)
+where
+    F: FnOnce(&mut Frame),
+{
+    if let Some(pattern) = background {
+        pattern.update(elapsed);
+        let area = frame.area();
+        pattern.render(area, frame.buffer_mut());
+    }
+    ui(frame);
+}
+
+/// How a [`Transition`] replaces one screen with the next
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TransitionEffect {
+    /// Reveal the new screen column by column from the left edge
+    #[default]
+    WipeLeft,
+    /// Blend the colors of the two screens, switching glyphs halfway
+    Fade,
+    /// Reveal the new screen one randomly chosen cell at a time
+    Dissolve,
+}
+
+/// A widget that draws a transition between two screens at a given progress
+///
+/// Each screen is a render closure drawn into its own buffer, then the two are
+/// composited with the chosen [`TransitionEffect`]. At progress 0.0 only the `from`
+/// screen shows and at 1.0 only the `to` screen. [`Dissolve`](TransitionEffect::Dissolve)
+/// reveals cells in an order fixed by the seed, so keep the seed the same across the
+/// frames of one transition.
+pub struct Transition<F, T> {
+    from: F,
+    to: T,
+    effect: TransitionEffect,
+    progress: f64,
+    seed: u64,
+}
+
+impl<F, T> Transition<F, T>
+where
+    F: FnOnce(Rect, &mut Buffer),
+    T: FnOnce(Rect, &mut Buffer),
+{
+    /// Create a wipe from the screen drawn by `from` to the one drawn by `to`
+    #[must_use]
+    pub fn new(from: F, to: T) -> Self {
+        Self {
+            from,
+            to,
+            effect: TransitionEffect::WipeLeft,
+            progress: 0.0,
+            seed: 0,
+        }
+    }
+
+    /// Set the effect used to composite the two screens
+    #[must_use]
+    pub fn effect(mut self, effect: TransitionEffect) -> Self {
+        self.effect = effect;
+        self
+    }
+
+    /// Set how far the transition has gone, from 0.0 to 1.0
+    #[must_use]
+    pub fn progress(mut self, progress: f64) -> Self {
+        self.progress = if progress.is_nan() {
+            0.0
+        } else {
+            progress.clamp(0.0, 1.0)
+        };
+        self
+    }
+
+    /// Set the seed that decides the order cells appear in for a dissolve
+    #[must_use]
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+impl<F, T> Widget for Transition<F, T>
+where
+    F: FnOnce(Rect, &mut Buffer),
+    T: FnOnce(Rect, &mut Buffer),
+{
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = area.intersection(buf.area);
+        let mut from = Buffer::empty(area);
+        let mut to = Buffer::empty(area);
+        (self.from)(area, &mut from);
+        (self.to)(area, &mut to);
+
+        let progress = self.progress;
+        let revealed = (f64::from(area.width) * progress).round() as u16;
+        let mut rng = Rng::new(self.seed);
+        for position in area.positions() {
+            // Drawn for every cell so each cell's threshold doesn't depend on the progress
+            let threshold = rng.next_f64();
+            let (old, new) = (&from[position], &to[position]);
+            let show_new = match self.effect {
+                _ if progress <= 0.0 || progress >= 1.0 => progress >= 1.0,
+                TransitionEffect::WipeLeft => position.x < area.x + revealed,
+                TransitionEffect::Dissolve => threshold < progress,
+                TransitionEffect::Fade => progress >= 0.5,
+            };
+            let mut cell = if show_new { new } else { old }.clone();
+            if self.effect == TransitionEffect::Fade && progress > 0.0 && progress < 1.0 {
+                let depth = ColorDepth::current();
+                cell.set_fg(depth.apply(blend(new.fg, old.fg, progress)))
+                    .set_bg(depth.apply(blend(new.bg, old.bg, progress)));
+            }
+            buf[position] = cell;
+        }
+    }
+}
+
+/// Staggered reveal timing for a sequence of cells, such as those of a `ResponsiveGrid`
+///
+/// Each cell starts revealing `stagger` after the previous one and takes `duration`
+/// to go from hidden (0.0) to fully shown (1.0). Apps use the progress to dim or
+/// clip each cell's content while it appears.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridReveal {
+    stagger: Duration,
+    duration: Duration,
+}
+
+impl GridReveal {
+    /// Create a reveal with a 50ms stagger and a 300ms duration per cell
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            stagger: Duration::from_millis(50),
+            duration: Duration::from_millis(300),
+        }
+    }
+
+    /// Set the delay between one cell starting to appear and the next
+    #[must_use]
+    pub fn stagger(mut self, stagger: Duration) -> Self {
+        self.stagger = stagger;
+        self
+    }
+
+    /// Set how long each cell takes to appear fully
+    #[must_use]
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Get the reveal progress of the cell at `index`, from 0.0 to 1.0
+    #[must_use]
+    pub fn progress(&self, index: usize, elapsed: Duration) -> f64 {
+        let start = self
+            .stagger
+            .saturating_mul(u32::try_from(index).unwrap_or(u32::MAX));
+        let since_start = elapsed.saturating_sub(start);
+        if self.duration.is_zero() {
+            return if elapsed >= start { 1.0 } else { 0.0 };
+        }
+        (since_start.as_secs_f64() / self.duration.as_secs_f64()).min(1.0)
+    }
+
+    /// Pair each cell with its reveal progress
+    #[must_use]
+    pub fn reveal(&self, cells: &[Rect], elapsed: Duration) -> Vec<(Rect, f64)> {
+        cells
+            .iter()
+            .enumerate()
+            .map(|(index, &cell)| (cell, self.progress(index, elapsed)))
+            .collect()
+    }
+
+    /// Clip a cell to the part shown at `progress`, sliding it open from the top
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn clip(cell: Rect, progress: f64) -> Rect {
+        let height = (f64::from(cell.height) * progress.clamp(0.0, 1.0)).ceil() as u16;
+        Rect { height, ..cell }
+    }
+
+    /// Check whether all of `count` cells are fully revealed
+    #[must_use]
+    pub fn is_complete(&self, count: usize, elapsed: Duration) -> bool {
+        count == 0 || self.progress(count - 1, elapsed) >= 1.0
+    }
+}
+
+impl Default for GridReveal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::charset::with_charset;
 
     #[test]
     fn test_animation_timer() {
@@ -249,6 +1203,24 @@ mod tests {
         assert!(timer.elapsed().as_secs() == 0);
     }
 
+    #[test]
+    fn test_countdown_timer_clamps_to_zero() {
+        let countdown = CountdownTimer::new(Duration::from_secs(5));
+        let start = countdown.timer.start_time;
+
+        assert_eq!(countdown.remaining_at(start), Duration::from_secs(5));
+        assert_eq!(
+            countdown.remaining_at(start + Duration::from_millis(1500)),
+            Duration::from_millis(3500)
+        );
+        assert!(!countdown.is_finished_at(start + Duration::from_millis(4999)));
+        assert!(countdown.is_finished_at(start + Duration::from_secs(5)));
+        assert_eq!(
+            countdown.remaining_at(start + Duration::from_mins(1)),
+            Duration::ZERO
+        );
+    }
+
     #[test]
     fn test_wave_pattern() {
         let mut pattern = WavePattern::new();
@@ -264,6 +1236,209 @@ mod tests {
         assert!(has_content, "Buffer should contain wave pattern");
     }
 
+    #[test]
+    fn test_seek_matches_stepping() {
+        let area = Rect::new(0, 0, 16, 8);
+        let chars: Vec<char> = "abcdefgh".chars().collect();
+        let mut stepped = WavePattern::new().chars(chars.clone()).speed(1.5);
+        for _ in 0..37 {
+            stepped.update(Duration::from_millis(20));
+        }
+        let mut sought = WavePattern::new().chars(chars).speed(1.5);
+        sought.seek(Duration::from_millis(740));
+        assert_eq!(sought.snapshot(16, 8), stepped.snapshot(16, 8));
+
+        let mut stepped = RainPattern::new().seed(3).drop_chance(0.8).splash(true);
+        for _ in 0..20 {
+            stepped.update(Duration::from_millis(50));
+        }
+        let mut sought = RainPattern::new().seed(3).drop_chance(0.8).splash(true);
+        sought.seek(Duration::from_secs(3));
+        // Seeking back to an earlier time rebuilds the rain from the start
+        sought.seek(Duration::from_secs(1));
+        let expected = stepped.snapshot(area.width, area.height);
+        assert_eq!(sought.snapshot(area.width, area.height), expected);
+        assert!(expected.content().iter().any(|cell| cell.symbol() != " "));
+    }
+
+    /// A single dot that moves one cell to the right on every update
+    struct DotPattern {
+        x: u16,
+        style: Style,
+    }
+
+    impl Pattern for DotPattern {
+        fn update(&mut self, _delta: Duration) {
+            self.x += 1;
+        }
+
+        fn render(&self, area: Rect, buf: &mut Buffer) {
+            buf[(area.left() + self.x % area.width, area.top())]
+                .set_char('█')
+                .set_style(self.style);
+        }
+    }
+
+    #[test]
+    fn test_pattern_snapshot() {
+        let mut pattern = WavePattern::new().seed(3);
+        pattern.update(Duration::from_secs_f64(0.1));
+
+        let snapshot = pattern.snapshot(10, 10);
+        assert_eq!(snapshot.area, Rect::new(0, 0, 10, 10));
+        assert!(snapshot.content.iter().any(|cell| cell.symbol() != " "));
+        assert_eq!(snapshot, pattern.snapshot(10, 10));
+    }
+
+    #[test]
+    fn test_trail_pattern() {
+        let style = Style::new().fg(Color::Rgb(200, 100, 0)).bg(Color::Blue);
+        let mut pattern = TrailPattern::new(DotPattern { x: 0, style }).decay(0.5);
+        let area = Rect::new(0, 0, 8, 1);
+
+        let mut buffer = Buffer::empty(area);
+        pattern.render(area, &mut buffer);
+        assert_eq!(buffer[(0, 0)].symbol(), "█");
+
+        pattern.update(Duration::from_millis(16));
+        let mut buffer = Buffer::empty(area);
+        pattern.render(area, &mut buffer);
+
+        // The dot moved on, but its previous cell is still drawn dimmed
+        assert_eq!(buffer[(1, 0)].symbol(), "█");
+        assert!(!buffer[(1, 0)].modifier.contains(Modifier::DIM));
+        assert_eq!(buffer[(0, 0)].symbol(), "█");
+        assert!(buffer[(0, 0)].modifier.contains(Modifier::DIM));
+        // The trail keeps the dot's colors, with RGB darkened by the lost brightness
+        assert_eq!(buffer[(0, 0)].fg, Color::Rgb(100, 50, 0));
+        assert_eq!(buffer[(0, 0)].bg, Color::Blue);
+
+        // Enough updates fade the trail out completely
+        for _ in 0..4 {
+            pattern.update(Duration::from_millis(16));
+        }
+        let mut buffer = Buffer::empty(area);
+        pattern.render(area, &mut buffer);
+        assert_eq!(buffer[(0, 0)].symbol(), " ");
+    }
+
+    #[test]
+    fn test_wave_geometry() {
+        let area = Rect::new(0, 0, 20, 10);
+        let chars: Vec<char> = "abcdefghij".chars().collect();
+        let symbols = |pattern: WavePattern| {
+            let mut pattern = pattern.chars(chars.clone());
+            pattern.update(Duration::from_millis(700));
+            let mut buffer = Buffer::empty(area);
+            pattern.render(area, &mut buffer);
+            buffer
+                .content()
+                .iter()
+                .map(|cell| cell.symbol().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        // The defaults keep the original wave equation
+        let default = symbols(WavePattern::new());
+        let time = 0.7 * 2.0;
+        let (x, y) = (7.0_f64, 3.0_f64);
+        let wave = ((x * 0.2 - time * 2.0).sin() * 5.0)
+            + ((y * 0.1 + time).cos() * 3.0)
+            + ((x + y) * 0.1 - time * 1.5).sin() * 2.0;
+        let expected = WavePattern::palette_char(&chars, wave).to_string();
+        assert_eq!(default[3 * 20 + 7], expected);
+
+        let flat = symbols(WavePattern::new().amplitude(0.0));
+        assert!(flat.iter().all(|s| s == "f"), "{flat:?}");
+        let low = symbols(WavePattern::new().amplitude(0.5));
+        assert_ne!(low, default);
+        assert_ne!(
+            symbols(WavePattern::new().direction(WaveDirection::Vertical)),
+            default
+        );
+    }
+
+    #[test]
+    fn test_wave_palette_crossfade() {
+        let area = Rect::new(0, 0, 20, 10);
+        let symbols = |pattern: &WavePattern| {
+            let mut buffer = Buffer::empty(area);
+            pattern.render(area, &mut buffer);
+            buffer
+                .content()
+                .iter()
+                .map(|cell| cell.symbol().to_string())
+                .collect::<std::collections::HashSet<_>>()
+        };
+
+        let mut pattern = WavePattern::new().seed(3).chars(vec!['a', 'b']);
+        pattern.crossfade_palette(vec!['x', 'y'], Duration::from_secs(1));
+
+        pattern.update(Duration::from_millis(500));
+        let midway = symbols(&pattern);
+        assert!(midway.iter().any(|s| s == "a" || s == "b"), "{midway:?}");
+        assert!(midway.iter().any(|s| s == "x" || s == "y"), "{midway:?}");
+
+        pattern.update(Duration::from_millis(500));
+        let done = symbols(&pattern);
+        assert!(done.iter().all(|s| s == "x" || s == "y"), "{done:?}");
+
+        // Fading to an empty palette blanks the pattern instead of panicking
+        pattern.crossfade_palette(Vec::new(), Duration::from_secs(1));
+        pattern.update(Duration::from_millis(500));
+        let midway = symbols(&pattern);
+        assert!(midway.contains(" "), "{midway:?}");
+        pattern.update(Duration::from_millis(500));
+        assert_eq!(symbols(&pattern), [" ".to_string()].into());
+    }
+
+    #[test]
+    fn test_rain_pattern_seeded() {
+        let area = Rect::new(0, 0, 10, 10);
+        let frames = |seed| {
+            let mut pattern = RainPattern::new().seed(seed).drop_chance(0.5);
+            let mut buffer = Buffer::empty(area);
+            for _ in 0..20 {
+                pattern.update(Duration::from_millis(10));
+                pattern.render(area, &mut buffer);
+            }
+            buffer
+        };
+
+        assert_eq!(frames(1), frames(1));
+    }
+
+    #[test]
+    fn test_rain_splash() {
+        let area = Rect::new(0, 0, 10, 4);
+        let bottom_row = |pattern: &RainPattern| {
+            let mut buffer = Buffer::empty(area);
+            pattern.render(area, &mut buffer);
+            (0..area.width)
+                .map(|x| buffer[(x, 3)].symbol().to_owned())
+                .collect::<String>()
+        };
+
+        let mut pattern = RainPattern::new().seed(7).drop_chance(0.0).splash(true);
+        pattern.drops.push((0.5, 0.95));
+        pattern.update(Duration::from_millis(10));
+        assert!(pattern.drops.is_empty());
+        assert_eq!(bottom_row(&pattern), "    ╌ ╌   ");
+
+        pattern.update(Duration::from_millis(10));
+        assert_eq!(bottom_row(&pattern), "   ╌╌ ╌╌  ");
+        pattern.update(Duration::from_millis(10));
+        assert_eq!(bottom_row(&pattern), "  ··· ··· ");
+        pattern.update(Duration::from_millis(10));
+        assert_eq!(bottom_row(&pattern), "          ");
+
+        // Without splashes the drop just vanishes
+        let mut pattern = RainPattern::new().seed(7).drop_chance(0.0);
+        pattern.drops.push((0.5, 0.95));
+        pattern.update(Duration::from_millis(10));
+        assert_eq!(bottom_row(&pattern), "          ");
+    }
+
     #[test]
     fn test_rain_pattern() {
         let mut pattern = RainPattern::new().speed(5.0); // Increase speed for testing
@@ -293,4 +1468,213 @@ mod tests {
 
         assert!(has_content, "Buffer should contain rain drops");
     }
+
+    #[test]
+    fn test_snow_pattern() {
+        let area = Rect::new(0, 0, 20, 10);
+        let frames = |seed| {
+            let mut pattern = SnowPattern::new().seed(seed).flake_count(15).wind(0.3);
+            pattern.update(Duration::from_millis(100));
+            pattern.update(Duration::from_millis(100));
+            pattern.snapshot(area.width, area.height)
+        };
+        assert_eq!(frames(5), frames(5));
+        assert!(frames(5).content.iter().any(|cell| cell.symbol() != " "));
+    }
+
+    #[test]
+    fn test_snow_drift_wraps_and_recycles() {
+        let mut pattern = SnowPattern::new().seed(1).flake_count(1).wind(-1.0);
+        pattern.update(Duration::ZERO);
+        pattern.flakes[0] = Flake {
+            x: 0.02,
+            y: 0.99,
+            depth: 1.0,
+            phase: 0.0,
+        };
+
+        pattern.update(Duration::from_millis(100));
+        let flake = pattern.flakes[0];
+        // Blown off the left edge onto the right, and back to the top after falling out
+        assert!(flake.x > 0.5 && flake.x < 1.0, "x = {}", flake.x);
+        assert!(flake.y < 0.1, "y = {}", flake.y);
+    }
+
+    #[test]
+    fn test_plasma_pattern() {
+        let mut pattern = PlasmaPattern::new();
+        pattern.update(Duration::from_millis(100));
+        let snapshot = pattern.snapshot(8, 4);
+
+        assert!(snapshot.content.iter().all(|cell| cell.symbol() == "▀"));
+        assert!(
+            snapshot
+                .content
+                .iter()
+                .all(|cell| matches!((cell.fg, cell.bg), (Color::Rgb(..), Color::Rgb(..))))
+        );
+        // The field varies across the area rather than filling it with one color
+        let first = snapshot.content[0].fg;
+        assert!(snapshot.content.iter().any(|cell| cell.fg != first));
+    }
+
+    #[test]
+    fn test_patterns_ascii_charset() {
+        let (wave, plasma) = with_charset(CharsetProfile::Ascii, || {
+            let mut wave = WavePattern::new();
+            wave.update(Duration::from_millis(100));
+            let mut plasma = PlasmaPattern::new();
+            plasma.update(Duration::from_millis(100));
+            (wave.snapshot(8, 4), plasma.snapshot(8, 4))
+        });
+
+        assert!(wave.content.iter().all(|cell| cell.symbol().is_ascii()));
+        assert!(plasma.content.iter().all(|cell| cell.symbol() == " "));
+        assert!(
+            plasma
+                .content
+                .iter()
+                .all(|cell| matches!(cell.bg, Color::Rgb(..)))
+        );
+    }
+
+    #[test]
+    fn test_pattern_opacity() {
+        let plasma = PlasmaPattern::new().palette(&[Color::Rgb(255, 0, 0)]);
+        let area = Rect::new(0, 0, 4, 2);
+        let mut buffer = Buffer::empty(area);
+        buffer.set_style(area, Style::new().fg(Color::Blue).bg(Color::Rgb(0, 0, 255)));
+
+        plasma.opacity(0.5).render(area, &mut buffer);
+        let cell = &buffer[(1, 1)];
+        assert_eq!(cell.symbol(), "▀");
+        assert_eq!(cell.bg, Color::Rgb(128, 0, 128));
+        assert_eq!(cell.fg, Color::Rgb(128, 0, 119));
+
+        // Colors the pattern leaves at Reset keep what was underneath
+        let mut buffer = Buffer::empty(area);
+        buffer.set_style(area, Style::new().fg(Color::Blue).bg(Color::Rgb(0, 0, 255)));
+        let dot = DotPattern {
+            x: 0,
+            style: Style::new(),
+        };
+        dot.opacity(0.8).render(area, &mut buffer);
+        let cell = &buffer[(0, 0)];
+        assert_eq!(cell.symbol(), "█");
+        assert_eq!((cell.fg, cell.bg), (Color::Blue, Color::Rgb(0, 0, 255)));
+    }
+
+    #[test]
+    fn test_compose_background_behind_ui() {
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(6, 2)).unwrap();
+        let mut background = PlasmaPattern::new();
+        let frame = terminal
+            .draw(|frame| {
+                compose(
+                    frame,
+                    Some(&mut background),
+                    Duration::from_millis(16),
+                    |frame| frame.render_widget("Hi", Rect::new(0, 0, 2, 1)),
+                );
+            })
+            .unwrap();
+
+        let buffer = frame.buffer;
+        assert_eq!(buffer[(0, 0)].symbol(), "H");
+        assert_eq!(buffer[(1, 0)].symbol(), "i");
+        assert!(
+            buffer
+                .area
+                .positions()
+                .filter(|position| position.y == 1 || position.x >= 2)
+                .all(|position| buffer[position].symbol() == "▀")
+        );
+    }
+
+    /// Draw a transition between a screen of `a`s and a screen of `b`s
+    fn transition(effect: TransitionEffect, progress: f64) -> Buffer {
+        let fill = |symbol: &'static str, color: Color| {
+            move |area: Rect, buf: &mut Buffer| {
+                for position in area.positions() {
+                    buf[position].set_symbol(symbol).set_fg(color);
+                }
+            }
+        };
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 10, 4));
+        Transition::new(fill("a", Color::Red), fill("b", Color::Blue))
+            .effect(effect)
+            .progress(progress)
+            .seed(7)
+            .render(buffer.area, &mut buffer);
+        buffer
+    }
+
+    fn count(buffer: &Buffer, symbol: &str) -> usize {
+        buffer
+            .content()
+            .iter()
+            .filter(|cell| cell.symbol() == symbol)
+            .count()
+    }
+
+    #[test]
+    fn test_transition_endpoints() {
+        for effect in [
+            TransitionEffect::WipeLeft,
+            TransitionEffect::Fade,
+            TransitionEffect::Dissolve,
+        ] {
+            let start = transition(effect, 0.0);
+            assert_eq!(count(&start, "a"), 40, "{effect:?}");
+            assert!(start.content().iter().all(|cell| cell.fg == Color::Red));
+            let end = transition(effect, 1.0);
+            assert_eq!(count(&end, "b"), 40, "{effect:?}");
+            assert!(end.content().iter().all(|cell| cell.fg == Color::Blue));
+        }
+    }
+
+    #[test]
+    fn test_transition_midway() {
+        let wipe = transition(TransitionEffect::WipeLeft, 0.3);
+        assert_eq!(wipe[(2, 3)].symbol(), "b");
+        assert_eq!(wipe[(3, 0)].symbol(), "a");
+
+        let fade = transition(TransitionEffect::Fade, 0.25);
+        assert_eq!(fade[(0, 0)].symbol(), "a");
+        assert_eq!(fade[(0, 0)].fg, Color::Rgb(154, 0, 60));
+
+        // Cells revealed early stay revealed as the dissolve goes on
+        let early = transition(TransitionEffect::Dissolve, 0.3);
+        let late = transition(TransitionEffect::Dissolve, 0.6);
+        assert!((6..=18).contains(&count(&early, "b")));
+        assert!(
+            early
+                .area
+                .positions()
+                .all(|position| early[position].symbol() == "a" || late[position].symbol() == "b")
+        );
+    }
+
+    #[test]
+    fn test_grid_reveal_stagger() {
+        let reveal = GridReveal::new()
+            .stagger(Duration::from_millis(100))
+            .duration(Duration::from_millis(200));
+        let cells = crate::ResponsiveGrid::new().split(Rect::new(0, 0, 80, 20), 4);
+        let elapsed = Duration::from_millis(250);
+
+        let progress: Vec<f64> = reveal
+            .reveal(&cells, elapsed)
+            .into_iter()
+            .map(|(_, progress)| progress)
+            .collect();
+        assert!(progress.windows(2).all(|pair| pair[0] >= pair[1]));
+        assert!(progress[0] > progress[2]);
+        assert!((progress[0] - 1.0).abs() < f64::EPSILON);
+        assert!(progress[3].abs() < f64::EPSILON);
+
+        assert_eq!(GridReveal::clip(Rect::new(0, 0, 10, 4), 0.5).height, 2);
+        assert!(!reveal.is_complete(4, elapsed));
+        assert!(reveal.is_complete(4, Duration::from_millis(500)));
+    }
 }
@@ -0,0 +1,257 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use crossterm::event::{Event, EventStream, KeyCode};
+use futures::{Stream, StreamExt};
+use ratatui::{Frame, Terminal, backend::Backend};
+use std::future::Future;
+use std::io;
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+
+use crate::{Error, ExitReason, KeyMap};
+
+/// Terminal UI application trait for apps that await I/O between frames
+///
+/// Implementations can write the methods as `async fn`.
+pub trait AsyncTerminalApp {
+    /// Render the UI
+    fn ui(&self, frame: &mut Frame);
+
+    /// Handle terminal events
+    ///
+    /// # Errors
+    /// Returns an error if event handling fails.
+    /// Returns Ok(true) if the application should exit, Ok(false) otherwise.
+    fn handle_event(&mut self, event: Event) -> impl Future<Output = anyhow::Result<bool>>;
+
+    /// Called once per frame tick, whether or not an event arrived
+    ///
+    /// # Errors
+    /// Returns an error if the tick handling fails.
+    /// Returns Ok(true) if the application should exit, Ok(false) otherwise.
+    fn on_tick(&mut self) -> impl Future<Output = anyhow::Result<bool>> {
+        async { Ok(false) }
+    }
+
+    /// Called when the terminal is resized
+    fn on_resize(&mut self, _width: u16, _height: u16) {}
+
+    /// Key map that the run loop should dispatch through
+    ///
+    /// Keys bound in the map are sent to [`handle_action`](Self::handle_action) by name
+    /// instead of to [`handle_event`](Self::handle_event). A binding for `q` takes
+    /// precedence over the built-in quit key.
+    fn key_map(&self) -> Option<&KeyMap> {
+        None
+    }
+
+    /// Handle an action triggered through the [`key_map`](Self::key_map)
+    ///
+    /// # Errors
+    /// Returns an error if the action fails.
+    /// Returns Ok(true) if the application should exit, Ok(false) otherwise.
+    fn handle_action(&mut self, _action: &str) -> impl Future<Output = anyhow::Result<bool>> {
+        async { Ok(false) }
+    }
+}
+
+/// Run an async terminal application on the current tokio runtime
+///
+/// Each iteration draws the UI, then waits for either the next terminal event or
+/// the next frame tick, whichever comes first.
+///
+/// # Errors
+/// Returns an error if:
+/// - Failed to draw to terminal
+/// - Failed to read events
+/// - Application event or tick handling failed
+///
+/// On success, returns the [`ExitReason`] that ended the loop.
+pub async fn run_app_async<B, A>(
+    terminal: &mut Terminal<B>,
+    app: A,
+    tick_rate: Duration,
+) -> Result<ExitReason, Error>
+where
+    B: Backend,
+    A: AsyncTerminalApp,
+{
+    run_app_async_with(terminal, app, EventStream::new(), tick_rate).await
+}
+
+/// Run an async terminal application with a custom event stream
+///
+/// # Errors
+/// Returns an error if:
+/// - Failed to draw to terminal
+/// - Failed to read events
+/// - Application event or tick handling failed
+///
+/// On success, returns the [`ExitReason`] that ended the loop.
+pub async fn run_app_async_with<B, A, S>(
+    terminal: &mut Terminal<B>,
+    mut app: A,
+    events: S,
+    tick_rate: Duration,
+) -> Result<ExitReason, Error>
+where
+    B: Backend,
+    A: AsyncTerminalApp,
+    S: Stream<Item = io::Result<Event>>,
+{
+    let mut events = std::pin::pin!(events);
+    let mut ticks = tokio::time::interval(tick_rate);
+    ticks.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+    loop {
+        // Skip drawing while the terminal has no rows or columns
+        terminal
+            .autoresize()
+            .map_err(|e| Error::Terminal(e.into()))?;
+        if !terminal.get_frame().area().is_empty() {
+            terminal
+                .draw(|f| app.ui(f))
+                .map_err(|e| Error::Terminal(e.into()))?;
+        }
+
+        tokio::select! {
+            _ = ticks.tick() => {
+                if app.on_tick().await.map_err(Error::Terminal)? {
+                    return Ok(ExitReason::AppRequested);
+                }
+            }
+            event = events.next() => match event.transpose()? {
+                None => return Ok(ExitReason::EventStreamEnded),
+                Some(Event::Resize(width, height)) => app.on_resize(width, height),
+                Some(Event::Key(key)) => {
+                    let action = app
+                        .key_map()
+                        .and_then(|map| map.lookup(&key))
+                        .map(str::to_owned);
+                    let exit = match action {
+                        Some(action) => app.handle_action(&action).await,
+                        None if key.code == KeyCode::Char('q') => return Ok(ExitReason::QuitKey),
+                        None => app.handle_event(Event::Key(key)).await,
+                    };
+                    if exit.map_err(Error::Terminal)? {
+                        return Ok(ExitReason::AppRequested);
+                    }
+                }
+                Some(_) => {}
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyEvent, KeyModifiers};
+    use futures::stream;
+    use ratatui::backend::TestBackend;
+
+    #[derive(Default)]
+    struct TestApp {
+        ticks: u32,
+        exit_after_ticks: Option<u32>,
+    }
+
+    impl AsyncTerminalApp for TestApp {
+        fn ui(&self, _frame: &mut Frame) {}
+
+        async fn handle_event(&mut self, event: Event) -> anyhow::Result<bool> {
+            if let Event::Key(KeyEvent {
+                code: KeyCode::Char(c),
+                ..
+            }) = event
+            {
+                // Stand-in for awaiting a network response
+                tokio::task::yield_now().await;
+                return Ok(c == 'x');
+            }
+            Ok(false)
+        }
+
+        async fn on_tick(&mut self) -> anyhow::Result<bool> {
+            self.ticks += 1;
+            Ok(self
+                .exit_after_ticks
+                .is_some_and(|limit| self.ticks >= limit))
+        }
+    }
+
+    fn key(c: char) -> Event {
+        Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+    }
+
+    #[tokio::test]
+    async fn test_events_and_exit_reasons() {
+        let mut terminal = Terminal::new(TestBackend::new(10, 3)).unwrap();
+        let tick = Duration::from_millis(10);
+
+        let events = stream::iter([key('a'), key('x'), key('b')].map(Ok));
+        let reason = run_app_async_with(&mut terminal, TestApp::default(), events, tick).await;
+        assert_eq!(reason.unwrap(), ExitReason::AppRequested);
+
+        let events = stream::iter([Ok(key('q'))]);
+        let reason = run_app_async_with(&mut terminal, TestApp::default(), events, tick).await;
+        assert_eq!(reason.unwrap(), ExitReason::QuitKey);
+
+        let events = stream::iter([Ok(key('a'))]);
+        let reason = run_app_async_with(&mut terminal, TestApp::default(), events, tick).await;
+        assert_eq!(reason.unwrap(), ExitReason::EventStreamEnded);
+    }
+
+    #[tokio::test]
+    async fn test_key_map_binding_overrides_quit_key() {
+        struct MappedApp {
+            keys: KeyMap,
+            actions: Vec<String>,
+        }
+
+        impl AsyncTerminalApp for MappedApp {
+            fn ui(&self, _frame: &mut Frame) {}
+
+            async fn handle_event(&mut self, _event: Event) -> anyhow::Result<bool> {
+                Ok(false)
+            }
+
+            fn key_map(&self) -> Option<&KeyMap> {
+                Some(&self.keys)
+            }
+
+            async fn handle_action(&mut self, action: &str) -> anyhow::Result<bool> {
+                self.actions.push(action.to_owned());
+                Ok(self.actions.len() == 2)
+            }
+        }
+
+        let mut terminal = Terminal::new(TestBackend::new(10, 3)).unwrap();
+        let app = MappedApp {
+            keys: KeyMap::new().bind(KeyCode::Char('q'), KeyModifiers::NONE, "queue", "Queue"),
+            actions: Vec::new(),
+        };
+        let events = stream::iter([key('q'), key('q')].map(Ok));
+        let reason =
+            run_app_async_with(&mut terminal, app, events, Duration::from_millis(10)).await;
+        assert_eq!(reason.unwrap(), ExitReason::AppRequested);
+    }
+
+    #[tokio::test]
+    async fn test_ticks_run_while_waiting_for_events() {
+        let mut terminal = Terminal::new(TestBackend::new(10, 3)).unwrap();
+        let app = TestApp {
+            exit_after_ticks: Some(3),
+            ..TestApp::default()
+        };
+
+        let reason = run_app_async_with(
+            &mut terminal,
+            app,
+            stream::pending(),
+            Duration::from_millis(10),
+        )
+        .await;
+        assert_eq!(reason.unwrap(), ExitReason::AppRequested);
+    }
+}
@@ -0,0 +1,109 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use ratatui::symbols::border;
+use std::cell::Cell;
+
+/// Environment variable that overrides charset detection (`ascii` or `unicode`)
+pub const CHARSET_ENV_VAR: &str = "RATUI_CHARSET";
+
+/// ASCII border with `+` corners
+pub const ASCII_BORDER: border::Set = border::Set {
+    top_left: "+",
+    top_right: "+",
+    bottom_left: "+",
+    bottom_right: "+",
+    vertical_left: "|",
+    vertical_right: "|",
+    horizontal_top: "-",
+    horizontal_bottom: "-",
+};
+
+thread_local! {
+    static CURRENT: Cell<Option<CharsetProfile>> = const { Cell::new(None) };
+}
+
+/// The set of glyphs the terminal can be trusted to display
+///
+/// Widgets and patterns consult [`CharsetProfile::current`] for their default glyphs,
+/// and use ASCII stand-ins for box drawing, block elements, and symbols when it is
+/// [`Ascii`](Self::Ascii). Glyphs passed explicitly to a builder are always used as given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CharsetProfile {
+    #[default]
+    Unicode,
+    Ascii,
+}
+
+impl CharsetProfile {
+    /// Detect the profile from the environment
+    ///
+    /// Defaults to Unicode unless [`CHARSET_ENV_VAR`] is set to `ascii`.
+    #[must_use]
+    pub fn detect() -> Self {
+        match std::env::var(CHARSET_ENV_VAR) {
+            Ok(value) if value.eq_ignore_ascii_case("ascii") => Self::Ascii,
+            _ => Self::Unicode,
+        }
+    }
+
+    /// Get the profile in effect on this thread, detecting it on first use
+    #[must_use]
+    pub fn current() -> Self {
+        CURRENT.with(|current| {
+            current.get().unwrap_or_else(|| {
+                let detected = Self::detect();
+                current.set(Some(detected));
+                detected
+            })
+        })
+    }
+
+    /// Set the profile used on this thread from now on
+    pub fn set_current(profile: Self) {
+        CURRENT.with(|current| current.set(Some(profile)));
+    }
+
+    /// Check whether only ASCII glyphs should be used
+    #[must_use]
+    pub fn is_ascii(self) -> bool {
+        self == Self::Ascii
+    }
+
+    /// Choose between a Unicode glyph and its ASCII fallback
+    #[must_use]
+    pub fn pick<T>(self, unicode: T, ascii: T) -> T {
+        match self {
+            Self::Unicode => unicode,
+            Self::Ascii => ascii,
+        }
+    }
+
+    /// Get the border glyphs for this profile
+    #[must_use]
+    pub fn border_set(self) -> border::Set {
+        self.pick(border::PLAIN, ASCII_BORDER)
+    }
+}
+
+/// Run `f` with `profile` as the current profile, restoring the previous one afterwards
+pub fn with_charset<R>(profile: CharsetProfile, f: impl FnOnce() -> R) -> R {
+    let previous = CURRENT.with(|current| current.replace(Some(profile)));
+    let result = f();
+    CURRENT.with(|current| current.set(previous));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_charset_restores_previous() {
+        CharsetProfile::set_current(CharsetProfile::Unicode);
+        let inside = with_charset(CharsetProfile::Ascii, || {
+            CharsetProfile::current().pick('●', '*')
+        });
+        assert_eq!(inside, '*');
+        assert_eq!(CharsetProfile::current(), CharsetProfile::Unicode);
+    }
+}
@@ -0,0 +1,451 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use ratatui::style::Color;
+use std::cell::Cell;
+
+/// Environment variable that overrides color depth detection (`truecolor`, `256`, or `16`)
+pub const COLOR_DEPTH_ENV_VAR: &str = "RATUI_COLOR_DEPTH";
+
+/// Channel values of the six steps of the xterm 6×6×6 color cube
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The named colors in ANSI index order
+const ANSI_COLORS: [Color; 16] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::Gray,
+    Color::DarkGray,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightYellow,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::LightCyan,
+    Color::White,
+];
+
+thread_local! {
+    static CURRENT: Cell<Option<ColorDepth>> = const { Cell::new(None) };
+}
+
+/// RGB values of the 16 ANSI colors, using the xterm defaults
+const ANSI_RGB: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// Resolve a color to RGB, if it has a well-defined value
+///
+/// Named colors use the xterm default palette, and indexed colors above 15 map onto
+/// the 6×6×6 color cube and the grayscale ramp. [`Color::Reset`] depends on the
+/// terminal's theme, so it has no RGB value.
+#[must_use]
+pub fn to_rgb(color: Color) -> Option<(u8, u8, u8)> {
+    let index = match color {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => return Some((r, g, b)),
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        Color::White => 15,
+        Color::Indexed(index) => index,
+    };
+    Some(match index {
+        0..=15 => ANSI_RGB[usize::from(index)],
+        16..=231 => {
+            let level = |value: u8| if value == 0 { 0 } else { 55 + value * 40 };
+            let cube = index - 16;
+            (level(cube / 36), level(cube / 6 % 6), level(cube % 6))
+        }
+        232..=255 => {
+            let gray = 8 + (index - 232) * 10;
+            (gray, gray, gray)
+        }
+    })
+}
+
+/// How many colors the terminal can display
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorDepth {
+    /// The 16 ANSI colors
+    Ansi16,
+    /// The xterm 256-color palette
+    Ansi256,
+    /// 24-bit RGB
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// Detect the depth from the environment
+    ///
+    /// [`COLOR_DEPTH_ENV_VAR`] takes priority, then `COLORTERM` and `TERM` are checked.
+    #[must_use]
+    pub fn detect() -> Self {
+        let var = |name| std::env::var(name).ok();
+        var(COLOR_DEPTH_ENV_VAR)
+            .and_then(|value| Self::parse(&value))
+            .unwrap_or_else(|| Self::from_env(var("COLORTERM").as_deref(), var("TERM").as_deref()))
+    }
+
+    /// Work out the depth from the values of `COLORTERM` and `TERM`
+    ///
+    /// Without `TERM`, as on Windows consoles, true color is assumed.
+    #[must_use]
+    pub fn from_env(colorterm: Option<&str>, term: Option<&str>) -> Self {
+        if colorterm.is_some_and(|value| matches!(value, "truecolor" | "24bit")) {
+            return Self::TrueColor;
+        }
+        match term {
+            None => Self::TrueColor,
+            Some(term) if term.contains("truecolor") || term.contains("direct") => Self::TrueColor,
+            Some(term) if term.contains("256color") => Self::Ansi256,
+            Some(_) => Self::Ansi16,
+        }
+    }
+
+    /// Parse an override value such as `truecolor`, `256`, or `16`
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "truecolor" | "24bit" => Some(Self::TrueColor),
+            "256" => Some(Self::Ansi256),
+            "16" => Some(Self::Ansi16),
+            _ => None,
+        }
+    }
+
+    /// Get the depth that widgets render for on this thread
+    ///
+    /// This is true color, leaving RGB untouched, until [`set_current`](Self::set_current)
+    /// is called. Setting up the terminal does that with the detected depth.
+    #[must_use]
+    pub fn current() -> Self {
+        CURRENT.with(Cell::get).unwrap_or(Self::TrueColor)
+    }
+
+    /// Check whether a depth has been set on this thread
+    #[must_use]
+    pub fn is_set() -> bool {
+        CURRENT.with(Cell::get).is_some()
+    }
+
+    /// Set the depth widgets render for on this thread from now on
+    pub fn set_current(depth: Self) {
+        CURRENT.with(|current| current.set(Some(depth)));
+    }
+
+    /// Reduce a color so this depth can display it
+    #[must_use]
+    pub fn apply(self, color: Color) -> Color {
+        downgrade_color(color, self)
+    }
+}
+
+/// Reduce `color` to the nearest color available at `depth`
+///
+/// RGB colors map onto the 256-color cube and grayscale ramp, or onto the 16 ANSI
+/// colors. Indexed colors above 15 are reduced at 16 colors too. Named colors and
+/// [`Color::Reset`] are always left alone.
+#[must_use]
+pub fn downgrade_color(color: Color, depth: ColorDepth) -> Color {
+    match (color, depth) {
+        (_, ColorDepth::TrueColor)
+        | (Color::Indexed(_), ColorDepth::Ansi256)
+        | (Color::Indexed(0..=15), ColorDepth::Ansi16) => color,
+        (Color::Rgb(r, g, b), ColorDepth::Ansi256) => Color::Indexed(nearest_256(r, g, b)),
+        (Color::Rgb(..) | Color::Indexed(_), ColorDepth::Ansi16) => to_rgb(color)
+            .map_or(color, |(r, g, b)| {
+                ANSI_COLORS[nearest_index(&ANSI_RGB, (r, g, b))]
+            }),
+        _ => color,
+    }
+}
+
+/// Scale an RGB color towards black, leaving other colors untouched
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub(crate) fn darken(color: Color, factor: f64) -> Color {
+    match color {
+        Color::Rgb(r, g, b) => {
+            let scale = |channel: u8| (f64::from(channel) * (1.0 - factor)).round() as u8;
+            ColorDepth::current().apply(Color::Rgb(scale(r), scale(g), scale(b)))
+        }
+        other => other,
+    }
+}
+
+/// Find the closest entry in the 256-color palette outside the 16 ANSI colors
+fn nearest_256(r: u8, g: u8, b: u8) -> u8 {
+    let step = |value: u8| nearest_index(&CUBE_LEVELS.map(|level| (level, 0, 0)), (value, 0, 0));
+    let (ri, gi, bi) = (step(r), step(g), step(b));
+    let cube = (CUBE_LEVELS[ri], CUBE_LEVELS[gi], CUBE_LEVELS[bi]);
+
+    let average = (u16::from(r) + u16::from(g) + u16::from(b)) / 3;
+    let gray_step = u8::try_from((average.saturating_sub(3) / 10).min(23)).unwrap_or(23);
+    let gray = 8 + gray_step * 10;
+
+    let cube_index = 16 + 36 * ri + 6 * gi + bi;
+    if distance((r, g, b), (gray, gray, gray)) < distance((r, g, b), cube) {
+        232 + gray_step
+    } else {
+        u8::try_from(cube_index).unwrap_or(u8::MAX)
+    }
+}
+
+/// Find the index of the palette entry closest to `target`
+fn nearest_index(palette: &[(u8, u8, u8)], target: (u8, u8, u8)) -> usize {
+    (0..palette.len())
+        .min_by_key(|&index| distance(palette[index], target))
+        .unwrap_or(0)
+}
+
+/// Squared distance between two RGB colors
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let channel = |x: u8, y: u8| u32::from(x.abs_diff(y)).pow(2);
+    channel(a.0, b.0) + channel(a.1, b.1) + channel(a.2, b.2)
+}
+
+/// Mix `src` over `dst` with the given opacity, from 0.0 (all `dst`) to 1.0 (all `src`)
+///
+/// Both colors are resolved to RGB with [`to_rgb`] and mixed per channel. If either
+/// has no RGB value, the result is whichever color has more weight.
+#[must_use]
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn blend(src: Color, dst: Color, alpha: f64) -> Color {
+    let alpha = if alpha.is_nan() {
+        0.0
+    } else {
+        alpha.clamp(0.0, 1.0)
+    };
+    match (to_rgb(src), to_rgb(dst)) {
+        (Some((r1, g1, b1)), Some((r2, g2, b2))) => {
+            let mix =
+                |s: u8, d: u8| (f64::from(s) * alpha + f64::from(d) * (1.0 - alpha)).round() as u8;
+            Color::Rgb(mix(r1, r2), mix(g1, g2), mix(b1, b2))
+        }
+        _ if alpha >= 0.5 => src,
+        _ => dst,
+    }
+}
+
+/// A mapping from positions between 0.0 and 1.0 to colors, through a list of stops
+///
+/// Between two stops the colors are mixed per channel with [`blend`], so named and
+/// indexed colors are resolved to RGB first. Positions outside the stops take the
+/// color of the nearest stop.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    stops: Vec<(f64, Color)>,
+}
+
+impl Gradient {
+    /// Create a gradient from `(position, color)` stops
+    ///
+    /// Positions are clamped to `0.0..=1.0` and the stops sorted by position. Stops
+    /// with a NaN position are dropped.
+    #[must_use]
+    pub fn new(stops: Vec<(f64, Color)>) -> Self {
+        let mut stops: Vec<(f64, Color)> = stops
+            .into_iter()
+            .filter(|(position, _)| !position.is_nan())
+            .map(|(position, color)| (position.clamp(0.0, 1.0), color))
+            .collect();
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { stops }
+    }
+
+    /// Create a gradient with the colors spread evenly from 0.0 to 1.0
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn evenly(colors: &[Color]) -> Self {
+        let last = colors.len().saturating_sub(1).max(1) as f64;
+        let stops = colors
+            .iter()
+            .enumerate()
+            .map(|(index, &color)| (index as f64 / last, color))
+            .collect();
+        Self::new(stops)
+    }
+
+    /// The perceptually uniform viridis colormap, from dark purple to yellow
+    #[must_use]
+    pub fn viridis() -> Self {
+        Self::evenly(&[
+            Color::Rgb(68, 1, 84),
+            Color::Rgb(59, 82, 139),
+            Color::Rgb(33, 145, 140),
+            Color::Rgb(94, 201, 98),
+            Color::Rgb(253, 231, 37),
+        ])
+    }
+
+    /// A heat colormap running from black through red and yellow to white
+    #[must_use]
+    pub fn heat() -> Self {
+        Self::evenly(&[
+            Color::Rgb(0, 0, 0),
+            Color::Rgb(255, 0, 0),
+            Color::Rgb(255, 255, 0),
+            Color::Rgb(255, 255, 255),
+        ])
+    }
+
+    /// The stops, sorted by position
+    #[must_use]
+    pub fn stops(&self) -> &[(f64, Color)] {
+        &self.stops
+    }
+
+    /// Get the color at `t`, clamped to `0.0..=1.0`
+    ///
+    /// Exactly at a stop the stop's own color is returned, and between stops the
+    /// result is an RGB color. Either way it is reduced to [`ColorDepth::current`]. A
+    /// gradient without stops is [`Color::Reset`] everywhere.
+    #[must_use]
+    pub fn sample(&self, t: f64) -> Color {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        let after = self.stops.partition_point(|&(position, _)| position < t);
+        let color = match (after.checked_sub(1), self.stops.get(after)) {
+            (_, None) => self.stops.last().map_or(Color::Reset, |&(_, color)| color),
+            // Up to the first stop, or exactly on any stop, its color is used as given
+            (None, Some(&(_, color))) => color,
+            (Some(_), Some(&(end, color))) if end <= t => color,
+            (Some(before), Some(&(end, to))) => {
+                let (start, from) = self.stops[before];
+                blend(to, from, (t - start) / (end - start))
+            }
+        };
+        ColorDepth::current().apply(color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blend_half_red_over_blue() {
+        assert_eq!(
+            blend(Color::Rgb(255, 0, 0), Color::Rgb(0, 0, 255), 0.5),
+            Color::Rgb(128, 0, 128)
+        );
+        // Named colors resolve through the xterm palette first
+        assert_eq!(blend(Color::Red, Color::Blue, 0.5), Color::Rgb(103, 0, 119));
+    }
+
+    #[test]
+    fn test_blend_extremes_and_unresolvable() {
+        let red = Color::Rgb(255, 0, 0);
+        let blue = Color::Rgb(0, 0, 255);
+        assert_eq!(blend(red, blue, 1.0), red);
+        assert_eq!(blend(red, blue, 0.0), blue);
+        assert_eq!(blend(red, Color::Reset, 0.7), red);
+        assert_eq!(blend(red, Color::Reset, 0.2), Color::Reset);
+    }
+
+    #[test]
+    fn test_gradient_sample() {
+        let gradient = Gradient::new(vec![
+            (1.0, Color::Rgb(0, 0, 255)),
+            (0.0, Color::Rgb(255, 0, 0)),
+            (0.5, Color::White),
+        ]);
+        assert_eq!(gradient.sample(0.0), Color::Rgb(255, 0, 0));
+        assert_eq!(gradient.sample(0.5), Color::White);
+        assert_eq!(gradient.sample(0.75), Color::Rgb(128, 128, 255));
+        assert_eq!(gradient.sample(1.0), Color::Rgb(0, 0, 255));
+        // Named stops are resolved to RGB before mixing
+        assert_eq!(gradient.sample(0.25), Color::Rgb(255, 128, 128));
+        assert_eq!(gradient.sample(-3.0), Color::Rgb(255, 0, 0));
+        assert_eq!(gradient.sample(f64::NAN), Color::Rgb(255, 0, 0));
+
+        assert_eq!(Gradient::heat().sample(1.0 / 6.0), Color::Rgb(128, 0, 0));
+        assert_eq!(Gradient::viridis().sample(1.0), Color::Rgb(253, 231, 37));
+        assert_eq!(Gradient::new(Vec::new()).sample(0.5), Color::Reset);
+
+        // Samples are reduced to the current color depth
+        ColorDepth::set_current(ColorDepth::Ansi256);
+        assert_eq!(Gradient::heat().sample(1.0 / 3.0), Color::Indexed(196));
+    }
+
+    #[test]
+    fn test_downgrade_to_256() {
+        let to_256 = |r, g, b| downgrade_color(Color::Rgb(r, g, b), ColorDepth::Ansi256);
+        assert_eq!(to_256(255, 0, 0), Color::Indexed(196));
+        assert_eq!(to_256(0, 95, 135), Color::Indexed(24));
+        assert_eq!(to_256(250, 250, 250), Color::Indexed(231));
+        // Grays closer to the ramp than to the cube use the ramp
+        assert_eq!(to_256(128, 128, 128), Color::Indexed(244));
+        assert_eq!(to_256(100, 100, 100), Color::Indexed(241));
+        assert_eq!(to_256(0, 0, 0), Color::Indexed(16));
+    }
+
+    #[test]
+    fn test_downgrade_to_16() {
+        let to_16 = |color| downgrade_color(color, ColorDepth::Ansi16);
+        assert_eq!(to_16(Color::Rgb(250, 10, 10)), Color::LightRed);
+        assert_eq!(to_16(Color::Rgb(10, 10, 20)), Color::Black);
+        assert_eq!(to_16(Color::Indexed(196)), Color::LightRed);
+        assert_eq!(to_16(Color::Cyan), Color::Cyan);
+        assert_eq!(to_16(Color::Reset), Color::Reset);
+        assert_eq!(
+            downgrade_color(Color::Rgb(1, 2, 3), ColorDepth::TrueColor),
+            Color::Rgb(1, 2, 3)
+        );
+    }
+
+    #[test]
+    fn test_color_depth_from_env() {
+        assert_eq!(
+            ColorDepth::from_env(Some("truecolor"), Some("xterm")),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(
+            ColorDepth::from_env(None, Some("xterm-256color")),
+            ColorDepth::Ansi256
+        );
+        assert_eq!(
+            ColorDepth::from_env(None, Some("linux")),
+            ColorDepth::Ansi16
+        );
+        assert_eq!(ColorDepth::from_env(None, None), ColorDepth::TrueColor);
+        assert_eq!(ColorDepth::parse("256"), Some(ColorDepth::Ansi256));
+    }
+
+    #[test]
+    fn test_to_rgb_indexed() {
+        assert_eq!(to_rgb(Color::Indexed(9)), to_rgb(Color::LightRed));
+        assert_eq!(to_rgb(Color::Indexed(196)), Some((255, 0, 0)));
+        assert_eq!(to_rgb(Color::Indexed(244)), Some((128, 128, 128)));
+        assert_eq!(to_rgb(Color::Reset), None);
+    }
+}
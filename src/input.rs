@@ -0,0 +1,1532 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::prelude::*;
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Tracks which keys are currently held down
+///
+/// Terminals with keyboard enhancement report `Press`, `Repeat` and `Release` events,
+/// which the tracker follows exactly. Most terminals never send `Release`, so until one
+/// is seen a key counts as released once no press or repeat has arrived for the
+/// release timeout. Auto-repeat keeps a held key alive in that mode.
+#[derive(Debug, Clone)]
+pub struct KeyRepeatTracker {
+    down: HashMap<KeyCode, Instant>,
+    release_timeout: Duration,
+    release_supported: bool,
+}
+
+impl KeyRepeatTracker {
+    /// Create a tracker with the default release timeout
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            down: HashMap::new(),
+            release_timeout: Duration::from_millis(500),
+            release_supported: false,
+        }
+    }
+
+    /// Set how long a key stays down without a new press on terminals lacking release events
+    ///
+    /// This should exceed the terminal's auto-repeat delay, or held keys will flicker
+    /// between down and up before repeating starts.
+    #[must_use]
+    pub fn release_timeout(mut self, timeout: Duration) -> Self {
+        self.release_timeout = timeout;
+        self
+    }
+
+    /// Record a key event received at `now`
+    pub fn record(&mut self, key: &KeyEvent, now: Instant) {
+        match key.kind {
+            KeyEventKind::Press | KeyEventKind::Repeat => {
+                self.down.insert(key.code, now);
+            }
+            KeyEventKind::Release => {
+                self.release_supported = true;
+                self.down.remove(&key.code);
+            }
+        }
+    }
+
+    /// Release keys that timed out, on terminals that don't report releases
+    ///
+    /// Returns whether any key was released.
+    pub fn expire(&mut self, now: Instant) -> bool {
+        if self.release_supported {
+            return false;
+        }
+        let timeout = self.release_timeout;
+        let before = self.down.len();
+        self.down
+            .retain(|_, last_seen| now.saturating_duration_since(*last_seen) < timeout);
+        self.down.len() < before
+    }
+
+    /// Check whether a key is currently considered held down
+    #[must_use]
+    pub fn is_down(&self, code: KeyCode) -> bool {
+        self.down.contains_key(&code)
+    }
+
+    /// Check whether release events have been seen, disabling the timeout fallback
+    #[must_use]
+    pub fn release_supported(&self) -> bool {
+        self.release_supported
+    }
+
+    /// Forget all held keys, e.g. when the terminal loses focus
+    pub fn clear(&mut self) {
+        self.down.clear();
+    }
+}
+
+impl Default for KeyRepeatTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Coalesces bursts of values, yielding only the latest once input goes quiet
+///
+/// Each [`push`](Self::push) replaces the pending value and restarts the quiet period,
+/// so a steady stream of values arriving faster than the period yields nothing until
+/// it stops. Set [`max_wait`](Self::max_wait) to yield the latest value periodically
+/// during such a stream instead.
+#[derive(Debug, Clone)]
+pub struct Debouncer<T> {
+    quiet: Duration,
+    max_wait: Option<Duration>,
+    pending: Option<T>,
+    first_push: Option<Instant>,
+    last_push: Option<Instant>,
+}
+
+impl<T> Debouncer<T> {
+    /// Create a debouncer that waits for `quiet` without new values before yielding
+    #[must_use]
+    pub fn new(quiet: Duration) -> Self {
+        Self {
+            quiet,
+            max_wait: None,
+            pending: None,
+            first_push: None,
+            last_push: None,
+        }
+    }
+
+    /// Yield the latest value once it has been pending this long, even mid-burst
+    #[must_use]
+    pub fn max_wait(mut self, max_wait: Duration) -> Self {
+        self.max_wait = Some(max_wait);
+        self
+    }
+
+    /// Replace the pending value and restart the quiet period
+    pub fn push(&mut self, value: T, now: Instant) {
+        self.pending = Some(value);
+        self.first_push.get_or_insert(now);
+        self.last_push = Some(now);
+    }
+
+    /// Take the pending value if the quiet period, or the max wait, has passed
+    pub fn poll(&mut self, now: Instant) -> Option<T> {
+        let deadline = self.deadline()?;
+        if now < deadline {
+            return None;
+        }
+        self.first_push = None;
+        self.last_push = None;
+        self.pending.take()
+    }
+
+    /// Get the time at which the pending value will be yielded, if one is pending
+    ///
+    /// Useful as a poll timeout so the value is picked up as soon as it is ready.
+    #[must_use]
+    pub fn deadline(&self) -> Option<Instant> {
+        self.pending.as_ref()?;
+        let quiet = self.last_push? + self.quiet;
+        Some(match (self.max_wait, self.first_push) {
+            (Some(max_wait), Some(first)) => quiet.min(first + max_wait),
+            _ => quiet,
+        })
+    }
+
+    /// Check whether a value is waiting to be yielded
+    #[must_use]
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Drop the pending value without yielding it
+    pub fn cancel(&mut self) {
+        self.pending = None;
+        self.first_push = None;
+        self.last_push = None;
+    }
+}
+
+/// Bounded history of submitted input values with Up/Down recall
+///
+/// Navigation starts at the "present", the text being typed. Moving back saves that
+/// draft and yields older entries; moving forward past the newest entry restores it.
+/// If the user edits a recalled entry and then navigates, the edited text becomes the
+/// new draft and navigation restarts from the newest entry, so the history itself is
+/// never modified.
+#[derive(Debug, Clone)]
+pub struct InputHistory {
+    entries: VecDeque<String>,
+    capacity: usize,
+    position: Option<usize>,
+    draft: String,
+}
+
+impl InputHistory {
+    /// Create an empty history holding up to 100 entries
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            entries: VecDeque::new(),
+            capacity: 100,
+            position: None,
+            draft: String::new(),
+        }
+    }
+
+    /// Set the maximum number of entries kept, dropping the oldest first
+    #[must_use]
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self.entries.truncate(self.capacity);
+        self
+    }
+
+    /// Record a submitted value and return to the present
+    ///
+    /// Empty values and repeats of the newest entry are not stored.
+    pub fn push_history(&mut self, value: impl Into<String>) {
+        let value = value.into();
+        self.position = None;
+        self.draft.clear();
+        if value.is_empty() || self.entries.front() == Some(&value) {
+            return;
+        }
+        self.entries.push_front(value);
+        self.entries.truncate(self.capacity);
+    }
+
+    /// Step back to an older entry, given the text currently in the input
+    ///
+    /// Returns the text to show, or `None` if there is nothing older.
+    pub fn previous(&mut self, current: &str) -> Option<&str> {
+        self.sync(current);
+        let next = self.position.map_or(0, |index| index + 1);
+        if next >= self.entries.len() {
+            return None;
+        }
+        if self.position.is_none() {
+            current.clone_into(&mut self.draft);
+        }
+        self.position = Some(next);
+        Some(&self.entries[next])
+    }
+
+    /// Step forward to a newer entry, given the text currently in the input
+    ///
+    /// Moving past the newest entry returns the saved draft. Returns `None` if
+    /// already at the present.
+    pub fn next(&mut self, current: &str) -> Option<&str> {
+        self.sync(current);
+        match self.position? {
+            0 => {
+                self.position = None;
+                Some(&self.draft)
+            }
+            index => {
+                self.position = Some(index - 1);
+                Some(&self.entries[index - 1])
+            }
+        }
+    }
+
+    /// Map Up and Down to [`previous`](Self::previous) and [`next`](Self::next)
+    ///
+    /// Returns the replacement text for the input, or `None` if the key was not a
+    /// history key or there was nowhere to move.
+    pub fn handle_key(&mut self, key: &KeyEvent, current: &str) -> Option<String> {
+        if key.kind == KeyEventKind::Release {
+            return None;
+        }
+        match key.code {
+            KeyCode::Up => self.previous(current).map(str::to_owned),
+            KeyCode::Down => self.next(current).map(str::to_owned),
+            _ => None,
+        }
+    }
+
+    /// Check whether an entry is currently recalled rather than the draft
+    #[must_use]
+    pub fn is_browsing(&self) -> bool {
+        self.position.is_some()
+    }
+
+    /// Iterate over the stored entries, newest first
+    pub fn entries(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(String::as_str)
+    }
+
+    /// Number of stored entries
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check whether no entries are stored
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Treat an edited recalled entry as a new draft
+    fn sync(&mut self, current: &str) {
+        if let Some(index) = self.position
+            && self.entries[index] != current
+        {
+            current.clone_into(&mut self.draft);
+            self.position = None;
+        }
+    }
+}
+
+impl Default for InputHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An entry in a [`FocusRing`]
+#[derive(Debug, Clone)]
+struct FocusEntry {
+    id: String,
+    enabled: bool,
+}
+
+/// Keyboard focus moving through an ordered set of widgets
+///
+/// Each focusable widget is registered by an identifier, and widgets check
+/// [`is_focused`](Self::is_focused) to pick their focused style. Moving past either end
+/// wraps around, and disabled entries are skipped. The first enabled entry added takes
+/// focus.
+#[derive(Debug, Clone, Default)]
+pub struct FocusRing {
+    entries: Vec<FocusEntry>,
+    focused: Option<usize>,
+}
+
+impl FocusRing {
+    /// Create an empty focus ring
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a focusable entry after the existing ones
+    #[must_use]
+    pub fn with(mut self, id: impl Into<String>) -> Self {
+        self.push(id);
+        self
+    }
+
+    /// Add a focusable entry after the existing ones
+    pub fn push(&mut self, id: impl Into<String>) {
+        self.entries.push(FocusEntry {
+            id: id.into(),
+            enabled: true,
+        });
+        if self.focused.is_none() {
+            self.focused = Some(self.entries.len() - 1);
+        }
+    }
+
+    /// Enable or disable an entry, moving focus on if it was focused
+    pub fn set_enabled(&mut self, id: &str, enabled: bool) {
+        let Some(index) = self.position(id) else {
+            return;
+        };
+        self.entries[index].enabled = enabled;
+        match self.focused {
+            Some(focused) if focused == index && !enabled => {
+                self.focused = self.step(index, 1);
+            }
+            None if enabled => self.focused = Some(index),
+            _ => {}
+        }
+    }
+
+    /// Check whether an entry can take focus
+    #[must_use]
+    pub fn is_enabled(&self, id: &str) -> bool {
+        self.position(id)
+            .is_some_and(|index| self.entries[index].enabled)
+    }
+
+    /// Focus the given entry, returning whether it could take focus
+    pub fn focus(&mut self, id: &str) -> bool {
+        match self.position(id) {
+            Some(index) if self.entries[index].enabled => {
+                self.focused = Some(index);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// The identifier of the focused entry
+    #[must_use]
+    pub fn focused(&self) -> Option<&str> {
+        self.focused.map(|index| self.entries[index].id.as_str())
+    }
+
+    /// Check whether the given entry has focus
+    #[must_use]
+    pub fn is_focused(&self, id: &str) -> bool {
+        self.focused() == Some(id)
+    }
+
+    /// Move focus to the next enabled entry, wrapping after the last
+    pub fn focus_next(&mut self) -> Option<&str> {
+        self.advance(1)
+    }
+
+    /// Move focus to the previous enabled entry, wrapping before the first
+    pub fn focus_prev(&mut self) -> Option<&str> {
+        self.advance(self.entries.len().saturating_sub(1))
+    }
+
+    /// Map Tab to [`focus_next`](Self::focus_next) and Shift-Tab to
+    /// [`focus_prev`](Self::focus_prev)
+    ///
+    /// Returns whether the key moved focus, so the caller knows not to pass it on.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        if key.kind == KeyEventKind::Release {
+            return false;
+        }
+        let before = self.focused;
+        match key.code {
+            KeyCode::BackTab => self.focus_prev(),
+            KeyCode::Tab if key.modifiers.contains(KeyModifiers::SHIFT) => self.focus_prev(),
+            KeyCode::Tab => self.focus_next(),
+            _ => return false,
+        };
+        self.focused != before
+    }
+
+    /// Move focus `offset` entries forward (modulo the length) to an enabled entry
+    fn advance(&mut self, offset: usize) -> Option<&str> {
+        // Nothing is focused only when no entry is enabled
+        self.focused = self.step(self.focused?, offset);
+        self.focused()
+    }
+
+    /// Find the first enabled entry walking from `start` in steps of `offset`
+    fn step(&self, start: usize, offset: usize) -> Option<usize> {
+        let len = self.entries.len();
+        (1..=len)
+            .map(|n| (start + n * offset) % len)
+            .find(|&index| self.entries[index].enabled)
+    }
+
+    /// Index of the entry with the given identifier
+    fn position(&self, id: &str) -> Option<usize> {
+        self.entries.iter().position(|entry| entry.id == id)
+    }
+}
+
+/// Arrow key movement over items laid out in rows, such as the cells of a
+/// [`ResponsiveGrid`](crate::ResponsiveGrid)
+///
+/// Items are numbered row by row, as with [`FillOrder::RowMajor`](crate::FillOrder).
+/// The last row may be shorter than the rest: moving down into it from a column it
+/// doesn't reach lands on its last item. Without wrapping, moves past an edge leave the
+/// selection where it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridNavigator {
+    columns: usize,
+    item_count: usize,
+    wrap: bool,
+}
+
+impl GridNavigator {
+    /// Create a navigator for `item_count` items in rows of `columns`
+    ///
+    /// Use [`ResponsiveGrid::columns`](crate::ResponsiveGrid::columns) for the column
+    /// count of a responsive grid. Fewer than one column is treated as one.
+    #[must_use]
+    pub fn new(columns: usize, item_count: usize) -> Self {
+        Self {
+            columns: columns.max(1),
+            item_count,
+            wrap: false,
+        }
+    }
+
+    /// Set whether moving past an edge wraps to the other end of the row or column
+    #[must_use]
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Number of rows, counting a partial last row
+    #[must_use]
+    pub fn rows(&self) -> usize {
+        self.item_count.div_ceil(self.columns)
+    }
+
+    /// Index of the item above `index`
+    #[must_use]
+    pub fn move_up(&self, index: usize) -> usize {
+        if self.item_count == 0 {
+            return index;
+        }
+        let index = self.clamp(index);
+        if index >= self.columns {
+            return index - self.columns;
+        }
+        if !self.wrap {
+            return index;
+        }
+        // The bottom of this column, which is one row up if the last row is too short
+        let bottom = (self.rows() - 1) * self.columns + index;
+        if bottom < self.item_count {
+            bottom
+        } else {
+            bottom - self.columns
+        }
+    }
+
+    /// Index of the item below `index`
+    ///
+    /// From a column the partial last row doesn't reach, this is the last item.
+    #[must_use]
+    pub fn move_down(&self, index: usize) -> usize {
+        if self.item_count == 0 {
+            return index;
+        }
+        let index = self.clamp(index);
+        let row = index / self.columns;
+        if row + 1 < self.rows() {
+            (index + self.columns).min(self.item_count - 1)
+        } else if self.wrap {
+            index % self.columns
+        } else {
+            index
+        }
+    }
+
+    /// Index of the item left of `index`
+    #[must_use]
+    pub fn move_left(&self, index: usize) -> usize {
+        if self.item_count == 0 {
+            return index;
+        }
+        let index = self.clamp(index);
+        if !index.is_multiple_of(self.columns) {
+            index - 1
+        } else if self.wrap {
+            self.row_end(index)
+        } else {
+            index
+        }
+    }
+
+    /// Index of the item right of `index`
+    #[must_use]
+    pub fn move_right(&self, index: usize) -> usize {
+        if self.item_count == 0 {
+            return index;
+        }
+        let index = self.clamp(index);
+        if index < self.row_end(index) {
+            index + 1
+        } else if self.wrap {
+            index - index % self.columns
+        } else {
+            index
+        }
+    }
+
+    /// Move `selected` with the arrow keys
+    ///
+    /// Returns whether the key changed the selection, so the caller knows not to pass
+    /// it on.
+    pub fn handle_key(&self, key: &KeyEvent, selected: &mut usize) -> bool {
+        if key.kind == KeyEventKind::Release || self.item_count == 0 {
+            return false;
+        }
+        let moved = match key.code {
+            KeyCode::Up => self.move_up(*selected),
+            KeyCode::Down => self.move_down(*selected),
+            KeyCode::Left => self.move_left(*selected),
+            KeyCode::Right => self.move_right(*selected),
+            _ => return false,
+        };
+        let changed = moved != *selected;
+        *selected = moved;
+        changed
+    }
+
+    /// Bring an index into range, treating an empty grid as a single slot
+    fn clamp(&self, index: usize) -> usize {
+        index.min(self.item_count.saturating_sub(1))
+    }
+
+    /// Index of the last item in the row containing `index`
+    fn row_end(&self, index: usize) -> usize {
+        let start = index - index % self.columns;
+        (start + self.columns - 1).min(self.item_count.saturating_sub(1))
+    }
+}
+
+/// A key binding registered in a [`KeyMap`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+    action: String,
+    description: String,
+}
+
+impl KeyBinding {
+    /// Get the bound key code
+    #[must_use]
+    pub fn code(&self) -> KeyCode {
+        self.code
+    }
+
+    /// Get the modifiers that must be held
+    #[must_use]
+    pub fn modifiers(&self) -> KeyModifiers {
+        self.modifiers
+    }
+
+    /// Get the name of the action the key triggers
+    #[must_use]
+    pub fn action(&self) -> &str {
+        &self.action
+    }
+
+    /// Get the description shown in help
+    #[must_use]
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Format the key for display, e.g. `Ctrl+S` or `Shift+Tab`
+    #[must_use]
+    pub fn label(&self) -> String {
+        key_label(self.code, self.modifiers)
+    }
+}
+
+/// A registry of key bindings that maps keys to named actions
+///
+/// Keeping bindings here lets the same table drive both dispatch and the help
+/// screen. Shift is ignored for character keys, since terminals disagree on
+/// whether to report it and the character's case already carries it.
+#[derive(Debug, Clone, Default)]
+pub struct KeyMap {
+    bindings: Vec<KeyBinding>,
+}
+
+impl KeyMap {
+    /// Create an empty key map
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bind a key to an action, replacing any existing binding for that key
+    #[must_use]
+    pub fn bind(
+        mut self,
+        code: KeyCode,
+        modifiers: KeyModifiers,
+        action: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        let (code, modifiers) = normalize_key(code, modifiers);
+        self.bindings
+            .retain(|binding| (binding.code, binding.modifiers) != (code, modifiers));
+        self.bindings.push(KeyBinding {
+            code,
+            modifiers,
+            action: action.into(),
+            description: description.into(),
+        });
+        self
+    }
+
+    /// Find the action bound to a key event
+    ///
+    /// Release events never match.
+    #[must_use]
+    pub fn lookup(&self, key: &KeyEvent) -> Option<&str> {
+        if key.kind == KeyEventKind::Release {
+            return None;
+        }
+        let (code, modifiers) = normalize_key(key.code, key.modifiers);
+        self.bindings
+            .iter()
+            .find(|binding| binding.code == code && binding.modifiers == modifiers)
+            .map(KeyBinding::action)
+    }
+
+    /// Iterate over the bindings in the order they were added
+    pub fn bindings(&self) -> impl Iterator<Item = &KeyBinding> {
+        self.bindings.iter()
+    }
+
+    /// Build a two-column help listing of keys and descriptions
+    ///
+    /// Keys bound to the same action share a row, e.g. `k/↑  Move up`. The key column
+    /// is padded to its widest entry so the descriptions line up.
+    #[must_use]
+    pub fn help_lines(&self) -> Vec<Line<'static>> {
+        let mut rows: Vec<(String, &str, &str)> = Vec::new();
+        for binding in &self.bindings {
+            match rows
+                .iter_mut()
+                .find(|(_, action, _)| *action == binding.action)
+            {
+                Some((keys, _, _)) => {
+                    keys.push('/');
+                    keys.push_str(&binding.label());
+                }
+                None => rows.push((binding.label(), &binding.action, &binding.description)),
+            }
+        }
+
+        let key_width = rows
+            .iter()
+            .map(|(keys, _, _)| keys.chars().count())
+            .max()
+            .unwrap_or(0);
+        rows.into_iter()
+            .map(|(keys, _, description)| {
+                Line::from(vec![
+                    Span::styled(
+                        format!("{keys:<key_width$}  "),
+                        Style::new().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(description.to_owned()),
+                ])
+            })
+            .collect()
+    }
+}
+
+/// What a [`ModeMachine`] handler did with a key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModeOutcome<M> {
+    /// The key means nothing in this mode
+    Ignored,
+    /// The key was used and the mode stays the same
+    Handled,
+    /// The key was used and the machine should switch to another mode
+    Switch(M),
+}
+
+/// Handles keys for one mode of a [`ModeMachine`]
+type ModeHandler<M> = Box<dyn FnMut(&KeyEvent) -> ModeOutcome<M>>;
+
+/// The current input mode of a modal UI, and which keys do what in each mode
+///
+/// Modes are any type the app defines, usually an enum such as `Normal`, `Insert`,
+/// and `Command`. Keys bound with [`transition`](Self::transition) switch modes
+/// directly; any other key goes to the handler registered for the current mode, which
+/// can also ask to switch. Shift is ignored for character keys, as in [`KeyMap`].
+pub struct ModeMachine<M> {
+    mode: M,
+    transitions: Vec<(M, KeyCode, KeyModifiers, M)>,
+    handlers: Vec<(M, ModeHandler<M>)>,
+}
+
+impl<M: Clone + PartialEq> ModeMachine<M> {
+    /// Create a machine starting in `initial` with no bindings
+    #[must_use]
+    pub fn new(initial: M) -> Self {
+        Self {
+            mode: initial,
+            transitions: Vec::new(),
+            handlers: Vec::new(),
+        }
+    }
+
+    /// Switch from one mode to another when a key is pressed in it
+    ///
+    /// Replaces any transition already bound to that key in `from`.
+    #[must_use]
+    pub fn transition(mut self, from: M, code: KeyCode, modifiers: KeyModifiers, to: M) -> Self {
+        let (code, modifiers) = normalize_key(code, modifiers);
+        self.transitions
+            .retain(|(mode, c, m, _)| (mode, *c, *m) != (&from, code, modifiers));
+        self.transitions.push((from, code, modifiers, to));
+        self
+    }
+
+    /// Handle the keys pressed in a mode that no transition claims
+    ///
+    /// Replaces any handler already registered for the mode.
+    #[must_use]
+    pub fn handler(
+        mut self,
+        mode: M,
+        handler: impl FnMut(&KeyEvent) -> ModeOutcome<M> + 'static,
+    ) -> Self {
+        self.handlers.retain(|(existing, _)| *existing != mode);
+        self.handlers.push((mode, Box::new(handler)));
+        self
+    }
+
+    /// Get the current mode
+    #[must_use]
+    pub fn mode(&self) -> &M {
+        &self.mode
+    }
+
+    /// Check whether the machine is in `mode`
+    #[must_use]
+    pub fn is(&self, mode: &M) -> bool {
+        self.mode == *mode
+    }
+
+    /// Switch to a mode without a key
+    pub fn set_mode(&mut self, mode: M) {
+        self.mode = mode;
+    }
+
+    /// Send a key to the current mode, switching modes if it asks to
+    ///
+    /// Returns whether the key was used, so the caller knows not to pass it on.
+    /// Release events are never used.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        if key.kind == KeyEventKind::Release {
+            return false;
+        }
+        let (code, modifiers) = normalize_key(key.code, key.modifiers);
+        let bound = self
+            .transitions
+            .iter()
+            .find(|(mode, c, m, _)| *mode == self.mode && *c == code && *m == modifiers);
+        let outcome = match bound {
+            Some((_, _, _, to)) => ModeOutcome::Switch(to.clone()),
+            None => match self
+                .handlers
+                .iter_mut()
+                .find(|(mode, _)| *mode == self.mode)
+            {
+                Some((_, handler)) => handler(key),
+                None => ModeOutcome::Ignored,
+            },
+        };
+        match outcome {
+            ModeOutcome::Ignored => false,
+            ModeOutcome::Handled => true,
+            ModeOutcome::Switch(mode) => {
+                self.mode = mode;
+                true
+            }
+        }
+    }
+}
+
+impl<M: std::fmt::Debug> std::fmt::Debug for ModeMachine<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ModeMachine")
+            .field("mode", &self.mode)
+            .field("transitions", &self.transitions)
+            .field(
+                "handlers",
+                &self
+                    .handlers
+                    .iter()
+                    .map(|(mode, _)| mode)
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+/// What a [`ChordRecognizer`] made of a key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChordOutcome<'a> {
+    /// The key doesn't start or continue a chord and should be handled as usual
+    Unmatched,
+    /// The key was taken as part of a chord that isn't finished yet
+    Pending,
+    /// The key finished a chord bound to this action
+    Matched(&'a str),
+    /// The key ended a buffered chord bound to this action without continuing it
+    ///
+    /// The key itself hasn't been handled yet; pass it to
+    /// [`handle_key`](ChordRecognizer::handle_key) again after running the action.
+    Flushed(&'a str),
+}
+
+/// A node of the [`ChordRecognizer`] prefix tree
+#[derive(Debug, Clone, Default)]
+struct ChordNode {
+    children: Vec<((KeyCode, KeyModifiers), ChordNode)>,
+    action: Option<String>,
+}
+
+impl ChordNode {
+    fn child(&self, key: (KeyCode, KeyModifiers)) -> Option<&ChordNode> {
+        self.children
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, node)| node)
+    }
+}
+
+/// Recognizes multi-key sequences such as vim's `gg` and `dd`
+///
+/// Sequences are kept in a prefix tree. A key that continues a bound sequence is
+/// buffered and reported as [`Pending`](ChordOutcome::Pending) until the sequence is
+/// complete, when its action is reported. A key that doesn't continue the buffered
+/// keys clears them and is tried as the start of a new chord. The buffer is also
+/// dropped once more than the timeout passes between two keys. Shift is ignored for
+/// character keys, as in [`KeyMap`].
+///
+/// When one bound sequence is a prefix of another, such as `d` and `dd`, the shorter
+/// one is held until the timeout passes without another key and is then returned by
+/// [`poll`](Self::poll). A key that arrives late or doesn't continue the longer
+/// sequence reports the held action as [`Flushed`](ChordOutcome::Flushed) instead.
+#[derive(Debug, Clone)]
+pub struct ChordRecognizer {
+    root: ChordNode,
+    timeout: Duration,
+    pending: Vec<(KeyCode, KeyModifiers)>,
+    last_key: Option<Instant>,
+}
+
+impl ChordRecognizer {
+    /// Create a recognizer that forgets a partial chord after `timeout` between keys
+    #[must_use]
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            root: ChordNode::default(),
+            timeout,
+            pending: Vec::new(),
+            last_key: None,
+        }
+    }
+
+    /// Bind a sequence of keys to an action, replacing any action already bound to it
+    ///
+    /// An empty sequence is ignored.
+    #[must_use]
+    pub fn bind(mut self, keys: &[(KeyCode, KeyModifiers)], action: impl Into<String>) -> Self {
+        if keys.is_empty() {
+            return self;
+        }
+        let mut node = &mut self.root;
+        for &(code, modifiers) in keys {
+            let key = normalize_key(code, modifiers);
+            let index = node
+                .children
+                .iter()
+                .position(|(k, _)| *k == key)
+                .unwrap_or_else(|| {
+                    node.children.push((key, ChordNode::default()));
+                    node.children.len() - 1
+                });
+            node = &mut node.children[index].1;
+        }
+        node.action = Some(action.into());
+        self
+    }
+
+    /// Feed a key press, reporting whether it finished, continued, or missed a chord
+    ///
+    /// Release events are always unmatched and leave the buffer alone.
+    pub fn handle_key(&mut self, key: &KeyEvent, now: Instant) -> ChordOutcome<'_> {
+        if key.kind == KeyEventKind::Release {
+            return ChordOutcome::Unmatched;
+        }
+        let key = normalize_key(key.code, key.modifiers);
+        let expired = self.deadline().is_some_and(|deadline| now > deadline);
+        let continues = self.node(&self.pending).and_then(|node| node.child(key));
+        if expired || continues.is_none() {
+            // The keys so far may be a bound chord held back for a longer one
+            if self
+                .node(&self.pending)
+                .is_some_and(|node| node.action.is_some())
+            {
+                let keys = std::mem::take(&mut self.pending);
+                self.last_key = None;
+                let action = self.node(&keys).and_then(|node| node.action.as_deref());
+                return ChordOutcome::Flushed(action.unwrap_or_default());
+            }
+            self.reset();
+            if self.root.child(key).is_none() {
+                return ChordOutcome::Unmatched;
+            }
+        }
+
+        self.pending.push(key);
+        self.last_key = Some(now);
+        let Some(node) = self.node(&self.pending) else {
+            return ChordOutcome::Unmatched;
+        };
+        if !node.children.is_empty() {
+            return ChordOutcome::Pending;
+        }
+        let keys = std::mem::take(&mut self.pending);
+        self.last_key = None;
+        match self.node(&keys).and_then(|node| node.action.as_deref()) {
+            Some(action) => ChordOutcome::Matched(action),
+            None => ChordOutcome::Unmatched,
+        }
+    }
+
+    /// Drop a timed-out partial chord, returning its action if the keys so far are bound
+    ///
+    /// Call this when no key arrives, for example with [`deadline`](Self::deadline) as
+    /// the poll timeout.
+    pub fn poll(&mut self, now: Instant) -> Option<&str> {
+        if self.deadline().is_none_or(|deadline| now <= deadline) {
+            return None;
+        }
+        let keys = std::mem::take(&mut self.pending);
+        self.last_key = None;
+        self.node(&keys).and_then(|node| node.action.as_deref())
+    }
+
+    /// Get the time after which a partial chord times out, if one is buffered
+    #[must_use]
+    pub fn deadline(&self) -> Option<Instant> {
+        self.last_key
+            .filter(|_| !self.pending.is_empty())
+            .map(|last| last + self.timeout)
+    }
+
+    /// Get the keys of the partial chord, for showing in a status bar
+    #[must_use]
+    pub fn pending_keys(&self) -> &[(KeyCode, KeyModifiers)] {
+        &self.pending
+    }
+
+    /// Check whether a partial chord is buffered
+    #[must_use]
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Drop the partial chord
+    pub fn reset(&mut self) {
+        self.pending.clear();
+        self.last_key = None;
+    }
+
+    /// Walk the prefix tree along `keys`
+    fn node(&self, keys: &[(KeyCode, KeyModifiers)]) -> Option<&ChordNode> {
+        keys.iter()
+            .try_fold(&self.root, |node, &key| node.child(key))
+    }
+}
+
+/// Drop Shift from character keys and express Shift+Tab as `BackTab`
+fn normalize_key(code: KeyCode, modifiers: KeyModifiers) -> (KeyCode, KeyModifiers) {
+    match code {
+        KeyCode::Char(_) | KeyCode::BackTab => (code, modifiers - KeyModifiers::SHIFT),
+        KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => {
+            (KeyCode::BackTab, modifiers - KeyModifiers::SHIFT)
+        }
+        _ => (code, modifiers),
+    }
+}
+
+/// Format a key and its modifiers for display
+fn key_label(code: KeyCode, modifiers: KeyModifiers) -> String {
+    let mut label = String::new();
+    for (modifier, name) in [
+        (KeyModifiers::CONTROL, "Ctrl+"),
+        (KeyModifiers::ALT, "Alt+"),
+        (KeyModifiers::SUPER, "Super+"),
+        (KeyModifiers::SHIFT, "Shift+"),
+    ] {
+        if modifiers.contains(modifier) {
+            label.push_str(name);
+        }
+    }
+    let name = match code {
+        KeyCode::Char(' ') => "Space".to_owned(),
+        KeyCode::Char(c) if modifiers.is_empty() => c.to_string(),
+        KeyCode::Char(c) => c.to_ascii_uppercase().to_string(),
+        KeyCode::F(n) => format!("F{n}"),
+        KeyCode::Up => "↑".to_owned(),
+        KeyCode::Down => "↓".to_owned(),
+        KeyCode::Left => "←".to_owned(),
+        KeyCode::Right => "→".to_owned(),
+        KeyCode::BackTab => "Shift+Tab".to_owned(),
+        KeyCode::PageUp => "PgUp".to_owned(),
+        KeyCode::PageDown => "PgDn".to_owned(),
+        KeyCode::Delete => "Del".to_owned(),
+        KeyCode::Insert => "Ins".to_owned(),
+        other => format!("{other:?}"),
+    };
+    label + &name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyEventState, KeyModifiers};
+
+    fn key(code: KeyCode, kind: KeyEventKind) -> KeyEvent {
+        KeyEvent {
+            code,
+            modifiers: KeyModifiers::NONE,
+            kind,
+            state: KeyEventState::NONE,
+        }
+    }
+
+    #[test]
+    fn test_press_and_release() {
+        let start = Instant::now();
+        let mut tracker = KeyRepeatTracker::new();
+
+        tracker.record(&key(KeyCode::Left, KeyEventKind::Press), start);
+        assert!(tracker.is_down(KeyCode::Left));
+        assert!(!tracker.is_down(KeyCode::Right));
+
+        tracker.record(&key(KeyCode::Left, KeyEventKind::Release), start);
+        assert!(!tracker.is_down(KeyCode::Left));
+        assert!(tracker.release_supported());
+    }
+
+    #[test]
+    fn test_timeout_fallback() {
+        let start = Instant::now();
+        let ms = Duration::from_millis;
+        let mut tracker = KeyRepeatTracker::new().release_timeout(ms(100));
+
+        // Auto-repeat presses keep the key held
+        tracker.record(&key(KeyCode::Char('w'), KeyEventKind::Press), start);
+        tracker.expire(start + ms(80));
+        tracker.record(
+            &key(KeyCode::Char('w'), KeyEventKind::Press),
+            start + ms(80),
+        );
+        assert!(!tracker.expire(start + ms(160)));
+        assert!(tracker.is_down(KeyCode::Char('w')));
+
+        // Once the presses stop, the key is released after the timeout
+        assert!(tracker.expire(start + ms(180)));
+        assert!(!tracker.is_down(KeyCode::Char('w')));
+    }
+
+    #[test]
+    fn test_release_events_disable_timeout() {
+        let start = Instant::now();
+        let mut tracker = KeyRepeatTracker::new().release_timeout(Duration::from_millis(100));
+
+        tracker.record(&key(KeyCode::Up, KeyEventKind::Release), start);
+        tracker.record(&key(KeyCode::Down, KeyEventKind::Press), start);
+        tracker.expire(start + Duration::from_secs(5));
+        assert!(tracker.is_down(KeyCode::Down));
+    }
+
+    #[test]
+    fn test_debouncer_yields_latest_after_quiet() {
+        let start = Instant::now();
+        let ms = Duration::from_millis;
+        let mut debouncer = Debouncer::new(ms(50));
+        assert_eq!(debouncer.poll(start), None);
+
+        // A burst of resizes 10ms apart keeps pushing the deadline back
+        for (i, size) in [(80, 24), (90, 30), (100, 40)].into_iter().enumerate() {
+            let now = start + ms(10) * u32::try_from(i).unwrap();
+            debouncer.push(size, now);
+            assert_eq!(debouncer.poll(now + ms(5)), None);
+        }
+        assert_eq!(debouncer.deadline(), Some(start + ms(70)));
+        assert_eq!(debouncer.poll(start + ms(69)), None);
+        assert_eq!(debouncer.poll(start + ms(70)), Some((100, 40)));
+        assert_eq!(debouncer.poll(start + ms(200)), None);
+        assert!(!debouncer.is_pending());
+    }
+
+    #[test]
+    fn test_debouncer_max_wait_during_steady_stream() {
+        let start = Instant::now();
+        let ms = Duration::from_millis;
+        let mut debouncer = Debouncer::new(ms(50)).max_wait(ms(100));
+
+        // Values every 20ms never leave a quiet gap, but one is yielded every 100ms
+        let mut yielded = Vec::new();
+        for tick in 0..15_u32 {
+            let now = start + ms(20) * tick;
+            debouncer.push(tick, now);
+            if let Some(value) = debouncer.poll(now) {
+                yielded.push(value);
+            }
+        }
+        assert_eq!(yielded, [5, 11]);
+
+        debouncer.cancel();
+        assert_eq!(debouncer.poll(start + ms(1000)), None);
+    }
+
+    #[test]
+    fn test_history_recall_preserves_draft() {
+        let mut history = InputHistory::new();
+        history.push_history("first");
+        history.push_history("second");
+
+        assert_eq!(history.previous("draft"), Some("second"));
+        assert_eq!(history.previous("second"), Some("first"));
+        assert_eq!(history.previous("first"), None);
+        assert_eq!(history.next("first"), Some("second"));
+        assert_eq!(history.next("second"), Some("draft"));
+        assert_eq!(history.next("draft"), None);
+        assert!(!history.is_browsing());
+    }
+
+    #[test]
+    fn test_history_keys() {
+        let mut history = InputHistory::new();
+        history.push_history("ls");
+
+        let up = key(KeyCode::Up, KeyEventKind::Press);
+        let down = key(KeyCode::Down, KeyEventKind::Press);
+        assert_eq!(history.handle_key(&up, "cd"), Some("ls".to_owned()));
+        assert_eq!(history.handle_key(&down, "ls"), Some("cd".to_owned()));
+        assert_eq!(
+            history.handle_key(&key(KeyCode::Left, KeyEventKind::Press), "cd"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_history_edit_recalled_entry() {
+        let mut history = InputHistory::new();
+        history.push_history("one");
+        history.push_history("two");
+
+        assert_eq!(history.previous(""), Some("two"));
+        // Editing the recalled entry makes it the draft; the history is unchanged
+        assert_eq!(history.previous("two!"), Some("two"));
+        assert_eq!(history.next("two"), Some("two!"));
+        assert_eq!(history.entries().collect::<Vec<_>>(), ["two", "one"]);
+    }
+
+    #[test]
+    fn test_history_bounded_and_deduplicated() {
+        let mut history = InputHistory::new().capacity(2);
+        history.push_history("a");
+        history.push_history("b");
+        history.push_history("b");
+        history.push_history("");
+        history.push_history("c");
+        assert_eq!(history.entries().collect::<Vec<_>>(), ["c", "b"]);
+    }
+
+    #[test]
+    fn test_focus_tab_wraps_past_last() {
+        let mut focus = FocusRing::new().with("name").with("email").with("submit");
+        focus.set_enabled("email", false);
+        assert_eq!(focus.focused(), Some("name"));
+
+        let tab = key(KeyCode::Tab, KeyEventKind::Press);
+        assert!(focus.handle_key(&tab));
+        assert!(focus.is_focused("submit"));
+        assert!(focus.handle_key(&tab));
+        assert!(focus.is_focused("name"));
+        assert!(!focus.handle_key(&key(KeyCode::Enter, KeyEventKind::Press)));
+
+        // Disabling the focused entry passes focus on
+        focus.set_enabled("name", false);
+        assert_eq!(focus.focused(), Some("submit"));
+        assert!(!focus.handle_key(&tab));
+    }
+
+    #[test]
+    fn test_focus_shift_tab_wraps_before_first() {
+        let mut focus = FocusRing::new().with("a").with("b").with("c");
+        let shift_tab = key(KeyCode::BackTab, KeyEventKind::Press);
+        assert!(focus.handle_key(&shift_tab));
+        assert_eq!(focus.focused(), Some("c"));
+        assert!(focus.handle_key(&shift_tab));
+        assert_eq!(focus.focused(), Some("b"));
+
+        let mut tab = key(KeyCode::Tab, KeyEventKind::Press);
+        tab.modifiers = KeyModifiers::SHIFT;
+        assert!(focus.handle_key(&tab));
+        assert_eq!(focus.focused(), Some("a"));
+        assert_eq!(focus.focus_prev(), Some("c"));
+    }
+
+    #[test]
+    fn test_grid_down_into_partial_row() {
+        // 0 1 2
+        // 3 4 5
+        // 6
+        let grid = GridNavigator::new(3, 7);
+        assert_eq!(grid.rows(), 3);
+        assert_eq!(grid.move_down(3), 6);
+        assert_eq!(grid.move_down(5), 6);
+        assert_eq!(grid.move_down(6), 6);
+        assert_eq!(grid.move_right(6), 6);
+        assert_eq!(grid.move_right(4), 5);
+        assert_eq!(grid.move_right(5), 5);
+
+        let wrapping = grid.wrap(true);
+        assert_eq!(wrapping.move_down(6), 0);
+        assert_eq!(wrapping.move_right(5), 3);
+        assert_eq!(wrapping.move_left(6), 6);
+    }
+
+    #[test]
+    fn test_grid_up_out_of_partial_row() {
+        let grid = GridNavigator::new(3, 8);
+        assert_eq!(grid.move_up(7), 4);
+        assert_eq!(grid.move_up(1), 1);
+
+        // Wrapping up from a column the last row doesn't reach stops a row higher
+        let wrapping = grid.wrap(true);
+        assert_eq!(wrapping.move_up(1), 7);
+        assert_eq!(wrapping.move_up(2), 5);
+
+        let mut selected = 7;
+        assert!(grid.handle_key(&key(KeyCode::Up, KeyEventKind::Press), &mut selected));
+        assert_eq!(selected, 4);
+        assert!(!grid.handle_key(&key(KeyCode::Up, KeyEventKind::Release), &mut selected));
+        assert!(
+            !GridNavigator::new(3, 0)
+                .handle_key(&key(KeyCode::Up, KeyEventKind::Press), &mut selected)
+        );
+
+        // An empty grid leaves the index alone in every direction
+        let empty = GridNavigator::new(3, 0).wrap(true);
+        assert_eq!(empty.move_up(0), 0);
+        assert_eq!(empty.move_down(0), 0);
+        assert_eq!(empty.move_left(0), 0);
+        assert_eq!(empty.move_right(0), 0);
+    }
+
+    #[test]
+    fn test_chord_recognizer_two_key_chord() {
+        let none = KeyModifiers::NONE;
+        let mut chords = ChordRecognizer::new(Duration::from_millis(500))
+            .bind(
+                &[(KeyCode::Char('g'), none), (KeyCode::Char('g'), none)],
+                "top",
+            )
+            .bind(
+                &[(KeyCode::Char('d'), none), (KeyCode::Char('d'), none)],
+                "delete",
+            );
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+        let press = |c| key(KeyCode::Char(c), KeyEventKind::Press);
+
+        assert_eq!(chords.handle_key(&press('g'), at(0)), ChordOutcome::Pending);
+        assert_eq!(
+            chords.handle_key(&press('g'), at(300)),
+            ChordOutcome::Matched("top")
+        );
+        assert!(!chords.is_pending());
+
+        // A key that breaks a chord is passed on, and can start another one
+        assert_eq!(
+            chords.handle_key(&press('g'), at(400)),
+            ChordOutcome::Pending
+        );
+        assert_eq!(
+            chords.handle_key(&press('x'), at(500)),
+            ChordOutcome::Unmatched
+        );
+        assert!(!chords.is_pending());
+        chords.handle_key(&press('g'), at(600));
+        assert_eq!(
+            chords.handle_key(&press('d'), at(700)),
+            ChordOutcome::Pending
+        );
+        assert_eq!(chords.pending_keys(), [(KeyCode::Char('d'), none)]);
+        assert_eq!(
+            chords.handle_key(&press('d'), at(800)),
+            ChordOutcome::Matched("delete")
+        );
+    }
+
+    #[test]
+    fn test_chord_recognizer_timeout_discards_partial_chord() {
+        let none = KeyModifiers::NONE;
+        let mut chords = ChordRecognizer::new(Duration::from_millis(500))
+            .bind(
+                &[(KeyCode::Char('g'), none), (KeyCode::Char('g'), none)],
+                "top",
+            )
+            .bind(&[(KeyCode::Char('d'), none)], "cut")
+            .bind(
+                &[(KeyCode::Char('d'), none), (KeyCode::Char('d'), none)],
+                "delete",
+            );
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+        let press = |c| key(KeyCode::Char(c), KeyEventKind::Press);
+
+        chords.handle_key(&press('g'), at(0));
+        assert_eq!(chords.deadline(), Some(at(500)));
+        // Too slow: the first 'g' is forgotten and this one starts over
+        assert_eq!(
+            chords.handle_key(&press('g'), at(501)),
+            ChordOutcome::Pending
+        );
+        assert_eq!(chords.poll(at(1100)), None);
+        assert!(!chords.is_pending());
+
+        // A bound prefix of a longer chord fires once the timeout passes
+        chords.handle_key(&press('d'), at(2000));
+        assert_eq!(chords.poll(at(2500)), None);
+        assert_eq!(chords.poll(at(2501)), Some("cut"));
+
+        // A key that doesn't continue the longer chord flushes the held one first
+        chords.handle_key(&press('d'), at(3000));
+        assert_eq!(
+            chords.handle_key(&press('j'), at(3100)),
+            ChordOutcome::Flushed("cut")
+        );
+        assert!(!chords.is_pending());
+        assert_eq!(
+            chords.handle_key(&press('j'), at(3100)),
+            ChordOutcome::Unmatched
+        );
+
+        // So does a late key, even when poll wasn't called in between
+        chords.handle_key(&press('d'), at(4000));
+        assert_eq!(
+            chords.handle_key(&press('d'), at(4501)),
+            ChordOutcome::Flushed("cut")
+        );
+        assert_eq!(
+            chords.handle_key(&press('d'), at(4501)),
+            ChordOutcome::Pending
+        );
+    }
+
+    #[test]
+    fn test_mode_machine_routes_keys_to_active_mode() {
+        #[derive(Debug, Clone, PartialEq)]
+        enum Mode {
+            Normal,
+            Insert,
+        }
+
+        let typed = std::rc::Rc::new(std::cell::RefCell::new(String::new()));
+        let buffer = std::rc::Rc::clone(&typed);
+        let mut modes = ModeMachine::new(Mode::Normal)
+            .transition(
+                Mode::Normal,
+                KeyCode::Char('i'),
+                KeyModifiers::NONE,
+                Mode::Insert,
+            )
+            .transition(Mode::Insert, KeyCode::Esc, KeyModifiers::NONE, Mode::Normal)
+            .handler(Mode::Insert, move |key| match key.code {
+                KeyCode::Char(c) => {
+                    buffer.borrow_mut().push(c);
+                    ModeOutcome::Handled
+                }
+                _ => ModeOutcome::Ignored,
+            });
+        let press = |code| key(code, KeyEventKind::Press);
+
+        // Normal mode has no handler, so other keys are left for the caller
+        assert!(!modes.handle_key(&press(KeyCode::Char('x'))));
+        assert!(modes.handle_key(&press(KeyCode::Char('i'))));
+        assert!(modes.is(&Mode::Insert));
+
+        // In Insert mode 'i' is typed rather than being a transition
+        assert!(modes.handle_key(&press(KeyCode::Char('h'))));
+        assert!(modes.handle_key(&press(KeyCode::Char('i'))));
+        assert!(!modes.handle_key(&key(KeyCode::Char('!'), KeyEventKind::Release)));
+        assert_eq!(*typed.borrow(), "hi");
+
+        assert!(modes.handle_key(&press(KeyCode::Esc)));
+        assert_eq!(modes.mode(), &Mode::Normal);
+    }
+
+    #[test]
+    fn test_mode_machine_handler_switches_mode() {
+        let mut modes = ModeMachine::new("normal").handler("normal", |key| {
+            if key.code == KeyCode::Char(':') {
+                ModeOutcome::Switch("command")
+            } else {
+                ModeOutcome::Ignored
+            }
+        });
+        assert!(modes.handle_key(&KeyEvent::new(KeyCode::Char(':'), KeyModifiers::SHIFT)));
+        assert!(modes.is(&"command"));
+        assert!(!modes.handle_key(&KeyEvent::new(KeyCode::Char(':'), KeyModifiers::NONE)));
+    }
+
+    #[test]
+    fn test_key_map_lookup() {
+        let map = KeyMap::new()
+            .bind(KeyCode::Char('s'), KeyModifiers::CONTROL, "save", "Save")
+            .bind(KeyCode::Char('S'), KeyModifiers::NONE, "save_as", "Save as")
+            .bind(
+                KeyCode::BackTab,
+                KeyModifiers::NONE,
+                "prev",
+                "Previous field",
+            );
+
+        let event = |code, modifiers| KeyEvent::new(code, modifiers);
+        assert_eq!(
+            map.lookup(&event(KeyCode::Char('s'), KeyModifiers::CONTROL)),
+            Some("save")
+        );
+        assert_eq!(
+            map.lookup(&event(KeyCode::Char('s'), KeyModifiers::NONE)),
+            None
+        );
+        // Terminals may or may not report Shift alongside an uppercase letter
+        assert_eq!(
+            map.lookup(&event(KeyCode::Char('S'), KeyModifiers::SHIFT)),
+            Some("save_as")
+        );
+        assert_eq!(
+            map.lookup(&event(KeyCode::BackTab, KeyModifiers::SHIFT)),
+            Some("prev")
+        );
+        assert_eq!(
+            map.lookup(&key(KeyCode::Char('S'), KeyEventKind::Release)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_key_map_help_lines() {
+        let map = KeyMap::new()
+            .bind(KeyCode::Char('k'), KeyModifiers::NONE, "up", "Move up")
+            .bind(KeyCode::Up, KeyModifiers::NONE, "up", "Move up")
+            .bind(KeyCode::Char('s'), KeyModifiers::CONTROL, "save", "Save")
+            .bind(KeyCode::Enter, KeyModifiers::NONE, "open", "Open item");
+
+        let lines: Vec<String> = map.help_lines().iter().map(ToString::to_string).collect();
+        assert_eq!(
+            lines,
+            ["k/↑     Move up", "Ctrl+S  Save", "Enter   Open item"]
+        );
+    }
+}
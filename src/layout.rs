@@ -1,6 +1,16 @@
 #![warn(clippy::all, clippy::pedantic)]
 
-use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
+
+/// The order in which [`ResponsiveGrid`] assigns items to cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FillOrder {
+    /// Fill each row left to right before starting the next
+    #[default]
+    RowMajor,
+    /// Fill each column top to bottom before starting the next
+    ColumnMajor,
+}
 
 /// Creates a responsive grid layout based on available space and number of items
 pub struct ResponsiveGrid {
@@ -8,6 +18,10 @@ pub struct ResponsiveGrid {
     pub min_column_width: u16,
     /// Maximum number of columns
     pub max_columns: u16,
+    /// Where the cells of a partially filled last row sit within the row
+    pub last_row_alignment: Alignment,
+    /// The order in which items are assigned to cells
+    pub fill_order: FillOrder,
 }
 
 impl ResponsiveGrid {
@@ -17,6 +31,8 @@ impl ResponsiveGrid {
         Self {
             min_column_width: 30,
             max_columns: 4,
+            last_row_alignment: Alignment::Left,
+            fill_order: FillOrder::RowMajor,
         }
     }
 
@@ -26,9 +42,35 @@ impl ResponsiveGrid {
         Self {
             min_column_width,
             max_columns,
+            last_row_alignment: Alignment::Left,
+            fill_order: FillOrder::RowMajor,
         }
     }
 
+    /// Set where the cells of a partially filled last row sit within the row
+    ///
+    /// The cells keep their column width; only their horizontal position changes.
+    #[must_use]
+    pub fn last_row_alignment(mut self, alignment: Alignment) -> Self {
+        self.last_row_alignment = alignment;
+        self
+    }
+
+    /// Set the order in which items are assigned to cells
+    ///
+    /// The cell geometry is the same either way; only which item gets which cell changes.
+    #[must_use]
+    pub fn fill_order(mut self, order: FillOrder) -> Self {
+        self.fill_order = order;
+        self
+    }
+
+    /// Number of columns the grid uses for an area of the given width
+    #[must_use]
+    pub fn columns(&self, width: u16) -> u16 {
+        self.calculate_columns(width)
+    }
+
     /// Calculate optimal number of columns based on available width
     fn calculate_columns(&self, width: u16) -> u16 {
         // Always ensure at least one column, even if narrower than min_column_width
@@ -86,23 +128,48 @@ impl ResponsiveGrid {
         let col_constraints =
             vec![Constraint::Ratio(1, u32::from(optimal_columns)); optimal_columns as usize];
 
-        let mut cells = Vec::with_capacity(rows * optimal_columns as usize);
+        let columns = optimal_columns as usize;
+        let mut cells = vec![Rect::default(); item_count.min(rows * columns)];
         for (row_idx, row) in vertical_chunks.iter().enumerate() {
             let horizontal_chunks = Layout::default()
                 .direction(Direction::Horizontal)
                 .constraints(col_constraints.clone())
                 .split(*row);
 
-            for col_idx in 0..optimal_columns as usize {
-                let item_idx = row_idx * optimal_columns as usize + col_idx;
-                if item_idx < item_count {
-                    cells.push(horizontal_chunks[col_idx]);
+            let mut row_items = Vec::with_capacity(columns);
+            for col_idx in 0..columns {
+                let item_idx = match self.fill_order {
+                    FillOrder::RowMajor => row_idx * columns + col_idx,
+                    FillOrder::ColumnMajor => col_idx * rows + row_idx,
+                };
+                if item_idx < cells.len() {
+                    cells[item_idx] = horizontal_chunks[col_idx];
+                    row_items.push(item_idx);
                 }
             }
+            if row_idx + 1 == rows {
+                self.align_last_row(&mut cells, &row_items, *row);
+            }
         }
 
         cells
     }
+
+    /// Shift the cells of the items in the last row to honor `last_row_alignment`
+    fn align_last_row(&self, cells: &mut [Rect], row_items: &[usize], row: Rect) {
+        let Some(right) = row_items.iter().map(|&index| cells[index].right()).max() else {
+            return;
+        };
+        let slack = row.right().saturating_sub(right);
+        let shift = match self.last_row_alignment {
+            Alignment::Left => 0,
+            Alignment::Center => slack / 2,
+            Alignment::Right => slack,
+        };
+        for &index in row_items {
+            cells[index].x += shift;
+        }
+    }
 }
 
 impl Default for ResponsiveGrid {
@@ -125,6 +192,243 @@ pub fn centered_rect_with_size(width: u16, height: u16, container: Rect) -> Rect
     )
 }
 
+/// A corner of a container, for placing small overlays
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Corner {
+    TopLeft,
+    #[default]
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// Creates a rectangle with specified dimensions in a corner of the container
+///
+/// Like [`centered_rect_with_size`], the size is clamped to the container.
+#[must_use]
+pub fn corner_rect_with_size(width: u16, height: u16, corner: Corner, container: Rect) -> Rect {
+    let width = width.min(container.width);
+    let height = height.min(container.height);
+    let x = match corner {
+        Corner::TopLeft | Corner::BottomLeft => container.x,
+        Corner::TopRight | Corner::BottomRight => container.right() - width,
+    };
+    let y = match corner {
+        Corner::TopLeft | Corner::TopRight => container.y,
+        Corner::BottomLeft | Corner::BottomRight => container.bottom() - height,
+    };
+    Rect::new(x, y, width, height)
+}
+
+/// Split an area into content and a one-row status bar at the bottom
+///
+/// Returns `(content, status_bar)`. An empty area yields two empty rects.
+#[must_use]
+pub fn with_status_bar(area: Rect) -> (Rect, Rect) {
+    let bar_height = area.height.min(1);
+    let content = Rect {
+        height: area.height - bar_height,
+        ..area
+    };
+    let status_bar = Rect {
+        y: content.bottom(),
+        height: bar_height,
+        ..area
+    };
+    (content, status_bar)
+}
+
+/// Split an area into a one-row header, content, and a one-row status bar
+///
+/// Returns `(header, content, status_bar)`. The status bar takes priority over the
+/// header when the area is too short for both.
+#[must_use]
+pub fn with_header_and_status(area: Rect) -> (Rect, Rect, Rect) {
+    let (rest, status_bar) = with_status_bar(area);
+    let header_height = rest.height.min(1);
+    let header = Rect {
+        height: header_height,
+        ..rest
+    };
+    let content = Rect {
+        y: header.bottom(),
+        height: rest.height - header_height,
+        ..rest
+    };
+    (header, content, status_bar)
+}
+
+/// Check whether two rects share at least one cell
+///
+/// Rects that only touch along an edge do not overlap.
+#[must_use]
+pub fn rects_overlap(a: Rect, b: Rect) -> bool {
+    !a.intersection(b).is_empty()
+}
+
+/// Move `moving` to the nearest position inside `bounds` where it overlaps none of `fixed`
+///
+/// Candidate positions are the original one plus every position that lines the rect up
+/// against an edge of `bounds` or of a fixed rect; the closest free candidate wins.
+/// If there is no free position, the rect is only clamped into `bounds`.
+#[must_use]
+pub fn resolve_overlap(moving: Rect, fixed: &[Rect], bounds: Rect) -> Rect {
+    let clamp = |rect: Rect| {
+        let max_x = bounds.right().saturating_sub(rect.width).max(bounds.x);
+        let max_y = bounds.bottom().saturating_sub(rect.height).max(bounds.y);
+        Rect {
+            x: rect.x.clamp(bounds.x, max_x),
+            y: rect.y.clamp(bounds.y, max_y),
+            ..rect
+        }
+    };
+    let is_free = |rect: Rect| !fixed.iter().any(|&other| rects_overlap(rect, other));
+
+    let start = clamp(moving);
+    if is_free(start) {
+        return start;
+    }
+
+    let mut xs = vec![
+        start.x,
+        bounds.x,
+        bounds.right().saturating_sub(moving.width),
+    ];
+    let mut ys = vec![
+        start.y,
+        bounds.y,
+        bounds.bottom().saturating_sub(moving.height),
+    ];
+    for other in fixed {
+        xs.extend([other.x.saturating_sub(moving.width), other.right()]);
+        ys.extend([other.y.saturating_sub(moving.height), other.bottom()]);
+    }
+
+    let distance =
+        |rect: Rect| u32::from(rect.x.abs_diff(start.x)) + u32::from(rect.y.abs_diff(start.y));
+    xs.iter()
+        .flat_map(|&x| ys.iter().map(move |&y| Rect { x, y, ..moving }))
+        .filter(|&candidate| clamp(candidate) == candidate && is_free(candidate))
+        .min_by_key(|&candidate| distance(candidate))
+        .unwrap_or(start)
+}
+
+/// Which edge of the screen a [`DashboardLayout`] sidebar sits on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Side {
+    #[default]
+    Left,
+    Right,
+}
+
+/// The regions produced by [`DashboardLayout::split`]
+///
+/// Regions that were not requested are empty rects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DashboardRegions {
+    pub header: Rect,
+    pub footer: Rect,
+    pub sidebar: Rect,
+    pub body: Rect,
+}
+
+/// Builder for a header/footer/sidebar/body screen layout
+///
+/// The header and footer span the full width; the sidebar fills the height between
+/// them, and the body takes whatever is left. When the fixed regions don't fit they
+/// shrink in proportion to their requested sizes, leaving the body empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DashboardLayout {
+    header: u16,
+    footer: u16,
+    sidebar: Option<(u16, Side)>,
+}
+
+impl DashboardLayout {
+    /// Create a layout where the body fills the whole area
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a header of the given height
+    #[must_use]
+    pub fn header(mut self, height: u16) -> Self {
+        self.header = height;
+        self
+    }
+
+    /// Add a footer of the given height
+    #[must_use]
+    pub fn footer(mut self, height: u16) -> Self {
+        self.footer = height;
+        self
+    }
+
+    /// Add a sidebar of the given width on one side of the body
+    #[must_use]
+    pub fn sidebar(mut self, width: u16, side: Side) -> Self {
+        self.sidebar = Some((width, side));
+        self
+    }
+
+    /// Split an area into the configured regions
+    #[must_use]
+    pub fn split(&self, area: Rect) -> DashboardRegions {
+        let (header_height, footer_height) = fit_pair(self.header, self.footer, area.height);
+        let header = Rect {
+            height: header_height,
+            ..area
+        };
+        let footer = Rect {
+            y: area.bottom() - footer_height,
+            height: footer_height,
+            ..area
+        };
+        let middle = Rect {
+            y: header.bottom(),
+            height: area.height - header_height - footer_height,
+            ..area
+        };
+
+        let (sidebar_width, side) = self.sidebar.unwrap_or_default();
+        let sidebar_width = sidebar_width.min(middle.width);
+        let body_width = middle.width - sidebar_width;
+        let (sidebar_x, body_x) = match side {
+            Side::Left => (middle.x, middle.x + sidebar_width),
+            Side::Right => (middle.x + body_width, middle.x),
+        };
+        let sidebar = Rect {
+            x: sidebar_x,
+            width: sidebar_width,
+            ..middle
+        };
+        let body = Rect {
+            x: body_x,
+            width: body_width,
+            ..middle
+        };
+
+        DashboardRegions {
+            header,
+            footer,
+            sidebar,
+            body,
+        }
+    }
+}
+
+/// Fit two fixed sizes into `available`, shrinking both proportionally if needed
+fn fit_pair(first: u16, second: u16, available: u16) -> (u16, u16) {
+    let total = u32::from(first) + u32::from(second);
+    if total <= u32::from(available) {
+        return (first, second);
+    }
+    let first = u32::from(first) * u32::from(available) / total;
+    let first = u16::try_from(first).unwrap_or(available);
+    (first, available - first)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -167,7 +471,7 @@ mod tests {
 
         for (items, expected_rows, expected_cols) in test_cases {
             let cells = grid.split(area, items);
-            assert_eq!(cells.len(), items, "Expected {} cells for {} items", items, items);
+            assert_eq!(cells.len(), items, "Expected {items} cells for {items} items");
             
             if items > 0 {
                 // Verify row count by checking the y coordinates
@@ -176,7 +480,7 @@ mod tests {
                     .collect::<std::collections::HashSet<_>>()
                     .len();
                 assert_eq!(unique_rows, expected_rows, 
-                    "Expected {} rows for {} items", expected_rows, items);
+                    "Expected {expected_rows} rows for {items} items");
 
                 // Verify column count by checking items in first row
                 let first_row_items = cells.iter()
@@ -218,6 +522,42 @@ mod tests {
         assert_eq!(cells.len(), 4, "Should handle zero width areas");
     }
 
+    #[test]
+    fn test_last_row_alignment() {
+        let area = Rect::new(0, 0, 200, 20);
+        let grid = ResponsiveGrid::new().last_row_alignment(Alignment::Center);
+        let cells = grid.split(area, 5);
+
+        // The first row is untouched and the lone trailing cell sits in the middle
+        assert_eq!(cells[0], Rect::new(0, 0, 50, 10));
+        assert_eq!(cells[3], Rect::new(150, 0, 50, 10));
+        assert_eq!(cells[4], Rect::new(75, 10, 50, 10));
+
+        let grid = ResponsiveGrid::new().last_row_alignment(Alignment::Right);
+        assert_eq!(
+            grid.split(area, 6)[4..],
+            [Rect::new(100, 10, 50, 10), Rect::new(150, 10, 50, 10)]
+        );
+        assert_eq!(ResponsiveGrid::new().split(area, 5)[4].x, 0);
+    }
+
+    #[test]
+    fn test_column_major_fill_order() {
+        let area = Rect::new(0, 0, 60, 30);
+        let row_major = ResponsiveGrid::new().split(area, 6);
+        let column_major = ResponsiveGrid::new()
+            .fill_order(FillOrder::ColumnMajor)
+            .split(area, 6);
+
+        // Same three-by-two geometry, but item 1 moves down instead of across
+        assert_eq!(row_major[1], Rect::new(30, 0, 30, 10));
+        assert_eq!(column_major[1], Rect::new(0, 10, 30, 10));
+        assert_eq!(column_major[3], Rect::new(30, 0, 30, 10));
+        let mut sorted = column_major.clone();
+        sorted.sort_by_key(|cell| (cell.y, cell.x));
+        assert_eq!(sorted, row_major);
+    }
+
     #[test]
     fn test_centered_rect() {
         let container = Rect::new(0, 0, 100, 100);
@@ -228,4 +568,127 @@ mod tests {
         assert_eq!(centered.width, 20);
         assert_eq!(centered.height, 20);
     }
+
+    #[test]
+    fn test_corner_rect() {
+        let container = Rect::new(10, 5, 100, 50);
+        assert_eq!(
+            corner_rect_with_size(20, 4, Corner::TopRight, container),
+            Rect::new(90, 5, 20, 4)
+        );
+        assert_eq!(
+            corner_rect_with_size(20, 4, Corner::BottomLeft, container),
+            Rect::new(10, 51, 20, 4)
+        );
+        assert_eq!(
+            corner_rect_with_size(200, 4, Corner::BottomRight, container),
+            Rect::new(10, 51, 100, 4)
+        );
+    }
+
+    #[test]
+    fn test_with_status_bar() {
+        let area = Rect::new(2, 3, 40, 10);
+        let (content, status_bar) = with_status_bar(area);
+
+        assert_eq!(status_bar, Rect::new(2, 12, 40, 1));
+        assert_eq!(content, Rect::new(2, 3, 40, 9));
+        assert_eq!(content.bottom(), status_bar.top());
+
+        let (content, status_bar) = with_status_bar(Rect::new(0, 0, 10, 0));
+        assert_eq!(content.height + status_bar.height, 0);
+    }
+
+    #[test]
+    fn test_with_header_and_status() {
+        let area = Rect::new(0, 0, 40, 10);
+        let (header, content, status_bar) = with_header_and_status(area);
+
+        assert_eq!(header, Rect::new(0, 0, 40, 1));
+        assert_eq!(content, Rect::new(0, 1, 40, 8));
+        assert_eq!(status_bar, Rect::new(0, 9, 40, 1));
+
+        // A single row goes to the status bar
+        let (header, content, status_bar) = with_header_and_status(Rect::new(0, 0, 40, 1));
+        assert_eq!(
+            (header.height, content.height, status_bar.height),
+            (0, 0, 1)
+        );
+    }
+
+    #[test]
+    fn test_rects_overlap() {
+        let a = Rect::new(0, 0, 10, 5);
+
+        assert!(rects_overlap(a, Rect::new(9, 4, 5, 5)));
+        assert!(rects_overlap(a, Rect::new(2, 1, 2, 2)));
+        // Touching edges share no cells
+        assert!(!rects_overlap(a, Rect::new(10, 0, 5, 5)));
+        assert!(!rects_overlap(a, Rect::new(0, 5, 10, 5)));
+    }
+
+    #[test]
+    fn test_resolve_overlap() {
+        let bounds = Rect::new(0, 0, 80, 24);
+        let fixed = [Rect::new(10, 5, 20, 10)];
+
+        // Overlapping the right side by 4 columns: nudged right to touch the edge
+        let moving = Rect::new(26, 7, 10, 4);
+        let resolved = resolve_overlap(moving, &fixed, bounds);
+        assert_eq!(resolved, Rect::new(30, 7, 10, 4));
+        assert!(!rects_overlap(resolved, fixed[0]));
+
+        // A free rect is left alone, and one outside the bounds is clamped in
+        let free = Rect::new(50, 0, 10, 4);
+        assert_eq!(resolve_overlap(free, &fixed, bounds), free);
+        let outside = Rect::new(75, 22, 10, 4);
+        assert_eq!(
+            resolve_overlap(outside, &fixed, bounds),
+            Rect::new(70, 20, 10, 4)
+        );
+    }
+
+    #[test]
+    fn test_resolve_overlap_between_neighbours() {
+        let bounds = Rect::new(0, 0, 40, 10);
+        let fixed = [Rect::new(0, 0, 15, 10), Rect::new(25, 0, 15, 10)];
+
+        let resolved = resolve_overlap(Rect::new(12, 3, 10, 4), &fixed, bounds);
+        assert_eq!(resolved, Rect::new(15, 3, 10, 4));
+        assert!(fixed.iter().all(|&other| !rects_overlap(resolved, other)));
+        assert!(bounds.contains(resolved.as_position()));
+    }
+
+    #[test]
+    fn test_dashboard_layout() {
+        let layout = DashboardLayout::new()
+            .header(3)
+            .footer(1)
+            .sidebar(20, Side::Left);
+        let regions = layout.split(Rect::new(0, 0, 80, 24));
+        assert_eq!(regions.header, Rect::new(0, 0, 80, 3));
+        assert_eq!(regions.footer, Rect::new(0, 23, 80, 1));
+        assert_eq!(regions.sidebar, Rect::new(0, 3, 20, 20));
+        assert_eq!(regions.body, Rect::new(20, 3, 60, 20));
+
+        let regions = DashboardLayout::new()
+            .sidebar(20, Side::Right)
+            .split(Rect::new(0, 0, 80, 24));
+        assert_eq!(regions.sidebar, Rect::new(60, 0, 20, 24));
+        assert_eq!(regions.body, Rect::new(0, 0, 60, 24));
+        assert!(regions.header.is_empty());
+    }
+
+    #[test]
+    fn test_dashboard_layout_shrinks_fixed_regions() {
+        let layout = DashboardLayout::new()
+            .header(6)
+            .footer(2)
+            .sidebar(30, Side::Left);
+        let regions = layout.split(Rect::new(0, 0, 20, 4));
+        assert_eq!(regions.header.height, 3);
+        assert_eq!(regions.footer, Rect::new(0, 3, 20, 1));
+        assert_eq!(regions.sidebar.width, 20);
+        assert!(regions.body.is_empty());
+    }
 }
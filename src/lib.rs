@@ -1,11 +1,14 @@
 #![warn(clippy::all, clippy::pedantic)]
 
 use crossterm::{
-    ExecutableCommand, event,
+    ExecutableCommand,
+    cursor::{SetCursorStyle, Show},
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use ratatui::prelude::*;
 use std::io;
+use std::sync::Once;
+use std::sync::atomic::{AtomicBool, Ordering};
 use thiserror::Error;
 
 // Re-export ratatui for use by applications
@@ -17,28 +20,52 @@ pub use ratatui::widgets::Widget;
 
 // UI Components
 mod animation;
+#[cfg(feature = "async")]
+mod async_runtime;
+mod charset;
+mod color;
+mod input;
 mod layout;
+#[cfg(feature = "record")]
+mod recording;
+pub mod rng;
+mod runtime;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+mod text;
+mod theme;
 pub mod widgets;
 
 pub use animation::*;
+#[cfg(feature = "async")]
+pub use async_runtime::*;
+pub use charset::*;
+pub use color::*;
+pub use input::*;
 pub use layout::*;
+#[cfg(feature = "record")]
+pub use recording::*;
+pub use rng::*;
+pub use runtime::*;
+pub use text::*;
+pub use theme::*;
 pub use widgets::*;
 
-pub const GAEROS_ASCII: &str = r#"
+pub const GAEROS_ASCII: &str = r"
 ▄▄ •  ▄▄▄· ▄▄▄ .▄▄▄        .▄▄ ·
 ▐█ ▀ ▪▐█ ▀█ ▀▄.▀·▀▄ █·▪     ▐█ ▀.
 ▄█ ▀█▄▄█▀▀█ ▐▀▀▪▄▐▀▀▄  ▄█▀▄ ▄▀▀▀█▄
 ▐█▄▪▐█▐█ ▪▐▌▐█▄▄▌▐█•█▌▐█▌.▐▌▐█▄▪▐█
 ·▀▀▀▀  ▀  ▀  ▀▀▀ .▀  ▀ ▀█▄▀▪ ▀▀▀▀
-"#;
+";
 
-pub const KADE_ASCII: &str = r#"
+pub const KADE_ASCII: &str = r"
  ▄ •▄  ▄▄▄· ·▄▄▄▄  ▄▄▄ .
 █▌▄▌▪▐█ ▀█ ██▪ ██ ▀▄.▀·
 ▐▀▀▄·▄█▀▀█ ▐█· ▐█▌▐▀▀▪▄
 ▐█.█▌▐█ ▪▐▌██. ██ ▐█▄▄▌
 ·▀  ▀ ▀  ▀ ▀▀▀▀▀•  ▀▀▀
-"#;
+";
 
 /// Error type for terminal operations
 #[derive(Debug, Error)]
@@ -49,6 +76,17 @@ pub enum Error {
     Terminal(#[from] anyhow::Error),
 }
 
+/// Why [`run_app`] stopped running the application
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitReason {
+    /// The built-in quit key (`q`) was pressed
+    QuitKey,
+    /// [`TerminalApp::handle_event`] returned `Ok(true)`
+    AppRequested,
+    /// The event source stopped producing events
+    EventStreamEnded,
+}
+
 /// Terminal UI application trait
 pub trait TerminalApp {
     /// Render the UI
@@ -60,6 +98,96 @@ pub trait TerminalApp {
     /// Returns an error if event handling fails.
     /// Returns Ok(true) if the application should exit, Ok(false) otherwise.
     fn handle_event(&mut self, event: Event) -> anyhow::Result<bool>;
+
+    /// Called once the terminal has settled on a new size
+    ///
+    /// Bursts of resize events are coalesced, so this receives only the final size.
+    fn on_resize(&mut self, _width: u16, _height: u16) {}
+
+    /// Called once per frame, before drawing
+    ///
+    /// This also runs for frames dropped by a [`FrameSkip`] policy, so animations
+    /// stepped here keep time even when their frames are not drawn.
+    fn on_tick(&mut self) {}
+
+    /// Whether the terminal bell should ring, checked after each [`on_tick`](Self::on_tick)
+    ///
+    /// Return `true` once per ring; apps usually clear a flag here. The bell can be
+    /// muted with [`RunConfig::bell_enabled`] and is rung on the stream set with
+    /// [`RunConfig::bell_writer`].
+    fn wants_bell(&mut self) -> bool {
+        false
+    }
+
+    /// Whether the UI has changed since the last frame was drawn
+    ///
+    /// While this returns `false`, `run_app` skips drawing and waits for events with
+    /// the longer [`RunConfig::idle_poll_interval`]. A resize, or toasts and held keys
+    /// expiring, always redraws. Animated apps should leave this returning `true`, the
+    /// default. Apps tracking a flag clear it in [`on_frame_drawn`](Self::on_frame_drawn).
+    fn is_dirty(&self) -> bool {
+        true
+    }
+
+    /// Called after each frame is drawn
+    fn on_frame_drawn(&mut self) {}
+
+    /// Called when no input has arrived for the [`RunConfig::idle_timeout`]
+    ///
+    /// Useful for starting a screensaver or logging out of a kiosk. Called again after
+    /// each further timeout without input.
+    ///
+    /// # Errors
+    /// Returns an error if the idle handling fails.
+    /// Returns Ok(true) if the application should exit, Ok(false) otherwise.
+    fn on_idle(&mut self) -> anyhow::Result<bool> {
+        Ok(false)
+    }
+
+    /// Called after each frame is drawn when [`RunConfig::collect_render_stats`] is set
+    fn on_frame_rendered(&mut self, _stats: RenderStats) {}
+
+    /// Key tracker that the run loop should keep up to date
+    ///
+    /// Return a tracker owned by the app to have `run_app` record every key event into
+    /// it and expire keys on terminals without release events.
+    fn key_tracker(&mut self) -> Option<&mut KeyRepeatTracker> {
+        None
+    }
+
+    /// Key map that the run loop should dispatch through
+    ///
+    /// Keys bound in the map are sent to [`handle_action`](Self::handle_action) by name
+    /// instead of to [`handle_event`](Self::handle_event). A binding for `q` takes
+    /// precedence over the built-in quit key.
+    fn key_map(&self) -> Option<&KeyMap> {
+        None
+    }
+
+    /// Handle an action triggered through the [`key_map`](Self::key_map)
+    ///
+    /// # Errors
+    /// Returns an error if the action fails.
+    /// Returns Ok(true) if the application should exit, Ok(false) otherwise.
+    fn handle_action(&mut self, _action: &str) -> anyhow::Result<bool> {
+        Ok(false)
+    }
+
+    /// Toast stack that the run loop should keep up to date
+    ///
+    /// Return a stack owned by the app to have `run_app` expire old toasts and, when
+    /// [`RunConfig::toast_errors`] is enabled, show event handling errors in it.
+    fn toasts(&mut self) -> Option<&mut ToastStack> {
+        None
+    }
+
+    /// Overlays that the run loop should draw above the UI
+    ///
+    /// Return a stack owned by the app to have `run_app` draw it into each frame after
+    /// [`ui`](Self::ui).
+    fn overlays(&self) -> Option<&OverlayStack> {
+        None
+    }
 }
 
 /// Setup the terminal for TUI application
@@ -70,9 +198,84 @@ pub trait TerminalApp {
 /// - Failed to enter alternate screen
 /// - Failed to create terminal
 pub fn setup_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>, Error> {
+    TerminalStream::Stdout.record();
+    setup_terminal_with(io::stdout())
+}
+
+/// Setup the terminal for TUI application, drawing to stderr
+///
+/// Useful for tools whose stdout is piped elsewhere, since the TUI then never mixes
+/// with the program's output. Pair with [`restore_terminal_on_stderr`].
+///
+/// # Errors
+/// Returns an error if:
+/// - Failed to enable raw mode
+/// - Failed to enter alternate screen
+/// - Failed to create terminal
+pub fn setup_terminal_on_stderr() -> Result<Terminal<CrosstermBackend<io::Stderr>>, Error> {
+    TerminalStream::Stderr.record();
+    setup_terminal_with(io::stderr())
+}
+
+/// Whether the terminal was last set up on stderr, so the panic hook restores that stream
+static ON_STDERR: AtomicBool = AtomicBool::new(false);
+
+/// The standard stream a terminal is set up on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TerminalStream {
+    Stdout,
+    Stderr,
+}
+
+impl TerminalStream {
+    /// The stream last set up by [`setup_terminal`] or [`setup_terminal_on_stderr`],
+    /// stdout if neither was called
+    pub(crate) fn current() -> Self {
+        if ON_STDERR.load(Ordering::Relaxed) {
+            Self::Stderr
+        } else {
+            Self::Stdout
+        }
+    }
+
+    /// Remember this stream as the one the terminal was set up on
+    fn record(self) {
+        ON_STDERR.store(self == Self::Stderr, Ordering::Relaxed);
+    }
+
+    /// Choose the writer for this stream out of the stdout and stderr writers
+    pub(crate) fn pick<'w>(
+        self,
+        stdout: &'w mut dyn io::Write,
+        stderr: &'w mut dyn io::Write,
+    ) -> &'w mut dyn io::Write {
+        match self {
+            Self::Stdout => stdout,
+            Self::Stderr => stderr,
+        }
+    }
+}
+
+/// Setup the terminal for TUI application, drawing to the given writer
+///
+/// The alternate screen is entered on the same writer the terminal draws to. Unless
+/// [`ColorDepth::set_current`] was already called, the detected color depth is set
+/// so RGB colors are reduced for terminals that can't show them.
+///
+/// # Errors
+/// Returns an error if:
+/// - Failed to enable raw mode
+/// - Failed to enter alternate screen
+/// - Failed to create terminal
+pub fn setup_terminal_with<W: io::Write>(
+    mut writer: W,
+) -> Result<Terminal<CrosstermBackend<W>>, Error> {
+    if !ColorDepth::is_set() {
+        ColorDepth::set_current(ColorDepth::detect());
+    }
     enable_raw_mode()?;
-    io::stdout().execute(EnterAlternateScreen)?;
-    Terminal::new(CrosstermBackend::new(io::stdout())).map_err(|e| Error::Terminal(e.into()))
+    writer.execute(EnterAlternateScreen)?;
+    Terminal::new(CrosstermBackend::new(writer)).map_err(|e| Error::Terminal(e.into()))
 }
 
 /// Restore terminal to original state
@@ -82,39 +285,123 @@ pub fn setup_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>, Error>
 /// - Failed to disable raw mode
 /// - Failed to leave alternate screen
 pub fn restore_terminal() -> Result<(), Error> {
-    disable_raw_mode()?;
-    io::stdout().execute(LeaveAlternateScreen)?;
-    Ok(())
+    restore_terminal_with(&mut io::stdout())
 }
 
-/// Run a terminal application
+/// Restore terminal to original state after [`setup_terminal_on_stderr`]
 ///
 /// # Errors
 /// Returns an error if:
-/// - Failed to draw to terminal
-/// - Failed to poll for events
-/// - Failed to read events
-/// - Application event handling failed
-pub fn run_app<A: TerminalApp>(
-    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
-    mut app: A,
-) -> Result<(), Error> {
-    loop {
-        terminal
-            .draw(|f| app.ui(f))
-            .map_err(|e| Error::Terminal(e.into()))?;
-
-        if event::poll(std::time::Duration::from_millis(50))? {
-            if let Event::Key(key) = event::read()? {
-                if key.code == KeyCode::Char('q') {
-                    break;
-                }
-                if app.handle_event(Event::Key(key)).map_err(Error::Terminal)? {
-                    break;
-                }
-            }
+/// - Failed to disable raw mode
+/// - Failed to leave alternate screen
+pub fn restore_terminal_on_stderr() -> Result<(), Error> {
+    restore_terminal_with(&mut io::stderr())
+}
+
+/// Restore terminal to original state, leaving the alternate screen on `writer`
+///
+/// The cursor is shown again in the shape configured in the terminal, undoing any
+/// [`CursorController`] changes.
+///
+/// # Errors
+/// Returns an error if:
+/// - Failed to disable raw mode
+/// - Failed to leave alternate screen
+/// - Failed to restore the cursor
+pub fn restore_terminal_with<W: io::Write + ?Sized>(writer: &mut W) -> Result<(), Error> {
+    disable_raw_mode()?;
+    writer.execute(LeaveAlternateScreen)?;
+    writer
+        .execute(SetCursorStyle::DefaultUserShape)?
+        .execute(Show)?;
+    Ok(())
+}
+
+/// Install a panic hook that restores the terminal before the panic message is printed
+///
+/// Without this, a panic inside a running app leaves the terminal in raw mode on the
+/// alternate screen, so the message is lost. The stream restored is the one last set up
+/// by [`setup_terminal`] or [`setup_terminal_on_stderr`], stdout if neither was called.
+/// The previously installed hook still runs afterwards. Calling this more than once has
+/// no further effect.
+pub fn install_panic_hook() {
+    static INSTALLED: Once = Once::new();
+    INSTALLED.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let (mut stdout, mut stderr) = (io::stdout(), io::stderr());
+            let _ = restore_terminal_with(TerminalStream::current().pick(&mut stdout, &mut stderr));
+            previous(info);
+        }));
+    });
+}
+
+/// Sets up the terminal on creation and restores it when dropped
+///
+/// For apps that drive the terminal without [`launch`], this makes early returns and
+/// panics leave the terminal usable. The guard can't be cloned or copied, so it is
+/// dropped exactly once and the terminal is restored exactly once.
+#[derive(Debug)]
+pub struct TerminalGuard<W: io::Write = io::Stdout> {
+    writer: W,
+}
+
+impl TerminalGuard {
+    /// Set up the terminal and install the panic hook
+    ///
+    /// # Errors
+    /// Returns an error if setting up the terminal fails. Anything already changed by
+    /// the failed setup is restored.
+    pub fn new() -> Result<(Self, Terminal<CrosstermBackend<io::Stdout>>), Error> {
+        TerminalStream::Stdout.record();
+        Self::with_writers(io::stdout(), io::stdout())
+    }
+}
+
+impl<W: io::Write> TerminalGuard<W> {
+    /// Set up the terminal on `writer`, restoring it through `restore` when dropped
+    fn with_writers(restore: W, writer: W) -> Result<(Self, Terminal<CrosstermBackend<W>>), Error> {
+        install_panic_hook();
+        // Created before setup so a half-finished setup is undone too
+        let guard = Self { writer: restore };
+        let terminal = setup_terminal_with(writer)?;
+        Ok((guard, terminal))
+    }
+}
+
+impl<W: io::Write> Drop for TerminalGuard<W> {
+    fn drop(&mut self) {
+        if let Err(error) = restore_terminal_with(&mut self.writer) {
+            eprintln!("failed to restore terminal: {error}");
         }
     }
+}
+
+/// Set up the terminal, run the app on it, and restore the terminal afterwards
+///
+/// The terminal is restored however the app stops, including when [`run_app`] returns
+/// an error or the app panics. Use [`setup_terminal`], [`run_app`], and
+/// [`restore_terminal`] directly for more control over any of the steps.
+///
+/// # Errors
+/// Returns an error if setting up the terminal or running the app fails.
+pub fn launch<A: TerminalApp>(app: A) -> Result<(), Error> {
+    launch_with(TerminalGuard::new, app, CrosstermEvents)
+}
+
+/// Run the app on the terminal returned by `setup`, reading events from `events`
+fn launch_with<W, A, E>(
+    setup: impl FnOnce() -> Result<(TerminalGuard<W>, Terminal<CrosstermBackend<W>>), Error>,
+    app: A,
+    events: E,
+) -> Result<(), Error>
+where
+    W: io::Write,
+    A: TerminalApp,
+    E: EventSource,
+{
+    let (_guard, mut terminal) = setup()?;
+    run_app_with(&mut terminal, app, events, RunConfig::default())?;
     Ok(())
 }
 
@@ -144,3 +431,110 @@ pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         ])
         .split(popup_layout[1])[1]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::terminal::is_raw_mode_enabled;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// A writer whose clones all append to the same buffer
+    #[derive(Debug, Clone, Default)]
+    struct SharedWriter(Rc<RefCell<Vec<u8>>>);
+
+    impl SharedWriter {
+        /// How many times the alternate screen was left
+        fn leave_count(&self) -> usize {
+            String::from_utf8_lossy(&self.0.borrow())
+                .matches("\x1b[?1049l")
+                .count()
+        }
+    }
+
+    impl io::Write for SharedWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_terminal_guard_drop_disables_raw_mode() {
+        if enable_raw_mode().is_err() {
+            // Raw mode needs a terminal, which test runners don't always have
+            return;
+        }
+        assert!(is_raw_mode_enabled().unwrap());
+        drop(TerminalGuard { writer: io::sink() });
+        assert!(!is_raw_mode_enabled().unwrap());
+    }
+
+    #[test]
+    fn test_terminal_guard_restores_once_on_drop() {
+        let writer = SharedWriter::default();
+        drop(TerminalGuard {
+            writer: writer.clone(),
+        });
+        assert_eq!(writer.leave_count(), 1);
+
+        // Without a terminal the setup fails part way, which must still restore once
+        let writer = SharedWriter::default();
+        let guarded = TerminalGuard::with_writers(writer.clone(), writer.clone());
+        assert_eq!(writer.leave_count(), usize::from(guarded.is_err()));
+        drop(guarded);
+        assert_eq!(writer.leave_count(), 1);
+    }
+
+    #[test]
+    fn test_launch_restores_terminal() {
+        struct QuietApp;
+
+        impl TerminalApp for QuietApp {
+            fn ui(&self, _frame: &mut Frame) {}
+
+            fn handle_event(&mut self, _event: Event) -> anyhow::Result<bool> {
+                Ok(false)
+            }
+        }
+
+        /// An event source that has already ended
+        struct NoEvents;
+
+        impl EventSource for NoEvents {
+            fn poll(&mut self, _timeout: std::time::Duration) -> io::Result<bool> {
+                Ok(true)
+            }
+
+            fn read(&mut self) -> io::Result<Option<Event>> {
+                Ok(None)
+            }
+        }
+
+        // Without a terminal the setup fails, otherwise the app stops when the events end
+        let writer = SharedWriter::default();
+        let setup = || TerminalGuard::with_writers(writer.clone(), writer.clone());
+        let _ = launch_with(setup, QuietApp, NoEvents);
+        assert_eq!(writer.leave_count(), 1);
+    }
+
+    #[test]
+    fn test_restore_follows_the_terminal_stream() {
+        TerminalStream::Stderr.record();
+        assert_eq!(TerminalStream::current(), TerminalStream::Stderr);
+        TerminalStream::Stdout.record();
+        assert_eq!(TerminalStream::current(), TerminalStream::Stdout);
+
+        for stream in [TerminalStream::Stdout, TerminalStream::Stderr] {
+            let (mut stdout, mut stderr) = (Vec::new(), Vec::new());
+            restore_terminal_with(stream.pick(&mut stdout, &mut stderr)).unwrap();
+            let on_stderr = stream == TerminalStream::Stderr;
+            assert_eq!(stdout.is_empty(), on_stderr);
+            assert_eq!(stderr.is_empty(), !on_stderr);
+        }
+    }
+}
@@ -0,0 +1,273 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use crossterm::event::Event;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::io::{self, BufRead, Write};
+use std::time::{Duration, Instant};
+
+use crate::runtime::{Clock, EventSource, SystemClock};
+
+/// An event and when it arrived, relative to the start of the recording
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    /// Milliseconds since the recording started
+    pub at_ms: u64,
+    /// The event itself
+    pub event: Event,
+}
+
+/// Wraps an event source and writes every event it produces to a JSON Lines log
+///
+/// Pass the recorder to [`run_app_with`](crate::run_app_with) in place of the source it
+/// wraps. Each event is written and flushed as it is read, so the log survives a crash.
+pub struct EventRecorder<S, W> {
+    source: S,
+    writer: W,
+    clock: Box<dyn Clock>,
+    start: Instant,
+}
+
+impl<S: EventSource, W: Write> EventRecorder<S, W> {
+    /// Record events from `source` into `writer`, timed from now
+    pub fn new(source: S, writer: W) -> Self {
+        Self {
+            source,
+            writer,
+            clock: Box::new(SystemClock),
+            start: Instant::now(),
+        }
+    }
+
+    /// Set the clock used to timestamp events, restarting the recording at its current time
+    #[must_use]
+    pub fn clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.start = clock.now();
+        self.clock = Box::new(clock);
+        self
+    }
+
+    /// Stop recording and get the writer back
+    ///
+    /// # Errors
+    /// Returns an error if flushing the writer fails.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+impl<S: EventSource, W: Write> EventSource for EventRecorder<S, W> {
+    fn poll(&mut self, timeout: Duration) -> io::Result<bool> {
+        self.source.poll(timeout)
+    }
+
+    fn read(&mut self) -> io::Result<Option<Event>> {
+        let event = self.source.read()?;
+        if let Some(event) = &event {
+            let elapsed = self.clock.now().saturating_duration_since(self.start);
+            let record = RecordedEvent {
+                at_ms: u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX),
+                event: event.clone(),
+            };
+            serde_json::to_writer(&mut self.writer, &record)?;
+            self.writer.write_all(b"\n")?;
+            self.writer.flush()?;
+        }
+        Ok(event)
+    }
+}
+
+/// How an [`EventReplayer`] paces the events it plays back
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReplayTiming {
+    /// Deliver each event at the same offset from the start as when it was recorded
+    #[default]
+    Original,
+    /// Deliver every event as soon as it is polled for
+    AsFastAsPossible,
+}
+
+/// An event source that plays back a recording made by [`EventRecorder`]
+///
+/// Timing starts from the first poll. Once every event has been delivered, the source
+/// reports the end of the stream, which ends the run loop.
+pub struct EventReplayer {
+    events: VecDeque<RecordedEvent>,
+    timing: ReplayTiming,
+    clock: Box<dyn Clock>,
+    start: Option<Instant>,
+}
+
+impl EventReplayer {
+    /// Create a replayer for the given events
+    #[must_use]
+    pub fn new(events: impl IntoIterator<Item = RecordedEvent>) -> Self {
+        Self {
+            events: events.into_iter().collect(),
+            timing: ReplayTiming::default(),
+            clock: Box::new(SystemClock),
+            start: None,
+        }
+    }
+
+    /// Read a JSON Lines recording, skipping blank lines
+    ///
+    /// # Errors
+    /// Returns an error if reading fails or a line is not a recorded event.
+    pub fn from_reader(reader: impl BufRead) -> io::Result<Self> {
+        let mut events = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let event = serde_json::from_str(&line)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+            events.push(event);
+        }
+        Ok(Self::new(events))
+    }
+
+    /// Set how the events are paced
+    #[must_use]
+    pub fn timing(mut self, timing: ReplayTiming) -> Self {
+        self.timing = timing;
+        self
+    }
+
+    /// Set the clock used to pace the events
+    #[must_use]
+    pub fn clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Box::new(clock);
+        self
+    }
+
+    /// Get the number of events not yet delivered
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.events.len()
+    }
+}
+
+impl EventSource for EventReplayer {
+    fn poll(&mut self, timeout: Duration) -> io::Result<bool> {
+        let now = self.clock.now();
+        let start = *self.start.get_or_insert(now);
+        let Some(next) = self.events.front() else {
+            return Ok(true);
+        };
+        if self.timing == ReplayTiming::AsFastAsPossible {
+            return Ok(true);
+        }
+
+        let wait = (start + Duration::from_millis(next.at_ms)).saturating_duration_since(now);
+        if wait <= timeout {
+            if !wait.is_zero() {
+                self.clock.sleep(wait);
+            }
+            Ok(true)
+        } else {
+            self.clock.sleep(timeout);
+            Ok(false)
+        }
+    }
+
+    fn read(&mut self) -> io::Result<Option<Event>> {
+        Ok(self.events.pop_front().map(|record| record.event))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::VirtualClock;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    /// Hands out its events one per read, advancing the clock before each
+    struct SteppedEvents {
+        clock: VirtualClock,
+        events: VecDeque<(Duration, Event)>,
+    }
+
+    impl EventSource for SteppedEvents {
+        fn poll(&mut self, _timeout: Duration) -> io::Result<bool> {
+            Ok(true)
+        }
+
+        fn read(&mut self) -> io::Result<Option<Event>> {
+            Ok(self.events.pop_front().map(|(gap, event)| {
+                self.clock.sleep(gap);
+                event
+            }))
+        }
+    }
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> Event {
+        Event::Key(KeyEvent::new(code, modifiers))
+    }
+
+    #[test]
+    fn test_record_and_replay_round_trip() {
+        let clock = VirtualClock::new();
+        let events = vec![
+            key(KeyCode::Char('a'), KeyModifiers::NONE),
+            key(
+                KeyCode::Char('s'),
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+            ),
+            Event::Resize(80, 24),
+            Event::Paste("hello\nworld".to_owned()),
+        ];
+        let source = SteppedEvents {
+            clock: clock.clone(),
+            events: events
+                .iter()
+                .cloned()
+                .map(|event| (Duration::from_millis(25), event))
+                .collect(),
+        };
+
+        let mut recorder = EventRecorder::new(source, Vec::new()).clock(clock.clone());
+        while recorder.read().unwrap().is_some() {}
+        let log = recorder.finish().unwrap();
+
+        let mut replayer = EventReplayer::from_reader(log.as_slice()).unwrap();
+        let at: Vec<u64> = replayer.events.iter().map(|record| record.at_ms).collect();
+        assert_eq!(at, [25, 50, 75, 100]);
+
+        let mut played_back = Vec::new();
+        while let Some(event) = replayer.read().unwrap() {
+            played_back.push(event);
+        }
+        assert_eq!(played_back, events);
+    }
+
+    #[test]
+    fn test_replay_respects_original_timing() {
+        let clock = VirtualClock::new();
+        let start = clock.now();
+        let record = |at_ms| RecordedEvent {
+            at_ms,
+            event: Event::FocusGained,
+        };
+        let mut replayer = EventReplayer::new([record(0), record(120)]).clock(clock.clone());
+
+        assert!(replayer.poll(Duration::from_millis(50)).unwrap());
+        replayer.read().unwrap();
+
+        // The second event isn't due within the first two polls
+        assert!(!replayer.poll(Duration::from_millis(50)).unwrap());
+        assert!(!replayer.poll(Duration::from_millis(50)).unwrap());
+        assert!(replayer.poll(Duration::from_millis(50)).unwrap());
+        assert_eq!(clock.now() - start, Duration::from_millis(120));
+        assert_eq!(replayer.read().unwrap(), Some(Event::FocusGained));
+        assert_eq!(replayer.read().unwrap(), None);
+
+        // Without pacing every event is ready straight away
+        let mut fast = EventReplayer::new([record(5000)])
+            .timing(ReplayTiming::AsFastAsPossible)
+            .clock(clock.clone());
+        assert!(fast.poll(Duration::ZERO).unwrap());
+    }
+}
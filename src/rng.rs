@@ -0,0 +1,117 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+/// A small, fast, seedable pseudo-random number generator (xorshift64*)
+///
+/// Patterns use this instead of a global RNG so that a fixed seed reproduces the
+/// same animation frame for frame. It is not suitable for cryptographic use.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Create a generator from a seed
+    ///
+    /// Any seed is valid, including zero.
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        // Scramble the seed with SplitMix64 so that nearby seeds diverge quickly and
+        // the xorshift state is never zero
+        let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+
+        Self {
+            state: if z == 0 { 0x2545_F491_4F6C_DD1D } else { z },
+        }
+    }
+
+    /// Create a generator with a seed that differs between runs
+    #[must_use]
+    pub fn from_entropy() -> Self {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u64(
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |elapsed| {
+                    elapsed.as_secs() ^ u64::from(elapsed.subsec_nanos())
+                }),
+        );
+        Self::new(hasher.finish())
+    }
+
+    /// Get the next raw 64-bit value
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Get a value uniformly distributed in `0.0..1.0`
+    #[allow(clippy::cast_precision_loss)]
+    pub fn next_f64(&mut self) -> f64 {
+        // The top 53 bits fill the mantissa exactly
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+
+    /// Get a value uniformly distributed in `lo..hi`
+    pub fn next_range(&mut self, lo: f64, hi: f64) -> f64 {
+        lo + self.next_f64() * (hi - lo)
+    }
+
+    /// Return `true` with probability `p`
+    ///
+    /// `p <= 0.0` never succeeds and `p >= 1.0` always does.
+    pub fn chance(&mut self, p: f64) -> bool {
+        self.next_f64() < p
+    }
+}
+
+impl Default for Rng {
+    fn default() -> Self {
+        Self::from_entropy()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        let mut c = Rng::new(43);
+
+        let first: Vec<u64> = (0..16).map(|_| a.next_u64()).collect();
+        let second: Vec<u64> = (0..16).map(|_| b.next_u64()).collect();
+        let other: Vec<u64> = (0..16).map(|_| c.next_u64()).collect();
+
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn test_ranges() {
+        let mut rng = Rng::new(0);
+        for _ in 0..1000 {
+            let value = rng.next_f64();
+            assert!((0.0..1.0).contains(&value));
+
+            let value = rng.next_range(-2.0, 3.0);
+            assert!((-2.0..3.0).contains(&value));
+        }
+    }
+
+    #[test]
+    fn test_chance_extremes() {
+        let mut rng = Rng::new(7);
+        assert!((0..1000).all(|_| !rng.chance(0.0)));
+        assert!((0..1000).all(|_| rng.chance(1.0)));
+    }
+}
@@ -0,0 +1,1933 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use crossterm::{
+    QueueableCommand,
+    cursor::{Hide, SetCursorStyle, Show},
+    event::{self, Event, KeyCode, KeyEvent},
+};
+use ratatui::{
+    Frame, Terminal,
+    backend::Backend,
+    buffer::Buffer,
+    layout::Rect,
+    text::{Line, Text},
+    widgets::Paragraph,
+};
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crate::{
+    CursorShape, Error, ExitReason, StatusColor, TerminalApp, TerminalStream, dim_area_by,
+};
+
+/// How long `run_app` waits for an event before redrawing, unless configured otherwise
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How long `run_app` waits for an event while the app is clean, unless configured otherwise
+const IDLE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Most events handled between two draws, unless configured otherwise
+const MAX_EVENTS_PER_FRAME: usize = 64;
+
+/// A source of terminal events for the run loop
+pub trait EventSource {
+    /// Wait up to `timeout` for an event to become available
+    ///
+    /// # Errors
+    /// Returns an error if polling the underlying input fails.
+    fn poll(&mut self, timeout: Duration) -> io::Result<bool>;
+
+    /// Read the next event, or `None` once the source has no more events
+    ///
+    /// # Errors
+    /// Returns an error if reading the underlying input fails.
+    fn read(&mut self) -> io::Result<Option<Event>>;
+}
+
+/// Reads events from the terminal through crossterm
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CrosstermEvents;
+
+impl EventSource for CrosstermEvents {
+    fn poll(&mut self, timeout: Duration) -> io::Result<bool> {
+        event::poll(timeout)
+    }
+
+    fn read(&mut self) -> io::Result<Option<Event>> {
+        event::read().map(Some)
+    }
+}
+
+/// A source of time for the run loop
+///
+/// Swapping the clock lets the loop's timing be tested without waiting in real time.
+pub trait Clock {
+    /// Get the current time
+    fn now(&self) -> Instant;
+
+    /// Block for the given duration
+    fn sleep(&self, duration: Duration);
+}
+
+/// The real system clock
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+}
+
+/// Ring the terminal bell by writing BEL to `writer`
+///
+/// Pass the stream the terminal draws to, so a terminal set up with
+/// [`setup_terminal_on_stderr`](crate::setup_terminal_on_stderr) rings on stderr.
+///
+/// # Errors
+/// Returns an error if writing to the terminal fails.
+pub fn ring_bell<W: Write + ?Sized>(writer: &mut W) -> io::Result<()> {
+    writer.write_all(b"\x07")?;
+    writer.flush()
+}
+
+/// Policy for dropping frames when drawing falls behind
+///
+/// When a frame is due more than a whole poll interval late, the loop skips drawing
+/// it so queued input is handled sooner. At most `max_skip` frames are skipped in a
+/// row before one is drawn regardless, so the screen never freezes entirely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameSkip {
+    /// The maximum number of consecutive frames to skip
+    pub max_skip: u32,
+}
+
+/// Time allowed for drawing a frame, and what to do when a draw overruns it
+///
+/// After a draw that takes longer than `frame_time`, the loop stops waiting for
+/// events and goes straight on to the next frame. With `coalesce_events` set it first
+/// handles every event that queued up during the draw, up to
+/// [`RunConfig::max_events_per_frame`], so the next frame reflects all of them.
+/// Without it only one event is handled, so the next frame comes as soon as possible.
+/// At least one pending event is always handled between draws, however slow drawing gets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderBudget {
+    /// The target time for one frame
+    pub frame_time: Duration,
+    /// Whether to handle all queued events before drawing after an overrun
+    pub coalesce_events: bool,
+}
+
+impl RenderBudget {
+    /// Count the whole frames a draw of the given length overran the budget by
+    fn overrun(&self, draw_time: Duration) -> u32 {
+        let frame_nanos = self.frame_time.as_nanos().max(1);
+        u32::try_from(draw_time.as_nanos() / frame_nanos).unwrap_or(u32::MAX)
+    }
+}
+
+/// Measurements of a single drawn frame
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RenderStats {
+    /// Number of cells that differ from the previous frame
+    pub changed_cells: usize,
+    /// Time spent rendering and flushing the frame
+    pub render_time: Duration,
+    /// Frames lost since the previous drawn frame
+    ///
+    /// Counts frames skipped by a [`FrameSkip`] policy plus, with a [`RenderBudget`],
+    /// the whole frame intervals this draw took beyond its budget.
+    pub dropped_frames: u32,
+}
+
+/// Keeps the last drawn frame to count how many cells the next one changes
+#[derive(Debug, Default)]
+struct FrameDiff {
+    previous: Option<Buffer>,
+}
+
+impl FrameDiff {
+    fn measure(
+        &mut self,
+        buffer: &Buffer,
+        render_time: Duration,
+        dropped_frames: u32,
+    ) -> RenderStats {
+        // After a resize the terminal is cleared, so every non-blank cell is new
+        let changed_cells = match &self.previous {
+            Some(previous) if previous.area == buffer.area => previous.diff(buffer).len(),
+            _ => Buffer::empty(buffer.area).diff(buffer).len(),
+        };
+        self.previous = Some(buffer.clone());
+        RenderStats {
+            changed_cells,
+            render_time,
+            dropped_frames,
+        }
+    }
+}
+
+/// Edits a finished frame before it is flushed, set with [`RunConfig::post_process`]
+type PostProcess = Box<dyn FnMut(&mut Buffer)>;
+
+/// Options controlling the behavior of [`run_app_with`]
+pub struct RunConfig {
+    poll_interval: Duration,
+    max_events_per_frame: usize,
+    resize_debounce: Duration,
+    frame_skip: Option<FrameSkip>,
+    render_budget: Option<RenderBudget>,
+    toast_errors: bool,
+    max_errors: Option<u32>,
+    collect_render_stats: bool,
+    min_frame_time: Duration,
+    idle_poll_interval: Duration,
+    bell_enabled: bool,
+    bell_writer: Option<Box<dyn Write>>,
+    post_process: Option<PostProcess>,
+    min_terminal_size: (u16, u16),
+    idle_timeout: Option<Duration>,
+    clock: Box<dyn Clock>,
+}
+
+impl RunConfig {
+    /// Create a run config with default settings
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            poll_interval: POLL_INTERVAL,
+            max_events_per_frame: MAX_EVENTS_PER_FRAME,
+            resize_debounce: Duration::from_millis(30),
+            frame_skip: None,
+            render_budget: None,
+            toast_errors: false,
+            max_errors: None,
+            collect_render_stats: false,
+            min_frame_time: Duration::ZERO,
+            idle_poll_interval: IDLE_POLL_INTERVAL,
+            bell_enabled: true,
+            bell_writer: None,
+            post_process: None,
+            min_terminal_size: (0, 0),
+            idle_timeout: None,
+            clock: Box::new(SystemClock),
+        }
+    }
+
+    /// Set how long the loop waits for an event before drawing the next frame
+    ///
+    /// This is also the frame interval used by [`FrameSkip`]. Defaults to 50ms.
+    #[must_use]
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Set the most events handled between two draws
+    ///
+    /// Every event already waiting is handled before the next frame is drawn, so a
+    /// burst such as a paste causes one redraw rather than one per event. The cap keeps
+    /// the screen updating while events keep arriving. Defaults to 64; values below 1
+    /// are treated as 1.
+    #[must_use]
+    pub fn max_events_per_frame(mut self, max: usize) -> Self {
+        self.max_events_per_frame = max.max(1);
+        self
+    }
+
+    /// Set how long the terminal size must stay unchanged before a resize is handled
+    ///
+    /// Resize events arriving within this window of each other are coalesced into a
+    /// single [`TerminalApp::on_resize`] call and a single redraw.
+    #[must_use]
+    pub fn resize_debounce(mut self, debounce: Duration) -> Self {
+        self.resize_debounce = debounce;
+        self
+    }
+
+    /// Set the policy for skipping frames when drawing can't keep up
+    ///
+    /// By default every frame is drawn.
+    #[must_use]
+    pub fn frame_skip(mut self, policy: FrameSkip) -> Self {
+        self.frame_skip = Some(policy);
+        self
+    }
+
+    /// Set the time budget for drawing a frame
+    ///
+    /// By default the loop always waits for events between frames, however long the
+    /// previous draw took.
+    #[must_use]
+    pub fn render_budget(mut self, budget: RenderBudget) -> Self {
+        self.render_budget = Some(budget);
+        self
+    }
+
+    /// Set whether event handling errors are shown as toasts instead of ending the loop
+    ///
+    /// Caught errors are pushed to the stack from [`TerminalApp::toasts`], if the app
+    /// has one, and the loop carries on.
+    #[must_use]
+    pub fn toast_errors(mut self, enabled: bool) -> Self {
+        self.toast_errors = enabled;
+        self
+    }
+
+    /// Set how many errors [`toast_errors`](Self::toast_errors) catches before giving up
+    ///
+    /// Once this many errors have been shown, the next one is returned from the loop.
+    /// There is no limit by default.
+    #[must_use]
+    pub fn max_errors(mut self, max_errors: u32) -> Self {
+        self.max_errors = Some(max_errors);
+        self
+    }
+
+    /// Set whether to measure each drawn frame and report it to the app
+    ///
+    /// When enabled, [`TerminalApp::on_frame_rendered`] receives the number of cells
+    /// that changed and the time the draw took. This keeps a copy of the last frame,
+    /// so it is off by default.
+    #[must_use]
+    pub fn collect_render_stats(mut self, enabled: bool) -> Self {
+        self.collect_render_stats = enabled;
+        self
+    }
+
+    /// Set the shortest time allowed between the start of one draw and the next
+    ///
+    /// The loop sleeps before drawing if the previous frame was drawn more recently
+    /// than this, which reduces tearing on terminals that redraw slowly. This is
+    /// separate from how long the loop waits for events. There is no minimum by default.
+    #[must_use]
+    pub fn min_frame_time(mut self, min_frame_time: Duration) -> Self {
+        self.min_frame_time = min_frame_time;
+        self
+    }
+
+    /// Set how long the loop waits for an event while the app has nothing to redraw
+    ///
+    /// While [`TerminalApp::is_dirty`] returns `false`, frames aren't drawn and the loop
+    /// waits this long for events instead of the [`poll_interval`](Self::poll_interval).
+    /// Defaults to one second.
+    #[must_use]
+    pub fn idle_poll_interval(mut self, interval: Duration) -> Self {
+        self.idle_poll_interval = interval;
+        self
+    }
+
+    /// Set whether the bell rings when [`TerminalApp::wants_bell`] asks for it
+    ///
+    /// Enabled by default. When disabled, requests for the bell are ignored.
+    #[must_use]
+    pub fn bell_enabled(mut self, enabled: bool) -> Self {
+        self.bell_enabled = enabled;
+        self
+    }
+
+    /// Set the stream the bell is rung on
+    ///
+    /// Defaults to the stream the terminal was set up on: stderr after
+    /// [`setup_terminal_on_stderr`](crate::setup_terminal_on_stderr), stdout otherwise.
+    #[must_use]
+    pub fn bell_writer(mut self, writer: impl Write + 'static) -> Self {
+        self.bell_writer = Some(Box::new(writer));
+        self
+    }
+
+    /// Set a pass that edits each frame after the app has drawn it
+    ///
+    /// The function gets the finished buffer, overlays included, before it is
+    /// flushed to the terminal, which suits whole-screen effects like [`scanlines`].
+    #[must_use]
+    pub fn post_process(mut self, post_process: impl FnMut(&mut Buffer) + 'static) -> Self {
+        self.post_process = Some(Box::new(post_process));
+        self
+    }
+
+    /// Set the smallest terminal the app is drawn in
+    ///
+    /// Below this size, frames show a "terminal too small" message instead of the
+    /// app's UI, until the terminal grows again. A terminal with no rows or columns
+    /// is never drawn to, whatever this is set to.
+    #[must_use]
+    pub fn min_terminal_size(mut self, width: u16, height: u16) -> Self {
+        self.min_terminal_size = (width, height);
+        self
+    }
+
+    /// Set how long without input before [`TerminalApp::on_idle`] is called
+    ///
+    /// Any event restarts the wait, and so does each call to `on_idle`, which repeats
+    /// for as long as there is no input. Never called by default.
+    #[must_use]
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the clock used to time the loop
+    #[must_use]
+    pub fn clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Box::new(clock);
+        self
+    }
+}
+
+impl Default for RunConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A [`RunConfig::post_process`] pass that dims every other row, like a CRT
+pub fn scanlines() -> impl FnMut(&mut Buffer) {
+    |buf: &mut Buffer| {
+        let area = buf.area;
+        for y in (area.y + 1..area.bottom()).step_by(2) {
+            dim_area_by(Rect::new(area.x, y, area.width, 1), buf, 0.3);
+        }
+    }
+}
+
+/// Shape, blinking, and visibility of the terminal's own cursor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CursorState {
+    /// `None` for the shape configured in the user's terminal
+    shape: Option<CursorShape>,
+    blinking: bool,
+    visible: bool,
+}
+
+/// Keeps the terminal cursor in a wanted shape and visibility without redundant writes
+///
+/// The app updates the wanted state as focus moves, for example a blinking bar while
+/// a text input is focused and a hidden cursor otherwise, and calls
+/// [`apply`](Self::apply) once per frame, typically with stdout. Commands are only
+/// written when the wanted state differs from the last one applied, so calling it
+/// every frame doesn't make the cursor flicker or restart its blink.
+///
+/// Ratatui's `Terminal` hides the cursor after any frame that doesn't place it with
+/// `Frame::set_cursor_position`, so a visible cursor should also be placed there, as
+/// [`TextInput::render_with_cursor`](crate::TextInput::render_with_cursor) does.
+/// [`restore_terminal`](crate::restore_terminal) puts back the default cursor.
+#[derive(Debug, Clone)]
+pub struct CursorController {
+    wanted: CursorState,
+    applied: Option<CursorState>,
+}
+
+impl CursorController {
+    /// Create a controller that wants a visible cursor in the terminal's default shape
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            wanted: CursorState {
+                shape: None,
+                blinking: true,
+                visible: true,
+            },
+            applied: None,
+        }
+    }
+
+    /// Set the wanted shape, or `None` for the shape configured in the terminal
+    pub fn set_shape(&mut self, shape: Option<CursorShape>) {
+        self.wanted.shape = shape;
+    }
+
+    /// Set whether the wanted shape blinks
+    ///
+    /// Has no effect while the shape is the terminal default.
+    pub fn set_blinking(&mut self, blinking: bool) {
+        self.wanted.blinking = blinking;
+    }
+
+    /// Set whether the cursor should be shown
+    pub fn set_visible(&mut self, visible: bool) {
+        self.wanted.visible = visible;
+    }
+
+    /// Get the wanted shape
+    #[must_use]
+    pub fn shape(&self) -> Option<CursorShape> {
+        self.wanted.shape
+    }
+
+    /// Get whether the wanted shape blinks
+    #[must_use]
+    pub fn is_blinking(&self) -> bool {
+        self.wanted.blinking
+    }
+
+    /// Get whether the cursor should be shown
+    #[must_use]
+    pub fn is_visible(&self) -> bool {
+        self.wanted.visible
+    }
+
+    /// Forget what was last applied, so the next [`apply`](Self::apply) writes everything
+    ///
+    /// Call this after something else may have changed the cursor, such as resuming
+    /// from a suspend or running a child process on the terminal.
+    pub fn invalidate(&mut self) {
+        self.applied = None;
+    }
+
+    /// Write the commands that bring the cursor to the wanted state
+    ///
+    /// Only the parts that changed since the last call are written, and nothing at
+    /// all when the state is unchanged. Returns whether anything was written.
+    ///
+    /// # Errors
+    /// Returns an error if writing to or flushing `writer` fails. The state is then
+    /// treated as unknown, so the next call writes everything again.
+    pub fn apply<W: Write>(&mut self, writer: &mut W) -> io::Result<bool> {
+        let wanted = self.wanted;
+        let applied = self.applied.take();
+        let shape_changed = applied.is_none_or(|applied| {
+            applied.shape != wanted.shape
+                || wanted.shape.is_some() && applied.blinking != wanted.blinking
+        });
+        let visibility_changed = applied.is_none_or(|applied| applied.visible != wanted.visible);
+        if !shape_changed && !visibility_changed {
+            self.applied = applied;
+            return Ok(false);
+        }
+
+        if shape_changed {
+            writer.queue(cursor_style(wanted))?;
+        }
+        if visibility_changed {
+            if wanted.visible {
+                writer.queue(Show)?;
+            } else {
+                writer.queue(Hide)?;
+            }
+        }
+        writer.flush()?;
+        self.applied = Some(wanted);
+        Ok(true)
+    }
+}
+
+impl Default for CursorController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The crossterm command that sets a cursor's shape and blinking
+fn cursor_style(state: CursorState) -> SetCursorStyle {
+    match (state.shape, state.blinking) {
+        (None, _) => SetCursorStyle::DefaultUserShape,
+        (Some(CursorShape::Block), true) => SetCursorStyle::BlinkingBlock,
+        (Some(CursorShape::Block), false) => SetCursorStyle::SteadyBlock,
+        (Some(CursorShape::Underline), true) => SetCursorStyle::BlinkingUnderScore,
+        (Some(CursorShape::Underline), false) => SetCursorStyle::SteadyUnderScore,
+        (Some(CursorShape::Bar), true) => SetCursorStyle::BlinkingBar,
+        (Some(CursorShape::Bar), false) => SetCursorStyle::SteadyBar,
+    }
+}
+
+/// Draws frames and keeps track of their timing
+struct FramePacer {
+    poll_interval: Duration,
+    min_frame_time: Duration,
+    render_budget: Option<RenderBudget>,
+    last_draw: Option<Instant>,
+    next_frame: Instant,
+    skipped: u32,
+    over_budget: bool,
+    frame_diff: Option<FrameDiff>,
+    post_process: Option<PostProcess>,
+    min_size: (u16, u16),
+}
+
+impl FramePacer {
+    /// Create a pacer for the drawing settings of `config`, taking its post-process pass
+    fn new(config: &mut RunConfig) -> Self {
+        Self {
+            poll_interval: config.poll_interval,
+            min_frame_time: config.min_frame_time,
+            render_budget: config.render_budget,
+            last_draw: None,
+            next_frame: config.clock.now(),
+            skipped: 0,
+            over_budget: false,
+            frame_diff: config.collect_render_stats.then(FrameDiff::default),
+            post_process: config.post_process.take(),
+            min_size: config.min_terminal_size,
+        }
+    }
+
+    /// Draw a frame, first waiting out the minimum frame time unless running over budget
+    fn draw<B: Backend, A: TerminalApp>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        app: &mut A,
+        clock: &dyn Clock,
+        now: Instant,
+    ) -> Result<(), Error> {
+        // Terminals can briefly have no rows or columns, such as while tmux splits a pane
+        terminal
+            .autoresize()
+            .map_err(|e| Error::Terminal(e.into()))?;
+        if terminal.get_frame().area().is_empty() {
+            self.next_frame = self.next_frame.max(now) + self.poll_interval;
+            return Ok(());
+        }
+        if let Some(last) = self.last_draw
+            && !self.over_budget
+        {
+            let since = clock.now().saturating_duration_since(last);
+            let wait = self.min_frame_time.saturating_sub(since);
+            if !wait.is_zero() {
+                clock.sleep(wait);
+            }
+        }
+        let started = clock.now();
+        self.last_draw = Some(started);
+        let frame = terminal
+            .draw(|f| {
+                let (min_width, min_height) = self.min_size;
+                if f.area().width < min_width || f.area().height < min_height {
+                    draw_too_small(f, self.min_size);
+                    return;
+                }
+                draw_app(app, f);
+                if let Some(post_process) = &mut self.post_process {
+                    post_process(f.buffer_mut());
+                }
+            })
+            .map_err(|e| Error::Terminal(e.into()))?;
+        let draw_time = clock.now().saturating_duration_since(started);
+        let overrun = self
+            .render_budget
+            .map_or(0, |budget| budget.overrun(draw_time));
+        self.over_budget = overrun > 0;
+        if let Some(diff) = &mut self.frame_diff {
+            let stats = diff.measure(frame.buffer, draw_time, self.skipped + overrun);
+            app.on_frame_rendered(stats);
+        }
+        app.on_frame_drawn();
+        self.skipped = 0;
+        self.next_frame = self.next_frame.max(now) + self.poll_interval;
+        Ok(())
+    }
+}
+
+/// Tracks the time since the last input for [`RunConfig::idle_timeout`]
+struct IdleTimer {
+    timeout: Option<Duration>,
+    last_input: Instant,
+}
+
+impl IdleTimer {
+    /// Shorten a poll timeout so the loop wakes up when the idle timeout runs out
+    fn cap(&self, timeout: Duration, now: Instant) -> Duration {
+        self.timeout.map_or(timeout, |idle| {
+            let deadline = self.last_input + idle;
+            timeout.min(deadline.saturating_duration_since(now))
+        })
+    }
+
+    /// Call the app's idle hook if the timeout has run out, returning whether it asked
+    /// to exit
+    fn check<A: TerminalApp>(
+        &mut self,
+        app: &mut A,
+        error_toasts: &mut ErrorToasts,
+        now: Instant,
+    ) -> Result<bool, Error> {
+        let Some(idle) = self.timeout else {
+            return Ok(false);
+        };
+        if now.saturating_duration_since(self.last_input) < idle {
+            return Ok(false);
+        }
+        self.last_input = now;
+        let result = app.on_idle();
+        error_toasts.catch(app, result, now)
+    }
+}
+
+/// A resize that is waiting for the debounce window to pass
+#[derive(Debug, Clone, Copy)]
+struct PendingResize {
+    width: u16,
+    height: u16,
+    deadline: Instant,
+}
+
+/// Catches event handling errors and shows them as toasts, up to a limit
+struct ErrorToasts {
+    enabled: bool,
+    max_errors: Option<u32>,
+    caught: u32,
+}
+
+impl ErrorToasts {
+    /// Turn the result of handling an event into whether the app asked to exit
+    fn catch<A: TerminalApp>(
+        &mut self,
+        app: &mut A,
+        result: anyhow::Result<bool>,
+        now: Instant,
+    ) -> Result<bool, Error> {
+        match result {
+            Ok(exit) => Ok(exit),
+            Err(error) if self.enabled && self.max_errors.is_none_or(|max| self.caught < max) => {
+                self.caught += 1;
+                if let Some(toasts) = app.toasts() {
+                    toasts.push_at(format!("{error:#}"), StatusColor::Error, now);
+                }
+                Ok(false)
+            }
+            Err(error) => Err(Error::Terminal(error)),
+        }
+    }
+}
+
+/// Handle one event read from the source, returning why the loop should stop, if it should
+fn handle_event<A: TerminalApp>(
+    app: &mut A,
+    event: Option<&Event>,
+    pending_resize: &mut Option<PendingResize>,
+    error_toasts: &mut ErrorToasts,
+    clock: &dyn Clock,
+    resize_debounce: Duration,
+) -> Result<Option<ExitReason>, Error> {
+    match event {
+        None => return Ok(Some(ExitReason::EventStreamEnded)),
+        Some(&Event::Resize(width, height)) => {
+            *pending_resize = Some(PendingResize {
+                width,
+                height,
+                deadline: clock.now() + resize_debounce,
+            });
+        }
+        Some(&Event::Key(key)) => {
+            if let Some(tracker) = app.key_tracker() {
+                tracker.record(&key, clock.now());
+            }
+            let bound = app.key_map().is_some_and(|map| map.lookup(&key).is_some());
+            if !bound && key.code == KeyCode::Char('q') {
+                return Ok(Some(ExitReason::QuitKey));
+            }
+            let handled = dispatch_key(app, key);
+            if error_toasts.catch(app, handled, clock.now())? {
+                return Ok(Some(ExitReason::AppRequested));
+            }
+        }
+        Some(_) => {}
+    }
+    Ok(None)
+}
+
+/// Handle up to `batch` events, stopping early once none are waiting
+fn drain_events<A: TerminalApp, E: EventSource>(
+    app: &mut A,
+    events: &mut E,
+    batch: usize,
+    pending_resize: &mut Option<PendingResize>,
+    error_toasts: &mut ErrorToasts,
+    clock: &dyn Clock,
+    resize_debounce: Duration,
+) -> Result<Option<ExitReason>, Error> {
+    for handled in 1..=batch {
+        let event = events.read()?;
+        let stop = handle_event(
+            app,
+            event.as_ref(),
+            pending_resize,
+            error_toasts,
+            clock,
+            resize_debounce,
+        )?;
+        if stop.is_some() || handled == batch || !events.poll(Duration::ZERO)? {
+            return Ok(stop);
+        }
+    }
+    Ok(None)
+}
+
+/// Advance the app by a frame, ringing the bell if it asks for it
+///
+/// `bell` is `None` while the bell is muted, and holds the configured writer otherwise,
+/// falling back to the stream the terminal was set up on.
+fn tick_app<A: TerminalApp>(
+    app: &mut A,
+    bell: Option<&mut Option<Box<dyn Write>>>,
+) -> Result<(), Error> {
+    app.on_tick();
+    if app.wants_bell()
+        && let Some(bell) = bell
+    {
+        if let Some(writer) = bell {
+            ring_bell(writer)?;
+        } else {
+            let (mut stdout, mut stderr) = (io::stdout(), io::stderr());
+            ring_bell(TerminalStream::current().pick(&mut stdout, &mut stderr))?;
+        }
+    }
+    Ok(())
+}
+
+/// Draw the app's UI followed by its overlays
+fn draw_app<A: TerminalApp>(app: &A, frame: &mut Frame) {
+    app.ui(frame);
+    if let Some(overlays) = app.overlays() {
+        frame.render_widget(overlays, frame.area());
+    }
+}
+
+/// Draw the message shown in place of the UI while the terminal is below its minimum size
+fn draw_too_small(frame: &mut Frame, (width, height): (u16, u16)) {
+    let area = frame.area();
+    let message = Text::from(vec![
+        Line::raw("Terminal too small"),
+        Line::raw(format!("need {width}x{height}")),
+    ]);
+    let top = area.height.saturating_sub(2) / 2;
+    let area = Rect {
+        y: area.y + top,
+        height: area.height - top,
+        ..area
+    };
+    frame.render_widget(Paragraph::new(message).centered(), area);
+}
+
+/// Let go of held keys and toasts in app-owned state that have timed out
+///
+/// Returns whether anything expired, since the last frame then no longer matches.
+fn expire_app_state<A: TerminalApp>(app: &mut A, now: Instant) -> bool {
+    let released = app.key_tracker().is_some_and(|tracker| tracker.expire(now));
+    let dismissed = app.toasts().is_some_and(|toasts| toasts.expire(now));
+    released || dismissed
+}
+
+/// Send a key to the action bound in the app's key map, or to `handle_event`
+fn dispatch_key<A: TerminalApp>(app: &mut A, key: KeyEvent) -> anyhow::Result<bool> {
+    let action = app
+        .key_map()
+        .and_then(|map| map.lookup(&key))
+        .map(str::to_owned);
+    match action {
+        Some(action) => app.handle_action(&action),
+        None => app.handle_event(Event::Key(key)),
+    }
+}
+
+/// Run a terminal application
+///
+/// # Errors
+/// Returns an error if:
+/// - Failed to draw to terminal
+/// - Failed to poll for events
+/// - Failed to read events
+/// - Application event handling failed
+///
+/// On success, returns the [`ExitReason`] that ended the loop.
+pub fn run_app<B: Backend, A: TerminalApp>(
+    terminal: &mut Terminal<B>,
+    app: A,
+) -> Result<ExitReason, Error> {
+    run_app_with(terminal, app, CrosstermEvents, RunConfig::default())
+}
+
+/// Run a terminal application with a custom event source and configuration
+///
+/// # Errors
+/// Returns an error if:
+/// - Failed to draw to terminal
+/// - Failed to poll for events
+/// - Failed to read events
+/// - Application event handling failed
+///
+/// On success, returns the [`ExitReason`] that ended the loop.
+pub fn run_app_with<B, A, E>(
+    terminal: &mut Terminal<B>,
+    mut app: A,
+    mut events: E,
+    mut config: RunConfig,
+) -> Result<ExitReason, Error>
+where
+    B: Backend,
+    A: TerminalApp,
+    E: EventSource,
+{
+    let mut pacer = FramePacer::new(&mut config);
+    let RunConfig {
+        poll_interval,
+        max_events_per_frame,
+        resize_debounce,
+        frame_skip,
+        render_budget,
+        toast_errors,
+        max_errors,
+        idle_poll_interval,
+        bell_enabled,
+        mut bell_writer,
+        idle_timeout,
+        clock,
+        ..
+    } = config;
+    let mut pending_resize: Option<PendingResize> = None;
+    let mut error_toasts = ErrorToasts {
+        enabled: toast_errors,
+        max_errors,
+        caught: 0,
+    };
+    let mut idle = false;
+    let mut force_redraw = false;
+    let mut idle_timer = IdleTimer {
+        timeout: idle_timeout,
+        last_input: clock.now(),
+    };
+
+    loop {
+        // Hold off redrawing while a resize storm is still in progress
+        if pending_resize.is_none() {
+            tick_app(&mut app, bell_enabled.then_some(&mut bell_writer))?;
+
+            let now = clock.now();
+            // A clean app keeps its last frame, unless a resize has invalidated it
+            idle = !force_redraw && pacer.last_draw.is_some() && !app.is_dirty();
+            let behind = now.saturating_duration_since(pacer.next_frame) >= poll_interval;
+            if idle {
+                pacer.next_frame = now;
+            } else if behind && frame_skip.is_some_and(|policy| pacer.skipped < policy.max_skip) {
+                pacer.skipped += 1;
+                pacer.next_frame += poll_interval;
+            } else {
+                pacer.draw(terminal, &mut app, &*clock, now)?;
+                force_redraw = false;
+            }
+        }
+
+        let wait = if idle {
+            idle_poll_interval
+        } else {
+            poll_interval
+        };
+        let timeout = pending_resize.map_or(wait, |resize| {
+            resize
+                .deadline
+                .saturating_duration_since(clock.now())
+                .min(poll_interval)
+        });
+        // A frame that ran over budget is followed straight away by the next one
+        let timeout = if pacer.over_budget {
+            Duration::ZERO
+        } else {
+            idle_timer.cap(timeout, clock.now())
+        };
+
+        let polled = events.poll(timeout)?;
+        if expire_app_state(&mut app, clock.now()) {
+            force_redraw = true;
+        }
+
+        if polled {
+            idle_timer.last_input = clock.now();
+            let coalesce = render_budget.is_none_or(|budget| budget.coalesce_events);
+            let batch = if pacer.over_budget && !coalesce {
+                1
+            } else {
+                max_events_per_frame
+            };
+            let stop = drain_events(
+                &mut app,
+                &mut events,
+                batch,
+                &mut pending_resize,
+                &mut error_toasts,
+                &*clock,
+                resize_debounce,
+            )?;
+            if let Some(reason) = stop {
+                return Ok(reason);
+            }
+        } else if idle_timer.check(&mut app, &mut error_toasts, clock.now())? {
+            return Ok(ExitReason::AppRequested);
+        }
+
+        if let Some(resize) = pending_resize
+            && clock.now() >= resize.deadline
+        {
+            // Resize the buffers before the app sees the new size, then fall through
+            // to the top of the loop so the new layout is drawn straight away
+            terminal
+                .autoresize()
+                .map_err(|e| Error::Terminal(e.into()))?;
+            app.on_resize(resize.width, resize.height);
+            pending_resize = None;
+            force_redraw = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::VirtualClock;
+    use crate::{KeyMap, KeyRepeatTracker, OverlayStack, StatusColor, ToastStack};
+    use crossterm::event::KeyModifiers;
+    use ratatui::{
+        Frame,
+        backend::TestBackend,
+        style::Modifier,
+        text::{Line, Text},
+    };
+    use std::cell::{Cell, RefCell};
+    use std::collections::VecDeque;
+    use std::rc::Rc;
+
+    /// Replays events at fixed offsets from the start, advancing a virtual clock
+    struct ScriptedEvents {
+        clock: VirtualClock,
+        start: Instant,
+        events: VecDeque<(Duration, Event)>,
+    }
+
+    impl ScriptedEvents {
+        fn new(clock: &VirtualClock, events: impl IntoIterator<Item = (u64, Event)>) -> Self {
+            Self {
+                clock: clock.clone(),
+                start: clock.now(),
+                events: events
+                    .into_iter()
+                    .map(|(millis, event)| (Duration::from_millis(millis), event))
+                    .collect(),
+            }
+        }
+    }
+
+    impl EventSource for ScriptedEvents {
+        fn poll(&mut self, timeout: Duration) -> io::Result<bool> {
+            let Some((at, _)) = self.events.front() else {
+                // An exhausted script reports the end of the stream
+                return Ok(true);
+            };
+            let due = self.start + *at;
+            let now = self.clock.now();
+            if due <= now + timeout {
+                self.clock.advance(due.saturating_duration_since(now));
+                Ok(true)
+            } else {
+                self.clock.advance(timeout);
+                Ok(false)
+            }
+        }
+
+        fn read(&mut self) -> io::Result<Option<Event>> {
+            Ok(self.events.pop_front().map(|(_, event)| event))
+        }
+    }
+
+    struct TestApp {
+        clock: VirtualClock,
+        start: Instant,
+        resizes: Rc<RefCell<Vec<(u16, u16)>>>,
+        resized_at: Rc<RefCell<Vec<Duration>>>,
+        draws: Rc<RefCell<Vec<Duration>>>,
+        exit_on: Option<char>,
+    }
+
+    impl TestApp {
+        fn new(clock: &VirtualClock) -> Self {
+            Self {
+                clock: clock.clone(),
+                start: clock.now(),
+                resizes: Rc::default(),
+                resized_at: Rc::default(),
+                draws: Rc::default(),
+                exit_on: None,
+            }
+        }
+
+        fn elapsed(&self) -> Duration {
+            self.clock.now() - self.start
+        }
+    }
+
+    impl TerminalApp for TestApp {
+        fn ui(&self, _frame: &mut Frame) {
+            self.draws.borrow_mut().push(self.elapsed());
+        }
+
+        fn handle_event(&mut self, event: Event) -> anyhow::Result<bool> {
+            Ok(matches!(
+                (event, self.exit_on),
+                (Event::Key(KeyEvent { code: KeyCode::Char(c), .. }), Some(exit)) if c == exit
+            ))
+        }
+
+        fn on_resize(&mut self, width: u16, height: u16) {
+            self.resizes.borrow_mut().push((width, height));
+            self.resized_at.borrow_mut().push(self.elapsed());
+        }
+    }
+
+    fn key(c: char) -> Event {
+        Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+    }
+
+    fn run(app: TestApp, script: Vec<(u64, Event)>) -> ExitReason {
+        let clock = app.clock.clone();
+        let events = ScriptedEvents::new(&clock, script);
+        let mut terminal = Terminal::new(TestBackend::new(20, 5)).unwrap();
+        run_app_with(
+            &mut terminal,
+            app,
+            events,
+            RunConfig::new()
+                .resize_debounce(Duration::from_millis(30))
+                .clock(clock),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_exit_reasons() {
+        assert_eq!(
+            run(TestApp::new(&VirtualClock::new()), vec![(0, key('q'))]),
+            ExitReason::QuitKey
+        );
+
+        let app = TestApp {
+            exit_on: Some('x'),
+            ..TestApp::new(&VirtualClock::new())
+        };
+        assert_eq!(
+            run(app, vec![(0, key('a')), (10, key('x'))]),
+            ExitReason::AppRequested
+        );
+
+        assert_eq!(
+            run(TestApp::new(&VirtualClock::new()), vec![(0, key('a'))]),
+            ExitReason::EventStreamEnded
+        );
+    }
+
+    #[test]
+    fn test_resize_storm_is_debounced() {
+        let app = TestApp::new(&VirtualClock::new());
+        let resizes = Rc::clone(&app.resizes);
+
+        let script = vec![
+            (0, Event::Resize(30, 10)),
+            (5, Event::Resize(40, 12)),
+            (10, Event::Resize(50, 15)),
+            (200, key('q')),
+        ];
+        assert_eq!(run(app, script), ExitReason::QuitKey);
+        assert_eq!(*resizes.borrow(), [(50, 15)]);
+    }
+
+    #[test]
+    fn test_separate_resizes_are_not_coalesced() {
+        let app = TestApp::new(&VirtualClock::new());
+        let resizes = Rc::clone(&app.resizes);
+
+        let script = vec![
+            (0, Event::Resize(30, 10)),
+            (100, Event::Resize(50, 15)),
+            (200, key('q')),
+        ];
+        run(app, script);
+        assert_eq!(*resizes.borrow(), [(30, 10), (50, 15)]);
+    }
+
+    #[test]
+    fn test_resize_redraws_immediately() {
+        let app = TestApp::new(&VirtualClock::new());
+        let resized_at = Rc::clone(&app.resized_at);
+        let draws = Rc::clone(&app.draws);
+
+        let script = vec![(7, Event::Resize(30, 10)), (500, key('q'))];
+        run(app, script);
+
+        // The resize is handled once the debounce window passes, and the frame is
+        // redrawn at that same instant rather than on the next poll tick
+        let resized_at = resized_at.borrow()[0];
+        assert_eq!(resized_at, Duration::from_millis(37));
+        assert!(draws.borrow().contains(&resized_at));
+    }
+
+    #[test]
+    fn test_key_tracker_is_updated() {
+        struct TrackingApp {
+            tracker: KeyRepeatTracker,
+            held: Rc<RefCell<Vec<bool>>>,
+        }
+
+        impl TerminalApp for TrackingApp {
+            fn ui(&self, _frame: &mut Frame) {
+                self.held
+                    .borrow_mut()
+                    .push(self.tracker.is_down(KeyCode::Char('w')));
+            }
+
+            fn handle_event(&mut self, _event: Event) -> anyhow::Result<bool> {
+                Ok(false)
+            }
+
+            fn key_tracker(&mut self) -> Option<&mut KeyRepeatTracker> {
+                Some(&mut self.tracker)
+            }
+        }
+
+        let held = Rc::default();
+        let app = TrackingApp {
+            tracker: KeyRepeatTracker::new().release_timeout(Duration::from_millis(200)),
+            held: Rc::clone(&held),
+        };
+        let clock = VirtualClock::new();
+        let events = ScriptedEvents::new(&clock, [(100, key('w')), (1000, key('q'))]);
+        let mut terminal = Terminal::new(TestBackend::new(20, 5)).unwrap();
+        run_app_with(&mut terminal, app, events, RunConfig::new().clock(clock)).unwrap();
+
+        // Up before the press, down after it, and up again once it times out
+        let held = held.borrow();
+        assert!(!held[0]);
+        assert!(held.contains(&true));
+        assert!(!held[held.len() - 1]);
+    }
+
+    #[test]
+    fn test_frame_skip_when_drawing_lags() {
+        /// Draws take longer than the frame interval while `slow` is set
+        struct SlowApp {
+            clock: VirtualClock,
+            slow: Rc<Cell<bool>>,
+            frames: Rc<RefCell<Vec<bool>>>,
+        }
+
+        impl TerminalApp for SlowApp {
+            fn ui(&self, _frame: &mut Frame) {
+                if let Some(drawn) = self.frames.borrow_mut().last_mut() {
+                    *drawn = true;
+                }
+                if self.slow.get() {
+                    self.clock.advance(Duration::from_millis(120));
+                }
+            }
+
+            fn handle_event(&mut self, event: Event) -> anyhow::Result<bool> {
+                if let Event::Key(KeyEvent {
+                    code: KeyCode::Char('f'),
+                    ..
+                }) = event
+                {
+                    self.slow.set(false);
+                }
+                Ok(false)
+            }
+
+            fn on_tick(&mut self) {
+                self.frames.borrow_mut().push(false);
+            }
+        }
+
+        let clock = VirtualClock::new();
+        let frames = Rc::default();
+        let app = SlowApp {
+            clock: clock.clone(),
+            slow: Rc::new(Cell::new(true)),
+            frames: Rc::clone(&frames),
+        };
+
+        // A burst of input while drawing is slow, then idle once it speeds up
+        let mut script: Vec<(u64, Event)> = (0..40).map(|i| (i * 10, key('a'))).collect();
+        script.push((400, key('f')));
+        script.push((5000, key('q')));
+        let events = ScriptedEvents::new(&clock, script);
+
+        let mut terminal = Terminal::new(TestBackend::new(20, 5)).unwrap();
+        let config = RunConfig::new()
+            .frame_skip(FrameSkip { max_skip: 3 })
+            .clock(clock);
+        run_app_with(&mut terminal, app, events, config).unwrap();
+
+        let frames = frames.borrow();
+        let longest_skip_run = frames
+            .split(|&drawn| drawn)
+            .map(<[bool]>::len)
+            .max()
+            .unwrap();
+        assert!(frames.contains(&false), "no frames were skipped");
+        assert!(longest_skip_run <= 3);
+        // Once drawing is fast again, every frame is drawn
+        assert!(frames[frames.len() - 10..].iter().all(|&drawn| drawn));
+    }
+
+    /// Fails on every 'e' and records the toasts visible at each draw
+    struct FailingApp {
+        toasts: ToastStack,
+        seen: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl TerminalApp for FailingApp {
+        fn ui(&self, _frame: &mut Frame) {
+            let mut seen = self.seen.borrow_mut();
+            for toast in self.toasts.iter() {
+                if !seen.iter().any(|message| message == toast.message()) {
+                    seen.push(toast.message().to_owned());
+                }
+            }
+        }
+
+        fn handle_event(&mut self, event: Event) -> anyhow::Result<bool> {
+            match event {
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('e'),
+                    ..
+                }) => Err(anyhow::anyhow!("request failed")),
+                Event::Key(KeyEvent {
+                    code: KeyCode::Char('x'),
+                    ..
+                }) => Ok(true),
+                _ => Ok(false),
+            }
+        }
+
+        fn toasts(&mut self) -> Option<&mut ToastStack> {
+            Some(&mut self.toasts)
+        }
+    }
+
+    fn run_failing(
+        script: Vec<(u64, Event)>,
+        config: RunConfig,
+    ) -> (Result<ExitReason, Error>, Vec<String>) {
+        let seen = Rc::default();
+        let app = FailingApp {
+            toasts: ToastStack::new(),
+            seen: Rc::clone(&seen),
+        };
+        let clock = VirtualClock::new();
+        let events = ScriptedEvents::new(&clock, script);
+        let mut terminal = Terminal::new(TestBackend::new(20, 5)).unwrap();
+        let result = run_app_with(&mut terminal, app, events, config.clock(clock));
+        (result, seen.take())
+    }
+
+    #[test]
+    fn test_errors_become_toasts() {
+        let script = vec![(0, key('e')), (100, key('x'))];
+        let (result, seen) = run_failing(script, RunConfig::new().toast_errors(true));
+        assert_eq!(result.unwrap(), ExitReason::AppRequested);
+        assert_eq!(seen, ["request failed"]);
+
+        // Without the option the error ends the loop
+        let script = vec![(0, key('e')), (100, key('x'))];
+        let (result, _) = run_failing(script, RunConfig::new());
+        assert!(matches!(result, Err(Error::Terminal(_))));
+    }
+
+    #[test]
+    fn test_max_errors_gives_up() {
+        let script = vec![
+            (0, key('e')),
+            (10, key('e')),
+            (20, key('e')),
+            (100, key('x')),
+        ];
+        let config = RunConfig::new().toast_errors(true).max_errors(2);
+        let (result, _) = run_failing(script, config);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_key_map_dispatches_actions() {
+        struct ActionApp {
+            keys: KeyMap,
+            actions: Rc<RefCell<Vec<String>>>,
+        }
+
+        impl TerminalApp for ActionApp {
+            fn ui(&self, _frame: &mut Frame) {}
+
+            fn handle_event(&mut self, _event: Event) -> anyhow::Result<bool> {
+                self.actions.borrow_mut().push("event".to_owned());
+                Ok(false)
+            }
+
+            fn key_map(&self) -> Option<&KeyMap> {
+                Some(&self.keys)
+            }
+
+            fn handle_action(&mut self, action: &str) -> anyhow::Result<bool> {
+                self.actions.borrow_mut().push(action.to_owned());
+                Ok(action == "exit")
+            }
+        }
+
+        let actions = Rc::default();
+        let app = ActionApp {
+            keys: KeyMap::new()
+                .bind(KeyCode::Char('r'), KeyModifiers::NONE, "refresh", "Refresh")
+                .bind(KeyCode::Esc, KeyModifiers::NONE, "exit", "Exit"),
+            actions: Rc::clone(&actions),
+        };
+        let clock = VirtualClock::new();
+        let script = [
+            (0, key('r')),
+            (10, key('a')),
+            (20, Event::Key(KeyEvent::from(KeyCode::Esc))),
+        ];
+        let events = ScriptedEvents::new(&clock, script);
+        let mut terminal = Terminal::new(TestBackend::new(20, 5)).unwrap();
+        let reason = run_app_with(&mut terminal, app, events, RunConfig::new().clock(clock));
+
+        assert_eq!(reason.unwrap(), ExitReason::AppRequested);
+        assert_eq!(*actions.borrow(), ["refresh", "event", "exit"]);
+    }
+
+    #[test]
+    fn test_key_map_binding_overrides_quit_key() {
+        struct MappedApp {
+            keys: KeyMap,
+            actions: Rc<RefCell<Vec<String>>>,
+        }
+
+        impl TerminalApp for MappedApp {
+            fn ui(&self, _frame: &mut Frame) {}
+
+            fn handle_event(&mut self, _event: Event) -> anyhow::Result<bool> {
+                Ok(false)
+            }
+
+            fn key_map(&self) -> Option<&KeyMap> {
+                Some(&self.keys)
+            }
+
+            fn handle_action(&mut self, action: &str) -> anyhow::Result<bool> {
+                self.actions.borrow_mut().push(action.to_owned());
+                Ok(false)
+            }
+        }
+
+        let actions = Rc::default();
+        let app = MappedApp {
+            keys: KeyMap::new().bind(KeyCode::Char('q'), KeyModifiers::NONE, "queue", "Queue"),
+            actions: Rc::clone(&actions),
+        };
+        let clock = VirtualClock::new();
+        let script = [(0, key('q')), (10, key('q'))];
+        let events = ScriptedEvents::new(&clock, script);
+        let mut terminal = Terminal::new(TestBackend::new(20, 5)).unwrap();
+        let reason = run_app_with(&mut terminal, app, events, RunConfig::new().clock(clock));
+
+        assert_eq!(reason.unwrap(), ExitReason::EventStreamEnded);
+        assert_eq!(*actions.borrow(), ["queue", "queue"]);
+    }
+
+    #[test]
+    fn test_bell_rings_on_request() {
+        struct BellApp {
+            ticks: u32,
+        }
+
+        impl TerminalApp for BellApp {
+            fn ui(&self, _frame: &mut Frame) {}
+
+            fn handle_event(&mut self, _event: Event) -> anyhow::Result<bool> {
+                Ok(false)
+            }
+
+            fn on_tick(&mut self) {
+                self.ticks += 1;
+            }
+
+            fn wants_bell(&mut self) -> bool {
+                self.ticks == 2
+            }
+        }
+
+        /// Writer whose output can still be read after the terminal takes it
+        #[derive(Clone, Default)]
+        struct SharedOutput(Rc<RefCell<Vec<u8>>>);
+
+        impl Write for SharedOutput {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0.borrow_mut().write(buf)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let bells = |config: RunConfig| {
+            let clock = VirtualClock::new();
+            let events = ScriptedEvents::new(&clock, [(200, key('q'))]);
+            let output = SharedOutput::default();
+            let mut terminal = Terminal::new(TestBackend::new(20, 5)).unwrap();
+            run_app_with(
+                &mut terminal,
+                BellApp { ticks: 0 },
+                events,
+                config.bell_writer(output.clone()).clock(clock),
+            )
+            .unwrap();
+            String::from_utf8_lossy(&output.0.borrow())
+                .matches('\x07')
+                .count()
+        };
+
+        assert_eq!(bells(RunConfig::new()), 1);
+        assert_eq!(bells(RunConfig::new().bell_enabled(false)), 0);
+    }
+
+    #[test]
+    fn test_bell_rings_on_the_terminal_stream() {
+        // The loop's default writer for a terminal set up on stderr
+        let (mut stdout, mut stderr) = (Vec::new(), Vec::new());
+        ring_bell(TerminalStream::Stderr.pick(&mut stdout, &mut stderr)).unwrap();
+        assert!(stdout.is_empty());
+        assert_eq!(stderr, b"\x07");
+    }
+
+    #[test]
+    fn test_overlays_drawn_over_ui() {
+        struct OverlayApp {
+            overlays: OverlayStack,
+        }
+
+        impl TerminalApp for OverlayApp {
+            fn ui(&self, frame: &mut Frame) {
+                frame.render_widget(Line::raw("base base"), frame.area());
+            }
+
+            fn handle_event(&mut self, _event: Event) -> anyhow::Result<bool> {
+                Ok(false)
+            }
+
+            fn overlays(&self) -> Option<&OverlayStack> {
+                Some(&self.overlays)
+            }
+        }
+
+        let mut overlays = OverlayStack::new();
+        overlays.push(0, ratatui::layout::Rect::new(2, 0, 3, 1), |area, buf| {
+            buf.set_string(area.x, area.y, "TOP", ratatui::style::Style::new());
+        });
+        let clock = VirtualClock::new();
+        let events = ScriptedEvents::new(&clock, [(0, key('q'))]);
+        let mut terminal = Terminal::new(TestBackend::new(10, 1)).unwrap();
+        let config = RunConfig::new().clock(clock);
+        run_app_with(&mut terminal, OverlayApp { overlays }, events, config).unwrap();
+
+        terminal.backend().assert_buffer_lines(["baTOPbase "]);
+    }
+
+    #[test]
+    fn test_cursor_controller_writes_only_changes() {
+        let mut cursor = CursorController::new();
+        let mut out = Vec::new();
+        assert!(cursor.apply(&mut out).unwrap());
+        assert_eq!(out, b"\x1b[0 q\x1b[?25h");
+
+        out.clear();
+        assert!(!cursor.apply(&mut out).unwrap());
+        // Blinking means nothing for the default shape
+        cursor.set_blinking(false);
+        assert!(!cursor.apply(&mut out).unwrap());
+        assert!(out.is_empty());
+
+        cursor.set_shape(Some(CursorShape::Bar));
+        cursor.apply(&mut out).unwrap();
+        assert_eq!(out, b"\x1b[6 q");
+
+        out.clear();
+        cursor.set_visible(false);
+        cursor.apply(&mut out).unwrap();
+        assert_eq!(out, b"\x1b[?25l");
+
+        out.clear();
+        cursor.invalidate();
+        cursor.apply(&mut out).unwrap();
+        assert_eq!(out, b"\x1b[6 q\x1b[?25l");
+    }
+
+    #[test]
+    fn test_post_process_edits_finished_frame() {
+        struct TextApp;
+
+        impl TerminalApp for TextApp {
+            fn ui(&self, frame: &mut Frame) {
+                frame.render_widget(Text::raw("one\ntwo\nsix"), frame.area());
+            }
+
+            fn handle_event(&mut self, _event: Event) -> anyhow::Result<bool> {
+                Ok(false)
+            }
+        }
+
+        let run = |config: RunConfig| {
+            let clock = VirtualClock::new();
+            let events = ScriptedEvents::new(&clock, [(0, key('q'))]);
+            let mut terminal = Terminal::new(TestBackend::new(3, 3)).unwrap();
+            run_app_with(&mut terminal, TextApp, events, config.clock(clock)).unwrap();
+            terminal.backend().buffer().clone()
+        };
+
+        let buffer = run(RunConfig::new().post_process(|buf: &mut Buffer| {
+            buf[(0, 0)].set_symbol("O");
+        }));
+        assert_eq!(buffer[(0, 0)].symbol(), "O");
+
+        let buffer = run(RunConfig::new().post_process(scanlines()));
+        let dimmed = |y| buffer[(0, y)].modifier.contains(Modifier::DIM);
+        assert_eq!([dimmed(0), dimmed(1), dimmed(2)], [false, true, false]);
+    }
+
+    /// Draws a single line, panicking if asked to draw into a zero-size area
+    struct SizedApp;
+
+    impl TerminalApp for SizedApp {
+        fn ui(&self, frame: &mut Frame) {
+            let area = frame.area();
+            assert!(!area.is_empty(), "drawn into a zero-size terminal");
+            frame.render_widget(Line::raw("app"), area);
+        }
+
+        fn handle_event(&mut self, _event: Event) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+    }
+
+    #[test]
+    fn test_zero_size_terminal_is_not_drawn() {
+        for (width, height) in [(0, 0), (10, 0), (0, 3)] {
+            let clock = VirtualClock::new();
+            let events = ScriptedEvents::new(&clock, [(200, key('q'))]);
+            let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+            let config = RunConfig::new().clock(clock);
+            let result = run_app_with(&mut terminal, SizedApp, events, config);
+            assert_eq!(result.unwrap(), ExitReason::QuitKey);
+        }
+    }
+
+    #[test]
+    fn test_min_terminal_size_placeholder() {
+        let run = |width, height| {
+            let clock = VirtualClock::new();
+            let events = ScriptedEvents::new(&clock, [(0, key('q'))]);
+            let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+            let config = RunConfig::new().min_terminal_size(20, 5).clock(clock);
+            run_app_with(&mut terminal, SizedApp, events, config).unwrap();
+            terminal
+        };
+
+        run(19, 4).backend().assert_buffer_lines([
+            "                   ",
+            "Terminal too small ",
+            "     need 20x5     ",
+            "                   ",
+        ]);
+        let terminal = run(20, 5);
+        assert_eq!(terminal.backend().buffer()[(0, 0)].symbol(), "a");
+    }
+
+    #[test]
+    fn test_idle_timeout_after_input_stops() {
+        struct IdleApp {
+            clock: VirtualClock,
+            start: Instant,
+            idled_at: Rc<RefCell<Vec<u128>>>,
+        }
+
+        impl TerminalApp for IdleApp {
+            fn ui(&self, _frame: &mut Frame) {}
+
+            fn handle_event(&mut self, _event: Event) -> anyhow::Result<bool> {
+                Ok(false)
+            }
+
+            fn on_idle(&mut self) -> anyhow::Result<bool> {
+                let mut idled_at = self.idled_at.borrow_mut();
+                idled_at.push((self.clock.now() - self.start).as_millis());
+                Ok(idled_at.len() == 2)
+            }
+        }
+
+        let clock = VirtualClock::new();
+        let idled_at = Rc::default();
+        let app = IdleApp {
+            clock: clock.clone(),
+            start: clock.now(),
+            idled_at: Rc::clone(&idled_at),
+        };
+        // Input every 800ms keeps the app awake, then the script goes quiet
+        let script = [
+            (0, key('a')),
+            (800, key('a')),
+            (1600, key('a')),
+            (60_000, key('q')),
+        ];
+        let events = ScriptedEvents::new(&clock, script);
+        let mut terminal = Terminal::new(TestBackend::new(20, 5)).unwrap();
+        let config = RunConfig::new()
+            .idle_timeout(Duration::from_secs(1))
+            .clock(clock);
+        let reason = run_app_with(&mut terminal, app, events, config).unwrap();
+
+        assert_eq!(reason, ExitReason::AppRequested);
+        assert_eq!(*idled_at.borrow(), [2600, 3600]);
+    }
+
+    #[test]
+    fn test_clean_app_skips_redraws() {
+        struct CleanApp {
+            draws: Rc<Cell<u32>>,
+            ticks: Rc<Cell<u32>>,
+        }
+
+        impl TerminalApp for CleanApp {
+            fn ui(&self, _frame: &mut Frame) {
+                self.draws.set(self.draws.get() + 1);
+            }
+
+            fn handle_event(&mut self, _event: Event) -> anyhow::Result<bool> {
+                Ok(false)
+            }
+
+            fn on_tick(&mut self) {
+                self.ticks.set(self.ticks.get() + 1);
+            }
+
+            fn is_dirty(&self) -> bool {
+                false
+            }
+        }
+
+        let (draws, ticks) = (Rc::default(), Rc::default());
+        let app = CleanApp {
+            draws: Rc::clone(&draws),
+            ticks: Rc::clone(&ticks),
+        };
+        let clock = VirtualClock::new();
+        let script = [(2000, Event::Resize(30, 8)), (5000, key('q'))];
+        let events = ScriptedEvents::new(&clock, script);
+        let mut terminal = Terminal::new(TestBackend::new(20, 5)).unwrap();
+        let config = RunConfig::new().clock(clock);
+        run_app_with(&mut terminal, app, events, config).unwrap();
+
+        // The first frame, then one more for the resize
+        assert_eq!(draws.get(), 2);
+        // Waiting a second at a time rather than 50ms while idle
+        assert!(ticks.get() < 10, "{} ticks", ticks.get());
+    }
+
+    #[test]
+    fn test_clean_app_redraws_when_toasts_expire() {
+        /// Only dirty until the next frame is drawn
+        struct FlagApp {
+            dirty: bool,
+            toasts: ToastStack,
+            draws: Rc<Cell<u32>>,
+        }
+
+        impl TerminalApp for FlagApp {
+            fn ui(&self, _frame: &mut Frame) {
+                self.draws.set(self.draws.get() + 1);
+            }
+
+            fn handle_event(&mut self, _event: Event) -> anyhow::Result<bool> {
+                Ok(false)
+            }
+
+            fn is_dirty(&self) -> bool {
+                self.dirty
+            }
+
+            fn on_frame_drawn(&mut self) {
+                self.dirty = false;
+            }
+
+            fn toasts(&mut self) -> Option<&mut ToastStack> {
+                Some(&mut self.toasts)
+            }
+        }
+
+        let clock = VirtualClock::new();
+        let mut toasts = ToastStack::new().lifetime(Duration::from_millis(500));
+        toasts.push_at("saved", StatusColor::Success, clock.now());
+        let draws = Rc::default();
+        let app = FlagApp {
+            dirty: true,
+            toasts,
+            draws: Rc::clone(&draws),
+        };
+        let events = ScriptedEvents::new(&clock, [(5000, key('q'))]);
+        let mut terminal = Terminal::new(TestBackend::new(20, 5)).unwrap();
+        run_app_with(&mut terminal, app, events, RunConfig::new().clock(clock)).unwrap();
+
+        // The first frame, then one more once the toast is gone
+        assert_eq!(draws.get(), 2);
+    }
+
+    #[test]
+    fn test_render_stats() {
+        struct CounterApp {
+            count: u32,
+            stats: Rc<RefCell<Vec<RenderStats>>>,
+        }
+
+        impl TerminalApp for CounterApp {
+            fn ui(&self, frame: &mut Frame) {
+                frame.render_widget(Line::raw(format!("count {}", self.count)), frame.area());
+            }
+
+            fn handle_event(&mut self, _event: Event) -> anyhow::Result<bool> {
+                self.count += 1;
+                Ok(false)
+            }
+
+            fn on_frame_rendered(&mut self, stats: RenderStats) {
+                self.stats.borrow_mut().push(stats);
+            }
+        }
+
+        let stats = Rc::default();
+        let app = CounterApp {
+            count: 0,
+            stats: Rc::clone(&stats),
+        };
+        let clock = VirtualClock::new();
+        let events = ScriptedEvents::new(&clock, [(120, key('a')), (200, key('q'))]);
+        let mut terminal = Terminal::new(TestBackend::new(20, 5)).unwrap();
+        let config = RunConfig::new().collect_render_stats(true).clock(clock);
+        run_app_with(&mut terminal, app, events, config).unwrap();
+
+        let changed: Vec<usize> = stats.borrow().iter().map(|s| s.changed_cells).collect();
+        // The first frame draws the six visible chars of "count 0", idle frames
+        // change nothing, and the key press changes a single digit
+        assert_eq!(changed[0], 6);
+        assert_eq!(changed[1], 0);
+        assert_eq!(changed.iter().filter(|&&n| n == 1).count(), 1);
+    }
+
+    #[test]
+    fn test_render_budget_coalesces_events() {
+        /// Every draw takes well over the frame budget
+        struct SlowApp {
+            clock: VirtualClock,
+            keys: u32,
+            handled_per_frame: Rc<RefCell<Vec<u32>>>,
+            stats: Rc<RefCell<Vec<RenderStats>>>,
+        }
+
+        impl TerminalApp for SlowApp {
+            fn ui(&self, _frame: &mut Frame) {
+                self.handled_per_frame.borrow_mut().push(self.keys);
+                self.clock.advance(Duration::from_millis(120));
+            }
+
+            fn handle_event(&mut self, _event: Event) -> anyhow::Result<bool> {
+                self.keys += 1;
+                Ok(false)
+            }
+
+            fn on_frame_rendered(&mut self, stats: RenderStats) {
+                self.stats.borrow_mut().push(stats);
+            }
+        }
+
+        let clock = VirtualClock::new();
+        let handled_per_frame = Rc::default();
+        let stats = Rc::default();
+        let app = SlowApp {
+            clock: clock.clone(),
+            keys: 0,
+            handled_per_frame: Rc::clone(&handled_per_frame),
+            stats: Rc::clone(&stats),
+        };
+        let mut script: Vec<(u64, Event)> = (0..30).map(|i| (i * 10, key('a'))).collect();
+        script.push((2000, key('q')));
+        let events = ScriptedEvents::new(&clock, script);
+        let mut terminal = Terminal::new(TestBackend::new(20, 5)).unwrap();
+        let config = RunConfig::new()
+            .render_budget(RenderBudget {
+                frame_time: Duration::from_millis(50),
+                coalesce_events: true,
+            })
+            .collect_render_stats(true)
+            .clock(clock);
+        run_app_with(&mut terminal, app, events, config).unwrap();
+
+        // Keys that queued up during a slow draw are all handled before the next one
+        let handled = handled_per_frame.borrow();
+        assert_eq!(handled[0], 0);
+        assert!(handled[1] > 1);
+        assert_eq!(*handled.last().unwrap(), 30);
+        assert!(handled.len() < 30);
+        // A 120ms draw against a 50ms budget drops two frames
+        assert!(stats.borrow().iter().all(|stats| stats.dropped_frames == 2));
+    }
+
+    #[test]
+    fn test_event_bursts_are_drained_before_drawing() {
+        let app = TestApp::new(&VirtualClock::new());
+        let clock = app.clock.clone();
+        let draws = Rc::clone(&app.draws);
+
+        // Ten keys arrive at once, then nothing until the quit key
+        let mut script: Vec<(u64, Event)> = (0..10).map(|_| (20, key('a'))).collect();
+        script.push((100, key('q')));
+        let events = ScriptedEvents::new(&clock, script.clone());
+        let mut terminal = Terminal::new(TestBackend::new(20, 5)).unwrap();
+        let config = RunConfig::new()
+            .poll_interval(Duration::from_millis(200))
+            .clock(clock);
+        run_app_with(&mut terminal, app, events, config).unwrap();
+        // One draw at the start and one after the whole burst
+        assert_eq!(*draws.borrow(), [Duration::ZERO, Duration::from_millis(20)]);
+
+        // A cap of three still draws between parts of the burst
+        let app = TestApp::new(&VirtualClock::new());
+        let clock = app.clock.clone();
+        let draws = Rc::clone(&app.draws);
+        let events = ScriptedEvents::new(&clock, script);
+        let config = RunConfig::new()
+            .poll_interval(Duration::from_millis(200))
+            .max_events_per_frame(3)
+            .clock(clock);
+        run_app_with(&mut terminal, app, events, config).unwrap();
+        assert_eq!(draws.borrow().len(), 5);
+    }
+
+    #[test]
+    fn test_min_frame_time() {
+        let app = TestApp::new(&VirtualClock::new());
+        let clock = app.clock.clone();
+        let draws = Rc::clone(&app.draws);
+
+        // Every key asks for a redraw, far faster than the minimum frame time
+        let mut script: Vec<(u64, Event)> = (0..20).map(|i| (i * 5, key('a'))).collect();
+        script.push((300, key('q')));
+        let events = ScriptedEvents::new(&clock, script);
+        let mut terminal = Terminal::new(TestBackend::new(20, 5)).unwrap();
+        let config = RunConfig::new()
+            .min_frame_time(Duration::from_millis(30))
+            .clock(clock);
+        run_app_with(&mut terminal, app, events, config).unwrap();
+
+        let draws = draws.borrow();
+        assert!(draws.len() > 2);
+        assert!(
+            draws
+                .windows(2)
+                .all(|pair| pair[1].saturating_sub(pair[0]) >= Duration::from_millis(30))
+        );
+    }
+}
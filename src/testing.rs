@@ -0,0 +1,96 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use crate::Clock;
+use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// A clock that only moves when something advances it
+///
+/// Clones share the same time, so a test can hand one copy to the run loop and keep
+/// another to advance or inspect. Sleeping advances the clock instead of blocking.
+#[derive(Clone, Debug)]
+pub struct VirtualClock {
+    now: Rc<Cell<Instant>>,
+}
+
+impl VirtualClock {
+    /// Create a clock starting at the current instant
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            now: Rc::new(Cell::new(Instant::now())),
+        }
+    }
+
+    /// Move the clock forward
+    pub fn advance(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration);
+    }
+}
+
+impl Default for VirtualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for VirtualClock {
+    fn now(&self) -> Instant {
+        self.now.get()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+    }
+}
+
+/// Render a widget into a fresh buffer and return its rows joined by newlines
+///
+/// Each row contains exactly `width` cells, so trailing spaces are kept. This makes
+/// it easy to compare a widget's output against an expected multiline string.
+#[must_use]
+pub fn render_to_string(widget: impl Widget, width: u16, height: u16) -> String {
+    let area = Rect::new(0, 0, width, height);
+    let mut buffer = Buffer::empty(area);
+    widget.render(area, &mut buffer);
+    buffer_to_string(&buffer)
+}
+
+/// Flatten a buffer's symbols into newline-separated rows
+#[must_use]
+pub fn buffer_to_string(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    (area.top()..area.bottom())
+        .map(|y| {
+            (area.left()..area.right())
+                .map(|x| buffer[(x, y)].symbol())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Card, StatusColor, StatusIndicator};
+    use ratatui::text::Line;
+
+    #[test]
+    fn test_render_status_indicator() {
+        let indicator = StatusIndicator::new(StatusColor::Success).label("Running");
+        assert_eq!(
+            render_to_string(indicator, 12, 2),
+            "● Running   \n            "
+        );
+    }
+
+    #[test]
+    fn test_render_card() {
+        let card = Card::new().title("Hi").add_line(Line::from("there"));
+        let expected = ["┌Hi─────┐", "│there  │", "└───────┘"].join("\n");
+        assert_eq!(render_to_string(card, 9, 3), expected);
+    }
+}
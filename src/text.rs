@@ -0,0 +1,317 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::charset::CharsetProfile;
+
+/// Number of terminal columns `text` takes up
+///
+/// Wide characters such as CJK ideographs count as two columns and combining marks as
+/// none. Widths past `u16::MAX` saturate.
+#[must_use]
+pub fn text_width(text: &str) -> u16 {
+    u16::try_from(text.width()).unwrap_or(u16::MAX)
+}
+
+/// Cut `text` so it takes up at most `width` columns
+///
+/// Text is cut between grapheme clusters, so combining marks stay with the character
+/// they modify and a wide character that would straddle the limit is dropped whole
+/// rather than split. The result may be one column short of `width` as a result.
+#[must_use]
+pub fn truncate_to_width(text: &str, width: u16) -> &str {
+    let width = usize::from(width);
+    let mut columns = 0;
+    for (index, grapheme) in text.grapheme_indices(true) {
+        columns += grapheme.width();
+        if columns > width {
+            return &text[..index];
+        }
+    }
+    text
+}
+
+/// Shorten `text` to at most `max_width` columns, cutting after the last whole word
+///
+/// Text that already fits is returned unchanged. Otherwise the words that fit are
+/// kept, with the whitespace after them dropped, and `…` (or `.` under the ASCII
+/// [`CharsetProfile`]) is appended within the width. Only when the first word alone
+/// doesn't fit is it cut mid-word, at a grapheme boundary as in [`truncate_to_width`].
+#[must_use]
+pub fn truncate_words(text: &str, max_width: u16) -> String {
+    if text_width(text) <= max_width {
+        return text.to_string();
+    }
+    let ellipsis = CharsetProfile::current().pick("…", ".");
+    let Some(budget) = max_width.checked_sub(text_width(ellipsis)) else {
+        return String::new();
+    };
+
+    let mut kept = None;
+    for (index, c) in text.char_indices() {
+        if !c.is_whitespace() {
+            continue;
+        }
+        let words = text[..index].trim_end();
+        if text_width(words) > budget {
+            break;
+        }
+        if !words.is_empty() {
+            kept = Some(words);
+        }
+    }
+    let kept = kept.unwrap_or_else(|| truncate_to_width(text, budget).trim_end());
+    format!("{kept}{ellipsis}")
+}
+
+/// Score for each query character matched by [`fuzzy_match`]
+const FUZZY_MATCH: i64 = 16;
+
+/// Extra score for a match right after the previous one
+const FUZZY_CONSECUTIVE: i64 = 16;
+
+/// Extra score for a match on the first character of the candidate
+const FUZZY_PREFIX: i64 = 24;
+
+/// Extra score for a match at the start of a later word
+const FUZZY_WORD_START: i64 = 12;
+
+/// Match `query` as a subsequence of `candidate`, ignoring case
+///
+/// Returns the score of the best alignment and the character indices in `candidate`
+/// it matched, or `None` if the query characters don't all appear in order. Runs of
+/// consecutive matches, a match on the first character, and matches at word starts
+/// (after a space or punctuation, or a lowercase-to-uppercase change) score higher,
+/// and each skipped character costs a point, so "op" ranks "Open" above
+/// "Other Panel". An empty query matches everything with a score of zero.
+#[must_use]
+#[allow(clippy::cast_possible_wrap)]
+pub fn fuzzy_match(query: &str, candidate: &str) -> Option<(i64, Vec<usize>)> {
+    let query: Vec<char> = query.chars().collect();
+    let chars: Vec<char> = candidate.chars().collect();
+    if query.is_empty() {
+        return Some((0, Vec::new()));
+    }
+
+    let matches = |q: char, c: char| q == c || q.to_lowercase().eq(c.to_lowercase());
+    let bonus = |index: usize| {
+        if index == 0 {
+            return FUZZY_PREFIX;
+        }
+        let (prev, current) = (chars[index - 1], chars[index]);
+        let word_start = !prev.is_alphanumeric() && current.is_alphanumeric()
+            || prev.is_lowercase() && current.is_uppercase();
+        if word_start { FUZZY_WORD_START } else { 0 }
+    };
+
+    // best[i][j] is the top score for the first i + 1 query characters with the last
+    // one matched at candidate index j, and from[i][j] the index of the one before
+    let mut best = vec![vec![None; chars.len()]; query.len()];
+    let mut from = vec![vec![0; chars.len()]; query.len()];
+    for (j, &c) in chars.iter().enumerate() {
+        if matches(query[0], c) {
+            best[0][j] = Some(FUZZY_MATCH + bonus(j) - j as i64);
+        }
+    }
+    for i in 1..query.len() {
+        for j in i..chars.len() {
+            if !matches(query[i], chars[j]) {
+                continue;
+            }
+            for k in i - 1..j {
+                let Some(prev) = best[i - 1][k] else {
+                    continue;
+                };
+                let step = if k + 1 == j {
+                    FUZZY_CONSECUTIVE
+                } else {
+                    -((j - k - 1) as i64)
+                };
+                let score = prev + FUZZY_MATCH + bonus(j) + step;
+                if best[i][j].is_none_or(|best| score > best) {
+                    best[i][j] = Some(score);
+                    from[i][j] = k;
+                }
+            }
+        }
+    }
+
+    let last = query.len() - 1;
+    let (mut index, score) = best[last]
+        .iter()
+        .enumerate()
+        .filter_map(|(j, score)| score.map(|score| (j, score)))
+        // Earlier alignments win ties
+        .min_by_key(|&(j, score)| (std::cmp::Reverse(score), j))?;
+    let mut indices = vec![index; query.len()];
+    for i in (1..=last).rev() {
+        index = from[i][index];
+        indices[i - 1] = index;
+    }
+    Some((score, indices))
+}
+
+/// Rows in each [`big_text`] glyph
+pub const BIG_TEXT_HEIGHT: usize = 5;
+
+/// Columns in each [`big_text`] glyph
+const BIG_GLYPH_WIDTH: usize = 5;
+
+/// The 5x5 block font used by [`big_text`], with `#` for a filled cell
+fn big_glyph(c: char) -> [&'static str; BIG_TEXT_HEIGHT] {
+    match c.to_ascii_uppercase() {
+        'A' => [" ### ", "#   #", "#####", "#   #", "#   #"],
+        'B' => ["#### ", "#   #", "#### ", "#   #", "#### "],
+        'C' => [" ####", "#    ", "#    ", "#    ", " ####"],
+        'D' => ["#### ", "#   #", "#   #", "#   #", "#### "],
+        'E' => ["#####", "#    ", "#### ", "#    ", "#####"],
+        'F' => ["#####", "#    ", "#### ", "#    ", "#    "],
+        'G' => [" ####", "#    ", "#  ##", "#   #", " ####"],
+        'H' => ["#   #", "#   #", "#####", "#   #", "#   #"],
+        'I' => ["#####", "  #  ", "  #  ", "  #  ", "#####"],
+        'J' => ["#####", "   # ", "   # ", "#  # ", " ##  "],
+        'K' => ["#   #", "#  # ", "###  ", "#  # ", "#   #"],
+        'L' => ["#    ", "#    ", "#    ", "#    ", "#####"],
+        'M' => ["#   #", "## ##", "# # #", "#   #", "#   #"],
+        'N' => ["#   #", "##  #", "# # #", "#  ##", "#   #"],
+        'O' => [" ### ", "#   #", "#   #", "#   #", " ### "],
+        'P' => ["#### ", "#   #", "#### ", "#    ", "#    "],
+        'Q' => [" ### ", "#   #", "# # #", "#  # ", " ## #"],
+        'R' => ["#### ", "#   #", "#### ", "#  # ", "#   #"],
+        'S' => [" ####", "#    ", " ### ", "    #", "#### "],
+        'T' => ["#####", "  #  ", "  #  ", "  #  ", "  #  "],
+        'U' => ["#   #", "#   #", "#   #", "#   #", " ### "],
+        'V' => ["#   #", "#   #", "#   #", " # # ", "  #  "],
+        'W' => ["#   #", "#   #", "# # #", "## ##", "#   #"],
+        'X' => ["#   #", " # # ", "  #  ", " # # ", "#   #"],
+        'Y' => ["#   #", " # # ", "  #  ", "  #  ", "  #  "],
+        'Z' => ["#####", "   # ", "  #  ", " #   ", "#####"],
+        '0' => [" ### ", "#  ##", "# # #", "##  #", " ### "],
+        '1' => ["  #  ", " ##  ", "  #  ", "  #  ", " ### "],
+        '2' => [" ### ", "#   #", "  ## ", " #   ", "#####"],
+        '3' => ["#### ", "    #", " ### ", "    #", "#### "],
+        '4' => ["#   #", "#   #", "#####", "    #", "    #"],
+        '5' => ["#####", "#    ", "#### ", "    #", "#### "],
+        '6' => [" ### ", "#    ", "#### ", "#   #", " ### "],
+        '7' => ["#####", "    #", "   # ", "  #  ", "  #  "],
+        '8' => [" ### ", "#   #", " ### ", "#   #", " ### "],
+        '9' => [" ### ", "#   #", " ####", "    #", " ### "],
+        _ => ["     "; BIG_TEXT_HEIGHT],
+    }
+}
+
+/// Render `text` in large block letters, one string per row
+///
+/// Letters, digits, and spaces come from a built-in 5x5 font, with lowercase letters
+/// drawn as capitals and any other character as a blank glyph. Glyphs are separated by
+/// a blank column and every row has the same width, so the result can be joined with
+/// newlines and shown with [`Banner`](crate::Banner). Cells are drawn with `█`, or `#`
+/// under the ASCII [`CharsetProfile`].
+#[must_use]
+pub fn big_text(text: &str) -> Vec<String> {
+    let fill = CharsetProfile::current().pick('█', '#');
+    let glyphs: Vec<_> = text.chars().map(big_glyph).collect();
+    (0..BIG_TEXT_HEIGHT)
+        .map(|row| {
+            let mut line = String::with_capacity(glyphs.len() * (BIG_GLYPH_WIDTH + 1) * 3);
+            for (index, glyph) in glyphs.iter().enumerate() {
+                if index > 0 {
+                    line.push(' ');
+                }
+                line.extend(
+                    glyph[row]
+                        .chars()
+                        .map(|c| if c == '#' { fill } else { ' ' }),
+                );
+            }
+            line
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::charset::with_charset;
+
+    #[test]
+    fn test_truncate_words_at_word_boundary() {
+        assert_eq!(
+            truncate_words("the quick brown fox", 19),
+            "the quick brown fox"
+        );
+        assert_eq!(
+            truncate_words("the quick brown fox", 16),
+            "the quick brown…"
+        );
+        assert_eq!(truncate_words("the quick brown fox", 15), "the quick…");
+        assert_eq!(truncate_words("the  quick", 8), "the…");
+        // Wide characters count as two columns
+        assert_eq!(truncate_words("日本 語です", 6), "日本…");
+        with_charset(CharsetProfile::Ascii, || {
+            assert_eq!(truncate_words("the quick brown fox", 11), "the quick.");
+        });
+    }
+
+    #[test]
+    fn test_truncate_words_cuts_long_word() {
+        assert_eq!(truncate_words("internationalization", 8), "interna…");
+        assert_eq!(truncate_words("supercalifragilistic word", 6), "super…");
+        assert_eq!(truncate_words("日本語", 4), "日…");
+        assert_eq!(truncate_words("word", 1), "…");
+        assert_eq!(truncate_words("word", 0), "");
+    }
+
+    #[test]
+    fn test_fuzzy_match_prefers_contiguous_prefix() {
+        let (prefix, indices) = fuzzy_match("op", "Open").unwrap();
+        assert_eq!(indices, vec![0, 1]);
+        let (scattered, indices) = fuzzy_match("op", "Other Panel").unwrap();
+        assert_eq!(indices, vec![0, 6]);
+        assert!(prefix > scattered);
+
+        // The best alignment is found even when the first 'o' leads nowhere good
+        assert_eq!(
+            fuzzy_match("set", "sort settings").unwrap().1,
+            vec![5, 6, 7]
+        );
+        assert_eq!(fuzzy_match("xyz", "Open"), None);
+        assert_eq!(fuzzy_match("", "Open"), Some((0, Vec::new())));
+    }
+
+    #[test]
+    fn test_text_width_counts_columns() {
+        assert_eq!(text_width("abc"), 3);
+        assert_eq!(text_width("a漢b"), 4);
+        // "e" followed by a combining acute accent
+        assert_eq!(text_width("cafe\u{301}"), 4);
+        assert_eq!(text_width(""), 0);
+    }
+
+    #[test]
+    fn test_truncate_never_splits_wide_or_combined_chars() {
+        let text = "ab漢e\u{301}z";
+        assert_eq!(truncate_to_width(text, 2), "ab");
+        // The wide char would need columns 3 and 4, so it is dropped whole
+        assert_eq!(truncate_to_width(text, 3), "ab");
+        assert_eq!(truncate_to_width(text, 4), "ab漢");
+        // The accent stays with its "e"
+        assert_eq!(truncate_to_width(text, 5), "ab漢e\u{301}");
+        assert_eq!(truncate_to_width(text, 10), text);
+        assert_eq!(truncate_to_width(text, 0), "");
+    }
+
+    #[test]
+    fn test_big_text_rows() {
+        let rows = with_charset(CharsetProfile::Unicode, || big_text("HI"));
+        assert_eq!(rows.len(), BIG_TEXT_HEIGHT);
+        assert!(rows.iter().all(|row| text_width(row) == 11));
+        assert_eq!(rows[0], "█   █ █████");
+        assert_eq!(rows[2], "█████   █  ");
+
+        // Unknown characters are blank glyphs and lowercase is drawn as capitals
+        let rows = with_charset(CharsetProfile::Ascii, || big_text("i?"));
+        assert_eq!(rows[0], "#####      ");
+    }
+}
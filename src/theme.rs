@@ -0,0 +1,208 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+use ratatui::style::Color;
+
+use crate::color::ColorDepth;
+use crate::widgets::StatusColor;
+
+/// Named color roles shared by the widgets of an app
+///
+/// Widgets with a `themed(&theme)` builder take their styles from these roles, so an
+/// app can be recolored in one place. [`StatusColor`]s resolve through
+/// [`status_color`](Self::status_color), and the default theme is [`Theme::dark`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Accent for focused and highlighted elements
+    pub primary: Color,
+    /// Secondary accent
+    pub secondary: Color,
+    /// Borders and separators
+    pub border: Color,
+    /// Color for [`StatusColor::Success`]
+    pub success: Color,
+    /// Color for [`StatusColor::Warning`]
+    pub warning: Color,
+    /// Color for [`StatusColor::Error`]
+    pub error: Color,
+    /// Color for [`StatusColor::Info`]
+    pub info: Color,
+    /// Color for [`StatusColor::Neutral`]
+    pub neutral: Color,
+    /// Background behind widget content
+    pub background: Color,
+    /// Regular text
+    pub text: Color,
+}
+
+impl Theme {
+    /// A theme for dark terminals using the standard ANSI colors
+    #[must_use]
+    pub fn dark() -> Self {
+        Self {
+            primary: Color::Cyan,
+            secondary: Color::Magenta,
+            border: Color::Gray,
+            success: Color::Green,
+            warning: Color::Yellow,
+            error: Color::Red,
+            info: Color::Blue,
+            neutral: Color::Gray,
+            background: Color::Reset,
+            text: Color::White,
+        }
+    }
+
+    /// A theme for light terminals, with darker accents that read on a white background
+    #[must_use]
+    pub fn light() -> Self {
+        Self {
+            primary: Color::Rgb(0, 95, 175),
+            secondary: Color::Rgb(135, 0, 135),
+            border: Color::Rgb(128, 128, 128),
+            success: Color::Rgb(0, 128, 0),
+            warning: Color::Rgb(175, 95, 0),
+            error: Color::Rgb(175, 0, 0),
+            info: Color::Rgb(0, 95, 175),
+            neutral: Color::Rgb(88, 88, 88),
+            background: Color::Rgb(250, 250, 250),
+            text: Color::Rgb(30, 30, 30),
+        }
+    }
+
+    /// The dark theme with status colors that stay distinct with red-green color
+    /// blindness
+    ///
+    /// Uses the Okabe-Ito palette: blue for success, vermillion for errors, yellow for
+    /// warnings, and sky blue for info.
+    #[must_use]
+    pub fn color_blind_safe() -> Self {
+        Self {
+            success: Color::Rgb(0, 114, 178),
+            warning: Color::Rgb(240, 228, 66),
+            error: Color::Rgb(213, 94, 0),
+            info: Color::Rgb(86, 180, 233),
+            ..Self::dark()
+        }
+    }
+
+    /// Get the color this theme uses for a status
+    ///
+    /// The color is reduced to the current [`ColorDepth`], custom colors included.
+    #[must_use]
+    pub fn status_color(&self, status: StatusColor) -> Color {
+        let color = match status {
+            StatusColor::Success => self.success,
+            StatusColor::Warning => self.warning,
+            StatusColor::Error => self.error,
+            StatusColor::Info => self.info,
+            StatusColor::Neutral => self.neutral,
+            StatusColor::Custom(color) => color,
+        };
+        ColorDepth::current().apply(color)
+    }
+
+    /// Reduce every color of the theme so `depth` can display it
+    #[must_use]
+    pub fn for_depth(self, depth: ColorDepth) -> Self {
+        Self {
+            primary: depth.apply(self.primary),
+            secondary: depth.apply(self.secondary),
+            border: depth.apply(self.border),
+            success: depth.apply(self.success),
+            warning: depth.apply(self.warning),
+            error: depth.apply(self.error),
+            info: depth.apply(self.info),
+            neutral: depth.apply(self.neutral),
+            background: depth.apply(self.background),
+            text: depth.apply(self.text),
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+/// Which palette [`StatusColor`]s resolve through
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorProfile {
+    /// The usual green, yellow, and red of [`Theme::dark`]
+    #[default]
+    Default,
+    /// The palette of [`Theme::color_blind_safe`]
+    ColorBlindSafe,
+}
+
+impl ColorProfile {
+    /// Get the theme for this profile
+    #[must_use]
+    pub fn theme(self) -> Theme {
+        match self {
+            Self::Default => Theme::dark(),
+            Self::ColorBlindSafe => Theme::color_blind_safe(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_colors_follow_theme() {
+        let light = Theme::light();
+        assert_eq!(light.status_color(StatusColor::Error), light.error);
+        assert_eq!(
+            light.status_color(StatusColor::Custom(Color::Indexed(42))),
+            Color::Indexed(42)
+        );
+        // The dark theme matches the plain conversion
+        assert_eq!(
+            Theme::dark().status_color(StatusColor::Warning),
+            Color::from(StatusColor::Warning)
+        );
+    }
+
+    #[test]
+    fn test_theme_colors_follow_color_depth() {
+        ColorDepth::set_current(ColorDepth::Ansi256);
+        let light = Theme::light();
+        assert_eq!(light.status_color(StatusColor::Error), Color::Indexed(124));
+        assert_eq!(
+            light.status_color(StatusColor::Custom(Color::Rgb(255, 0, 0))),
+            Color::Indexed(196)
+        );
+
+        let reduced = light.for_depth(ColorDepth::Ansi256);
+        assert_eq!(reduced.background, Color::Indexed(231));
+        assert_eq!(reduced.border, Color::Indexed(244));
+        assert_eq!(Theme::dark().for_depth(ColorDepth::Ansi16), Theme::dark());
+    }
+
+    #[test]
+    fn test_color_blind_safe_profile() {
+        let profile = ColorProfile::ColorBlindSafe;
+        assert_eq!(
+            StatusColor::Success.to_color_with_profile(profile),
+            Color::Rgb(0, 114, 178)
+        );
+        assert_eq!(
+            StatusColor::Error.to_color_with_profile(profile),
+            Color::Rgb(213, 94, 0)
+        );
+        assert_eq!(
+            StatusColor::Neutral.to_color_with_profile(profile),
+            Color::Gray
+        );
+        assert_eq!(
+            StatusColor::Custom(Color::Indexed(42)).to_color_with_profile(profile),
+            Color::Indexed(42)
+        );
+        assert_eq!(
+            StatusColor::Error.to_color_with_profile(ColorProfile::Default),
+            Color::Red
+        );
+    }
+}
@@ -1,29 +1,176 @@
 #![warn(clippy::all, clippy::pedantic)]
 
+use crossterm::event::{
+    KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, Paragraph, Widget},
+    symbols::border,
+    widgets::{Block, Borders, Clear, Paragraph, Widget, Wrap},
 };
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::collections::{BTreeSet, VecDeque};
+use std::ops::Range;
+use std::time::{Duration, Instant};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::charset::{ASCII_BORDER, CharsetProfile};
+use crate::color::{ColorDepth, Gradient, darken};
+use crate::input::InputHistory;
+use crate::layout::{Corner, centered_rect_with_size, corner_rect_with_size};
+use crate::runtime::RenderStats;
+use crate::text::{fuzzy_match, text_width, truncate_to_width};
+use crate::theme::{ColorProfile, Theme};
+
+/// Widgets that need a minimum area to render legibly
+///
+/// Pair with [`render_or_placeholder`] to show a placeholder in cells too small for the
+/// widget, such as tight [`ResponsiveGrid`](crate::ResponsiveGrid) cells.
+pub trait MinSize {
+    /// Smallest `(width, height)` the widget renders legibly in
+    fn min_size(&self) -> (u16, u16);
+}
+
+/// Render `widget` if `area` meets its [`MinSize`], or a small placeholder if not
+///
+/// The placeholder is an ellipsis in the middle of the area.
+pub fn render_or_placeholder<W: Widget + MinSize>(widget: W, area: Rect, buf: &mut Buffer) {
+    let (min_width, min_height) = widget.min_size();
+    if area.width >= min_width && area.height >= min_height {
+        widget.render(area, buf);
+        return;
+    }
+    let area = area.intersection(buf.area);
+    if area.is_empty() {
+        return;
+    }
+    Clear.render(area, buf);
+    let center = Position::new(area.x + area.width / 2, area.y + area.height / 2);
+    buf[center]
+        .set_symbol(CharsetProfile::current().pick("…", "."))
+        .set_fg(Color::DarkGray);
+}
 
 /// Status indicator colors
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum StatusColor {
     Success,
     Warning,
     Error,
     Info,
+    Neutral,
     Custom(Color),
 }
 
+impl StatusColor {
+    /// Get the next status in the order Success, Warning, Error, Info, Neutral
+    ///
+    /// Wraps back to `Success` after `Neutral`. `Custom` colors are not part of the
+    /// cycle and also step to `Success`.
+    #[must_use]
+    pub fn cycle(&self) -> StatusColor {
+        match self {
+            StatusColor::Success => StatusColor::Warning,
+            StatusColor::Warning => StatusColor::Error,
+            StatusColor::Error => StatusColor::Info,
+            StatusColor::Info => StatusColor::Neutral,
+            StatusColor::Neutral | StatusColor::Custom(_) => StatusColor::Success,
+        }
+    }
+
+    /// Resolve the status through the theme of a color profile
+    #[must_use]
+    pub fn to_color_with_profile(self, profile: ColorProfile) -> Color {
+        profile.theme().status_color(self)
+    }
+
+    /// A symbol telling the status apart without relying on its color
+    fn symbol(self) -> &'static str {
+        let charset = CharsetProfile::current();
+        match self {
+            StatusColor::Success => charset.pick("✓", "+"),
+            StatusColor::Warning => "!",
+            StatusColor::Error => charset.pick("✕", "x"),
+            StatusColor::Info => "i",
+            StatusColor::Neutral | StatusColor::Custom(_) => charset.pick("●", "*"),
+        }
+    }
+}
+
+/// Maps values to status colors by boundaries, such as green, then yellow, then red
+///
+/// By default higher values are worse: a value at or above a boundary takes that
+/// boundary's status, and values below every boundary take the base status. With
+/// [`descending`](Self::descending) lower values are worse instead: a value at or
+/// below a boundary takes its status, and values above every boundary take the base.
+/// NaN always maps to the base status.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Thresholds {
+    base: StatusColor,
+    /// Sorted by boundary
+    steps: Vec<(f64, StatusColor)>,
+    descending: bool,
+}
+
+impl Thresholds {
+    /// Create thresholds that map every value to `base` until steps are added
+    #[must_use]
+    pub fn new(base: StatusColor) -> Self {
+        Self {
+            base,
+            steps: Vec::new(),
+            descending: false,
+        }
+    }
+
+    /// Add a boundary and the status for values past it
+    ///
+    /// Steps may be added in any order. A NaN boundary is ignored.
+    #[must_use]
+    pub fn step(mut self, boundary: f64, status: StatusColor) -> Self {
+        if !boundary.is_nan() {
+            let index = self.steps.partition_point(|&(step, _)| step <= boundary);
+            self.steps.insert(index, (boundary, status));
+        }
+        self
+    }
+
+    /// Set whether lower values are worse, as for time remaining
+    #[must_use]
+    pub fn descending(mut self, descending: bool) -> Self {
+        self.descending = descending;
+        self
+    }
+
+    /// Get the status for a value
+    #[must_use]
+    pub fn color_for(&self, value: f64) -> StatusColor {
+        if value.is_nan() {
+            return self.base;
+        }
+        let step = if self.descending {
+            // The lowest boundary the value is at or below
+            let index = self
+                .steps
+                .partition_point(|&(boundary, _)| boundary < value);
+            self.steps.get(index)
+        } else {
+            // The highest boundary the value is at or above
+            let index = self
+                .steps
+                .partition_point(|&(boundary, _)| boundary <= value);
+            index.checked_sub(1).map(|index| &self.steps[index])
+        };
+        step.map_or(self.base, |&(_, status)| status)
+    }
+}
+
 impl From<StatusColor> for Color {
+    /// Resolve the status through the default [`Theme`]
     fn from(status: StatusColor) -> Self {
-        match status {
-            StatusColor::Success => Color::Green,
-            StatusColor::Warning => Color::Yellow,
-            StatusColor::Error => Color::Red,
-            StatusColor::Info => Color::Blue,
-            StatusColor::Custom(color) => color,
-        }
+        Theme::default().status_color(status)
     }
 }
 
@@ -33,6 +180,8 @@ pub struct StatusIndicator<'a> {
     status: StatusColor,
     label: Option<&'a str>,
     style: Style,
+    theme: Theme,
+    with_symbols: bool,
 }
 
 impl<'a> StatusIndicator<'a> {
@@ -43,6 +192,8 @@ impl<'a> StatusIndicator<'a> {
             status,
             label: None,
             style: Style::default(),
+            theme: Theme::default(),
+            with_symbols: false,
         }
     }
 
@@ -59,6 +210,24 @@ impl<'a> StatusIndicator<'a> {
         self.style = style;
         self
     }
+
+    /// Take the status color and label color from a theme
+    #[must_use]
+    pub fn themed(mut self, theme: &Theme) -> Self {
+        self.theme = *theme;
+        self.style = self.style.fg(ColorDepth::current().apply(theme.text));
+        self
+    }
+
+    /// Show a symbol for each status (✓, !, ✕, i) instead of the same dot for all
+    ///
+    /// Pair with [`ColorProfile::ColorBlindSafe`] so the status doesn't rest on
+    /// color alone.
+    #[must_use]
+    pub fn with_symbols(mut self, with_symbols: bool) -> Self {
+        self.with_symbols = with_symbols;
+        self
+    }
 }
 
 impl Widget for StatusIndicator<'_> {
@@ -67,8 +236,12 @@ impl Widget for StatusIndicator<'_> {
             return;
         }
 
-        let status_color: Color = self.status.into();
-        let status_dot = "●";
+        let status_color = self.theme.status_color(self.status);
+        let status_dot = if self.with_symbols {
+            self.status.symbol()
+        } else {
+            CharsetProfile::current().pick("●", "*")
+        };
 
         let mut content = vec![Span::styled(status_dot, self.style.fg(status_color))];
 
@@ -81,13 +254,125 @@ impl Widget for StatusIndicator<'_> {
     }
 }
 
+impl MinSize for StatusIndicator<'_> {
+    /// Just the status dot; the label is cut short to fit
+    fn min_size(&self) -> (u16, u16) {
+        (1, 1)
+    }
+}
+
+/// The end caps drawn on either side of a [`Badge`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BadgeCaps {
+    /// Half blocks in the badge color, which round the ends off on most fonts
+    #[default]
+    HalfBlock,
+    /// Parentheses around the filled text
+    Parens,
+}
+
+/// A small filled pill for counts and tags
+///
+/// The badge is sized to its text plus one column of padding and a cap on each side,
+/// and sits on the middle row of taller areas. Text that doesn't fit is cut short
+/// with an ellipsis.
+#[derive(Debug, Clone)]
+pub struct Badge<'a> {
+    text: &'a str,
+    status: StatusColor,
+    caps: BadgeCaps,
+    style: Style,
+    theme: Theme,
+}
+
+impl<'a> Badge<'a> {
+    /// Create a badge showing `text`
+    #[must_use]
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            status: StatusColor::Info,
+            caps: BadgeCaps::default(),
+            style: Style::default().fg(Color::Black),
+            theme: Theme::default(),
+        }
+    }
+
+    /// Set the fill color of the badge
+    #[must_use]
+    pub fn status(mut self, status: StatusColor) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Set the end caps of the badge
+    #[must_use]
+    pub fn caps(mut self, caps: BadgeCaps) -> Self {
+        self.caps = caps;
+        self
+    }
+
+    /// Set the style of the text inside the badge
+    #[must_use]
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Take the fill color from a theme
+    #[must_use]
+    pub fn themed(mut self, theme: &Theme) -> Self {
+        self.theme = *theme;
+        self
+    }
+
+    /// Get the width the badge needs to show its whole text
+    #[must_use]
+    pub fn preferred_width(&self) -> u16 {
+        u16::try_from(self.text.width())
+            .unwrap_or(u16::MAX)
+            .saturating_add(4)
+    }
+}
+
+impl Widget for Badge<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let width = self.preferred_width().min(area.width);
+        if width < 2 || area.height == 0 {
+            return;
+        }
+
+        let color = self.theme.status_color(self.status);
+        let (left, right, cap_style) = match self.caps {
+            BadgeCaps::HalfBlock if !CharsetProfile::current().is_ascii() => {
+                ("▐", "▌", Style::default().fg(color))
+            }
+            _ => ("(", ")", self.style.bg(color)),
+        };
+        let y = area.y + (area.height - 1) / 2;
+        let fill = Rect::new(area.x + 1, y, width - 2, 1);
+
+        buf.set_string(area.x, y, left, cap_style);
+        buf.set_style(fill, self.style.bg(color));
+        let text = fit_span(&Span::raw(self.text), fill.width.saturating_sub(2));
+        buf.set_span(fill.x + 1, y, &text, fill.width.saturating_sub(2));
+        buf.set_string(fill.right(), y, right, cap_style);
+    }
+}
+
 /// A widget that displays a bordered box with a title and content
 #[derive(Debug, Clone)]
 pub struct Card<'a> {
     title: Option<&'a str>,
+    top_right: Option<&'a str>,
+    bottom_left: Option<&'a str>,
+    bottom_right: Option<&'a str>,
     content: Vec<Line<'a>>,
     style: Style,
     border_style: Style,
+    border_set: Option<border::Set>,
+    wrap: bool,
+    alignment: Alignment,
 }
 
 impl<'a> Card<'a> {
@@ -96,9 +381,15 @@ impl<'a> Card<'a> {
     pub fn new() -> Self {
         Self {
             title: None,
+            top_right: None,
+            bottom_left: None,
+            bottom_right: None,
             content: Vec::new(),
             style: Style::default(),
             border_style: Style::default(),
+            border_set: None,
+            wrap: false,
+            alignment: Alignment::Left,
         }
     }
 
@@ -109,6 +400,31 @@ impl<'a> Card<'a> {
         self
     }
 
+    /// Set a label for the right end of the top border
+    ///
+    /// The main title takes priority; this label is truncated to fit beside it.
+    #[must_use]
+    pub fn top_right(mut self, label: &'a str) -> Self {
+        self.top_right = Some(label);
+        self
+    }
+
+    /// Set a label for the left end of the bottom border
+    #[must_use]
+    pub fn bottom_left(mut self, label: &'a str) -> Self {
+        self.bottom_left = Some(label);
+        self
+    }
+
+    /// Set a label for the right end of the bottom border
+    ///
+    /// The bottom-left label takes priority; this label is truncated to fit beside it.
+    #[must_use]
+    pub fn bottom_right(mut self, label: &'a str) -> Self {
+        self.bottom_right = Some(label);
+        self
+    }
+
     /// Add a line of content to the card
     #[must_use]
     pub fn add_line(mut self, line: Line<'a>) -> Self {
@@ -136,27 +452,176 @@ impl<'a> Card<'a> {
         self.border_style = style;
         self
     }
+
+    /// Draw the border with custom corner and edge glyphs
+    ///
+    /// The set is used as given, whatever the [`CharsetProfile`]. Without one the
+    /// border follows the profile.
+    #[must_use]
+    pub fn border_set(mut self, set: border::Set) -> Self {
+        self.border_set = Some(set);
+        self
+    }
+
+    /// Draw the border with rounded corners
+    #[must_use]
+    pub fn rounded(self) -> Self {
+        self.border_set(border::ROUNDED)
+    }
+
+    /// Draw the border with double lines
+    #[must_use]
+    pub fn double(self) -> Self {
+        self.border_set(border::DOUBLE)
+    }
+
+    /// Draw the border with thick lines
+    #[must_use]
+    pub fn thick(self) -> Self {
+        self.border_set(border::THICK)
+    }
+
+    /// Draw the border with `+`, `-`, and `|`
+    #[must_use]
+    pub fn ascii(self) -> Self {
+        self.border_set(ASCII_BORDER)
+    }
+
+    /// Set whether content lines wider than the card wrap onto the next row
+    ///
+    /// Wrapped rows have leading whitespace trimmed. Off by default, which clips
+    /// long lines at the border.
+    #[must_use]
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Set the horizontal alignment of the content
+    #[must_use]
+    pub fn alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Take the content and border styles from a theme
+    #[must_use]
+    pub fn themed(mut self, theme: &Theme) -> Self {
+        let theme = theme.for_depth(ColorDepth::current());
+        self.style = self.style.fg(theme.text).bg(theme.background);
+        self.border_style = self.border_style.fg(theme.border).bg(theme.background);
+        self
+    }
+
+    /// Measure the outer size needed to show the title and content without clipping
+    ///
+    /// Returns `(width, height)` including the border.
+    #[must_use]
+    pub fn measure(&self) -> (u16, u16) {
+        let content_width = self.content.iter().map(Line::width).max().unwrap_or(0);
+        let title_width = border_labels_width(self.title, self.top_right);
+        let footer_width = border_labels_width(self.bottom_left, self.bottom_right);
+        let width =
+            u16::try_from(content_width.max(title_width).max(footer_width)).unwrap_or(u16::MAX);
+        let height = u16::try_from(self.content.len()).unwrap_or(u16::MAX);
+
+        (width.saturating_add(2), height.saturating_add(2))
+    }
+
+    /// Render the card sized to its content and centered within `container`
+    ///
+    /// The measured size is capped by `max_width`/`max_height` and by the container itself.
+    /// Returns the area the card was rendered into.
+    pub fn render_centered(
+        self,
+        container: Rect,
+        buf: &mut Buffer,
+        max_width: u16,
+        max_height: u16,
+    ) -> Rect {
+        let (width, height) = self.measure();
+        let area = centered_rect_with_size(width.min(max_width), height.min(max_height), container);
+        self.render(area, buf);
+        area
+    }
 }
 
 impl Widget for Card<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let block = Block::default()
+        let border_set = self
+            .border_set
+            .unwrap_or_else(|| CharsetProfile::current().border_set());
+        let mut block = Block::default()
             .borders(Borders::ALL)
+            .border_set(border_set)
             .border_style(self.border_style);
 
-        let block = if let Some(title) = self.title {
-            block.title(title)
-        } else {
-            block
-        };
+        let label_width = area.width.saturating_sub(2);
+        let (title, top_right) = fit_border_labels(self.title, self.top_right, label_width);
+        let (bottom_left, bottom_right) =
+            fit_border_labels(self.bottom_left, self.bottom_right, label_width);
+        if let Some(title) = title {
+            block = block.title_top(title);
+        }
+        if let Some(label) = top_right {
+            block = block.title_top(Line::from(label).right_aligned());
+        }
+        if let Some(label) = bottom_left {
+            block = block.title_bottom(label);
+        }
+        if let Some(label) = bottom_right {
+            block = block.title_bottom(Line::from(label).right_aligned());
+        }
 
         let inner_area = block.inner(area);
         block.render(area, buf);
 
         if !self.content.is_empty() {
-            Paragraph::new(Text::from(self.content))
+            let mut paragraph = Paragraph::new(Text::from(self.content))
                 .style(self.style)
-                .render(inner_area, buf);
+                .alignment(self.alignment);
+            if self.wrap {
+                paragraph = paragraph.wrap(Wrap { trim: true });
+            }
+            paragraph.render(inner_area, buf);
+        }
+    }
+}
+
+/// Width needed to show a left and right border label with a gap between them
+fn border_labels_width(left: Option<&str>, right: Option<&str>) -> usize {
+    let left = left.map_or(0, |label| usize::from(text_width(label)));
+    let right = right.map_or(0, |label| usize::from(text_width(label)));
+    if left > 0 && right > 0 {
+        left + 1 + right
+    } else {
+        left + right
+    }
+}
+
+/// Truncate a pair of border labels so they fit in `width` columns without touching
+///
+/// The left label keeps as much as it can; the right label gets what remains after a
+/// one-column gap.
+fn fit_border_labels<'a>(
+    left: Option<&'a str>,
+    right: Option<&'a str>,
+    width: u16,
+) -> (Option<&'a str>, Option<&'a str>) {
+    let left = left.map(|label| truncate_to_width(label, width));
+    let used = left.map_or(0, |label| text_width(label).saturating_add(1));
+    let right = right.map(|label| truncate_to_width(label, width.saturating_sub(used)));
+    let non_empty = |label: Option<&'a str>| label.filter(|label| !label.is_empty());
+    (non_empty(left), non_empty(right))
+}
+
+impl MinSize for Card<'_> {
+    /// The border, plus a cell inside it when there is content
+    fn min_size(&self) -> (u16, u16) {
+        if self.content.is_empty() {
+            (2, 2)
+        } else {
+            (3, 3)
         }
     }
 }
@@ -167,36 +632,6822 @@ impl Default for Card<'_> {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// A titled box whose border fills up clockwise from the top-left as progress advances
+///
+/// Each edge stands for a quarter of the ratio whatever its length, so at 0.25 the
+/// whole top edge is lit and at 0.5 the right edge too.
+#[derive(Debug, Clone)]
+pub struct BorderedGauge<'a> {
+    ratio: f64,
+    title: Option<&'a str>,
+    content: Vec<Line<'a>>,
+    status: StatusColor,
+    style: Style,
+    border_style: Style,
+}
 
-    #[test]
-    fn test_status_indicator() {
-        let indicator = StatusIndicator::new(StatusColor::Success).label("Running");
+impl<'a> BorderedGauge<'a> {
+    /// Create a new bordered gauge for a ratio between 0.0 and 1.0
+    #[must_use]
+    pub fn new(ratio: f64) -> Self {
+        Self {
+            ratio: if ratio.is_nan() {
+                0.0
+            } else {
+                ratio.clamp(0.0, 1.0)
+            },
+            title: None,
+            content: Vec::new(),
+            status: StatusColor::Success,
+            style: Style::default(),
+            border_style: Style::default().fg(Color::DarkGray),
+        }
+    }
 
-        // Create a test buffer and render the indicator
-        let area = Rect::new(0, 0, 10, 1);
-        let mut buffer = Buffer::empty(area);
-        indicator.render(area, &mut buffer);
+    /// Set the title shown on the top border
+    #[must_use]
+    pub fn title(mut self, title: &'a str) -> Self {
+        self.title = Some(title);
+        self
+    }
 
-        // Check that the buffer contains the expected content
-        assert_eq!(buffer[(0, 0)].symbol(), "●");
+    /// Set the lines shown inside the box
+    #[must_use]
+    pub fn content(mut self, content: Vec<Line<'a>>) -> Self {
+        self.content = content;
+        self
     }
 
-    #[test]
-    fn test_card() {
-        let card = Card::new()
-            .title("Test Card")
-            .add_line(Line::from("Test content"));
+    /// Set the status color of the filled part of the border
+    #[must_use]
+    pub fn status(mut self, status: StatusColor) -> Self {
+        self.status = status;
+        self
+    }
 
-        // Create a test buffer and render the card
-        let area = Rect::new(0, 0, 20, 5);
-        let mut buffer = Buffer::empty(area);
-        card.render(area, &mut buffer);
+    /// Set the style of the content
+    #[must_use]
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
 
-        // Check that the buffer contains the title
-        let title_found = (0..area.width).any(|x| buffer[(x, 0)].symbol() == "T");
-        assert!(title_found, "Title not found in buffer");
+    /// Set the style of the unfilled part of the border
+    #[must_use]
+    pub fn border_style(mut self, style: Style) -> Self {
+        self.border_style = style;
+        self
+    }
+}
+
+impl MinSize for BorderedGauge<'_> {
+    /// The border the progress runs around, plus a cell inside it when there is content
+    fn min_size(&self) -> (u16, u16) {
+        if self.content.is_empty() {
+            (2, 2)
+        } else {
+            (3, 3)
+        }
+    }
+}
+
+/// List the cells on the edge of `area` clockwise, starting at the top-left corner
+fn perimeter_positions(area: Rect) -> Vec<Position> {
+    if area.width == 0 || area.height == 0 {
+        return Vec::new();
+    }
+
+    let (left, top) = (area.left(), area.top());
+    let (right, bottom) = (area.right() - 1, area.bottom() - 1);
+
+    let mut positions: Vec<Position> = (left..=right).map(|x| Position::new(x, top)).collect();
+    positions.extend((top + 1..=bottom).map(|y| Position::new(right, y)));
+    if bottom > top {
+        positions.extend((left..right).rev().map(|x| Position::new(x, bottom)));
+    }
+    if right > left {
+        positions.extend((top + 1..bottom).rev().map(|y| Position::new(left, y)));
+    }
+    positions
+}
+
+impl Widget for BorderedGauge<'_> {
+    /// We suppress these Clippy warnings because each lit cell count is a fraction
+    /// (clamped to `0.0..=1.0`) of an edge's length, so it always fits.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let card = Card {
+            title: self.title,
+            content: self.content,
+            style: self.style,
+            border_style: self.border_style,
+            ..Card::new()
+        };
+        card.render(area, buf);
+
+        let area = area.intersection(buf.area);
+        let perimeter = perimeter_positions(area);
+        if perimeter.is_empty() {
+            return;
+        }
+        // Cells on the top, right, bottom, and left edges, in the order they are listed
+        let (width, height) = (usize::from(area.width), usize::from(area.height));
+        let edges = [
+            width,
+            height - 1,
+            if height > 1 { width - 1 } else { 0 },
+            if height > 1 && width > 1 { height - 2 } else { 0 },
+        ];
+
+        let fill_style = Style::default().fg(self.status.into());
+        let mut start = 0;
+        for (quarter, len) in (0_u8..).zip(edges) {
+            let fraction = (self.ratio * 4.0 - f64::from(quarter)).clamp(0.0, 1.0);
+            let lit = (fraction * len as f64).round() as usize;
+            for &position in &perimeter[start..start + lit] {
+                buf[position].set_style(fill_style);
+            }
+            start += len;
+        }
+    }
+}
+
+/// Block glyphs used to draw fractional bar heights, from one eighth to a full cell
+const BAR_GLYPHS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// ASCII stand-ins for [`BAR_GLYPHS`]
+const ASCII_BAR_GLYPHS: [char; 8] = ['_', '_', '.', '-', '-', '=', '=', '#'];
+
+/// A compact sparkline-style chart for a series of values
+#[derive(Debug, Clone)]
+pub struct MiniChart<'a> {
+    data: &'a [f64],
+    status: StatusColor,
+    baseline: Option<f64>,
+    style: Style,
+}
+
+impl<'a> MiniChart<'a> {
+    /// Create a new chart for the given data
+    #[must_use]
+    pub fn new(data: &'a [f64]) -> Self {
+        Self {
+            data,
+            status: StatusColor::Info,
+            baseline: None,
+            style: Style::default(),
+        }
+    }
+
+    /// Set the status color used for the bars
+    #[must_use]
+    pub fn status(mut self, status: StatusColor) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Set a baseline value that the chart scales from instead of the data minimum
+    ///
+    /// The baseline only extends the scale; data below it still lowers the minimum.
+    #[must_use]
+    pub fn baseline(mut self, baseline: f64) -> Self {
+        self.baseline = Some(baseline);
+        self
+    }
+
+    /// Set the style of the chart
+    #[must_use]
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Reduce the data to at most `columns` values, averaging neighbouring points
+    fn sample(&self, columns: usize) -> Vec<f64> {
+        if self.data.len() <= columns {
+            return self.data.to_vec();
+        }
+
+        (0..columns)
+            .map(|column| {
+                let start = column * self.data.len() / columns;
+                let end = ((column + 1) * self.data.len() / columns).max(start + 1);
+                let bucket = &self.data[start..end];
+                let count = f64::from(u32::try_from(bucket.len()).unwrap_or(u32::MAX));
+                bucket.iter().sum::<f64>() / count
+            })
+            .collect()
+    }
+}
+
+impl Widget for MiniChart<'_> {
+    /// We suppress these Clippy warnings because the scaled height is clamped to
+    /// `0..=total_eighths` before the conversion, so it always fits in a `u32`.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width == 0 || area.height == 0 || self.data.is_empty() {
+            return;
+        }
+
+        let values = self.sample(usize::from(area.width));
+        let finite = values.iter().copied().filter(|value| value.is_finite());
+        let min = finite.clone().fold(f64::INFINITY, f64::min);
+        let max = finite.fold(f64::NEG_INFINITY, f64::max);
+        let min = self.baseline.map_or(min, |baseline| baseline.min(min));
+        let range = max - min;
+
+        let total_eighths = u32::from(area.height) * 8;
+        let style = self.style.fg(self.status.into());
+        let glyphs = CharsetProfile::current().pick(&BAR_GLYPHS, &ASCII_BAR_GLYPHS);
+
+        for (x, value) in (area.left()..area.right()).zip(values) {
+            if !value.is_finite() {
+                continue;
+            }
+
+            let eighths = if range.is_finite() && range > f64::EPSILON {
+                let normalized = (value - min) / range;
+                let scaled = (normalized * f64::from(total_eighths - 1)).round();
+                1 + scaled.clamp(0.0, f64::from(total_eighths - 1)) as u32
+            } else {
+                total_eighths / 2
+            };
+
+            for (row, y) in (area.top()..area.bottom()).rev().enumerate() {
+                let filled = eighths.saturating_sub(u32::try_from(row).unwrap_or(u32::MAX) * 8);
+                if filled == 0 {
+                    break;
+                }
+                let glyph = glyphs[(filled.min(8) - 1) as usize];
+                buf[(x, y)].set_char(glyph).set_style(style);
+            }
+        }
+    }
+}
+
+/// A sparkline of the most recent values, marking the high and low points and
+/// labelled with the latest value
+///
+/// Only the newest points that fit are drawn, one per column, so a rolling window
+/// kept in a `VecDeque` can be passed whole with [`from_deque`](Self::from_deque).
+/// The highest and lowest visible points are drawn in their own colors; when several
+/// points tie, the most recent is marked.
+#[derive(Debug, Clone)]
+pub struct TrendLine<'a> {
+    data: (&'a [f64], &'a [f64]),
+    status: StatusColor,
+    high_color: Color,
+    low_color: Color,
+    precision: usize,
+    label: bool,
+    style: Style,
+}
+
+impl<'a> TrendLine<'a> {
+    /// Create a trend line for the given data, oldest first
+    #[must_use]
+    pub fn new(data: &'a [f64]) -> Self {
+        Self {
+            data: (data, &[]),
+            status: StatusColor::Info,
+            high_color: Color::Yellow,
+            low_color: Color::Cyan,
+            precision: 1,
+            label: true,
+            style: Style::default(),
+        }
+    }
+
+    /// Create a trend line for the values in a deque, oldest first
+    #[must_use]
+    pub fn from_deque(data: &'a VecDeque<f64>) -> Self {
+        let mut trend = Self::new(&[]);
+        trend.data = data.as_slices();
+        trend
+    }
+
+    /// Set the status color used for the line and label
+    #[must_use]
+    pub fn status(mut self, status: StatusColor) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Set the color of the highest visible point
+    #[must_use]
+    pub fn high_color(mut self, color: Color) -> Self {
+        self.high_color = color;
+        self
+    }
+
+    /// Set the color of the lowest visible point
+    #[must_use]
+    pub fn low_color(mut self, color: Color) -> Self {
+        self.low_color = color;
+        self
+    }
+
+    /// Set the number of decimal places in the label
+    #[must_use]
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Set whether the latest value is shown after the line
+    #[must_use]
+    pub fn label(mut self, label: bool) -> Self {
+        self.label = label;
+        self
+    }
+
+    /// Set the style of the trend line
+    #[must_use]
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// The newest `columns` values, oldest first
+    fn visible(&self, columns: usize) -> Vec<f64> {
+        let (front, back) = self.data;
+        let skip = (front.len() + back.len()).saturating_sub(columns);
+        front.iter().chain(back).skip(skip).copied().collect()
+    }
+
+    /// Text of the label, from the newest finite value
+    fn label_text(&self) -> Option<String> {
+        let (front, back) = self.data;
+        let latest = front
+            .iter()
+            .chain(back)
+            .rev()
+            .find(|value| value.is_finite())?;
+        Some(format!("{latest:.0$}", self.precision))
+    }
+
+    /// Columns of the highest and lowest finite values, or `None` if they are equal
+    fn extremes(values: &[f64]) -> Option<(usize, usize)> {
+        let finite = || {
+            values
+                .iter()
+                .enumerate()
+                .filter(|(_, value)| value.is_finite())
+        };
+        // Equal values replace the best so far, so later points win ties
+        let (high, max) =
+            finite().fold(None, |best: Option<(usize, f64)>, (x, &value)| match best {
+                Some((_, max)) if value < max => best,
+                _ => Some((x, value)),
+            })?;
+        let (low, min) =
+            finite().fold(None, |best: Option<(usize, f64)>, (x, &value)| match best {
+                Some((_, min)) if value > min => best,
+                _ => Some((x, value)),
+            })?;
+        (max > min).then_some((high, low))
+    }
+}
+
+impl Widget for TrendLine<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = area.intersection(buf.area);
+        if area.is_empty() {
+            return;
+        }
+
+        // The label takes a leading space and is dropped if it leaves no room for the line
+        let label = self.label.then(|| self.label_text()).flatten();
+        let label_width = label
+            .as_deref()
+            .map_or(0, |text| text_width(text).saturating_add(1));
+        let (chart, label) = if label_width < area.width {
+            let chart = Rect {
+                width: area.width - label_width,
+                ..area
+            };
+            (chart, label)
+        } else {
+            (area, None)
+        };
+
+        let values = self.visible(usize::from(chart.width));
+        // Right-aligned so the newest point sits next to the label
+        let offset = chart.width - u16::try_from(values.len()).unwrap_or(chart.width);
+        let line = Rect {
+            x: chart.x + offset,
+            width: chart.width - offset,
+            ..chart
+        };
+        MiniChart::new(&values)
+            .status(self.status)
+            .style(self.style)
+            .render(line, buf);
+
+        if let Some((high, low)) = Self::extremes(&values) {
+            for (index, color) in [(high, self.high_color), (low, self.low_color)] {
+                let x = line.x + u16::try_from(index).unwrap_or(0);
+                for y in line.top()..line.bottom() {
+                    if buf[(x, y)].symbol() != " " {
+                        buf[(x, y)].set_fg(color);
+                    }
+                }
+            }
+        }
+
+        if let Some(text) = label {
+            let y = area.bottom() - 1;
+            let style = self.style.fg(self.status.into());
+            buf.set_string(chart.right() + 1, y, text, style);
+        }
+    }
+}
+
+/// A debug overlay of recent frame times with FPS figures
+///
+/// Feed it from [`TerminalApp::on_frame_rendered`](crate::TerminalApp::on_frame_rendered)
+/// with [`record`](Self::record), or any other source of frame durations with
+/// [`push`](Self::push). Only the newest frames up to the capacity are kept. Rendered
+/// into the whole frame, it draws a small box in one corner holding the current and
+/// average FPS, the 99th percentile frame time, and a sparkline with a column for each
+/// of the newest frames. Frames over the budget are drawn in red, as is the border
+/// while the latest frame is over it.
+#[derive(Debug, Clone)]
+pub struct FrameGraph {
+    frames: VecDeque<Duration>,
+    capacity: usize,
+    budget: Duration,
+    corner: Corner,
+    width: u16,
+}
+
+impl FrameGraph {
+    /// Create a graph keeping the last `capacity` frames, with a 60 FPS budget
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            frames: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(1),
+            budget: Duration::from_secs(1) / 60,
+            corner: Corner::TopRight,
+            width: 30,
+        }
+    }
+
+    /// Set the frame time above which frames are drawn in red
+    #[must_use]
+    pub fn budget(mut self, budget: Duration) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Set the corner the overlay sits in
+    #[must_use]
+    pub fn corner(mut self, corner: Corner) -> Self {
+        self.corner = corner;
+        self
+    }
+
+    /// Set the width of the overlay, borders included
+    #[must_use]
+    pub fn width(mut self, width: u16) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Add a frame duration, dropping the oldest once the graph is full
+    pub fn push(&mut self, frame_time: Duration) {
+        if self.frames.len() == self.capacity {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(frame_time);
+    }
+
+    /// Add the render time of a drawn frame
+    pub fn record(&mut self, stats: RenderStats) {
+        self.push(stats.render_time);
+    }
+
+    /// Get the most recent frame time
+    #[must_use]
+    pub fn latest(&self) -> Option<Duration> {
+        self.frames.back().copied()
+    }
+
+    /// Get the mean frame time over the kept frames
+    #[must_use]
+    pub fn average(&self) -> Option<Duration> {
+        let count = u32::try_from(self.frames.len()).ok().filter(|&n| n > 0)?;
+        Some(self.frames.iter().sum::<Duration>() / count)
+    }
+
+    /// Get the frame time that 99% of the kept frames are at or under
+    #[must_use]
+    pub fn p99(&self) -> Option<Duration> {
+        let mut sorted: Vec<_> = self.frames.iter().copied().collect();
+        sorted.sort_unstable();
+        // Nearest rank: the smallest value with at least 99% of frames at or below it
+        let rank = (sorted.len() * 99).div_ceil(100);
+        sorted.get(rank.checked_sub(1)?).copied()
+    }
+
+    /// Get the frame rate the average frame time would sustain
+    #[must_use]
+    pub fn average_fps(&self) -> Option<f64> {
+        self.average().map(fps)
+    }
+
+    /// Check whether the latest frame took longer than the budget
+    #[must_use]
+    pub fn over_budget(&self) -> bool {
+        self.latest().is_some_and(|latest| latest > self.budget)
+    }
+
+    /// Remove every recorded frame
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    fn summary(&self) -> String {
+        match (self.latest(), self.average(), self.p99()) {
+            (Some(latest), Some(average), Some(p99)) => format!(
+                "{:.0} fps  avg {:.0}  p99 {:.1}ms",
+                fps(latest),
+                fps(average),
+                p99.as_secs_f64() * 1000.0
+            ),
+            _ => "no frames".to_string(),
+        }
+    }
+}
+
+/// Frames per second sustained at one frame per `frame_time`
+fn fps(frame_time: Duration) -> f64 {
+    if frame_time.is_zero() {
+        f64::INFINITY
+    } else {
+        1.0 / frame_time.as_secs_f64()
+    }
+}
+
+impl Widget for &FrameGraph {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let rect = corner_rect_with_size(self.width, 4, self.corner, area);
+        Clear.render(rect, buf);
+        let status = if self.over_budget() {
+            StatusColor::Error
+        } else {
+            StatusColor::Success
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_set(CharsetProfile::current().border_set())
+            .border_style(Style::default().fg(status.into()));
+        let inner = block.inner(rect);
+        block.render(rect, buf);
+        if inner.is_empty() {
+            return;
+        }
+
+        let summary = self.summary();
+        buf.set_stringn(
+            inner.x,
+            inner.y,
+            &summary,
+            usize::from(inner.width),
+            Style::default(),
+        );
+        let chart = Rect::new(inner.x, inner.y + 1, inner.width, inner.height - 1);
+        if chart.is_empty() {
+            return;
+        }
+
+        // One column per frame, newest at the right edge
+        let shown = self.frames.len().min(usize::from(chart.width));
+        let newest = self.frames.iter().skip(self.frames.len() - shown);
+        let millis: Vec<f64> = newest
+            .clone()
+            .map(|frame| frame.as_secs_f64() * 1000.0)
+            .collect();
+        let offset = chart.width - u16::try_from(shown).unwrap_or(chart.width);
+        let line = Rect::new(
+            chart.x + offset,
+            chart.y,
+            chart.width - offset,
+            chart.height,
+        );
+        MiniChart::new(&millis)
+            .status(StatusColor::Success)
+            .baseline(0.0)
+            .render(line, buf);
+        let over: Color = StatusColor::Error.into();
+        for (x, frame) in (line.left()..line.right()).zip(newest) {
+            if *frame > self.budget {
+                for y in line.top()..line.bottom() {
+                    buf[(x, y)].set_fg(over);
+                }
+            }
+        }
+    }
+}
+
+impl Widget for FrameGraph {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        (&self).render(area, buf);
+    }
+}
+
+/// A vertical bar chart with a label under each bar and an optional value axis
+///
+/// Bars scale against the largest value, or a fixed maximum, and share the width
+/// evenly with a gap between each. When the area is too narrow for the gaps they are
+/// dropped, and labels wider than their bar are abbreviated.
+#[derive(Debug, Clone)]
+pub struct BarChart<'a> {
+    bars: Vec<(&'a str, f64)>,
+    max: Option<f64>,
+    gap: u16,
+    axis: bool,
+    status: StatusColor,
+    style: Style,
+}
+
+impl<'a> BarChart<'a> {
+    /// Create a chart with a bar for each `(label, value)` pair
+    #[must_use]
+    pub fn new(bars: Vec<(&'a str, f64)>) -> Self {
+        Self {
+            bars,
+            max: None,
+            gap: 1,
+            axis: false,
+            status: StatusColor::Info,
+            style: Style::default(),
+        }
+    }
+
+    /// Set the value of a full-height bar instead of using the largest value
+    #[must_use]
+    pub fn max(mut self, max: f64) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Set the number of columns between bars
+    #[must_use]
+    pub fn gap(mut self, gap: u16) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Set whether to show a value axis with ticks at zero, half, and the maximum
+    #[must_use]
+    pub fn axis(mut self, axis: bool) -> Self {
+        self.axis = axis;
+        self
+    }
+
+    /// Set the status color used for the bars
+    #[must_use]
+    pub fn status(mut self, status: StatusColor) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Set the style of the chart
+    #[must_use]
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// The value a full-height bar stands for
+    fn scale_max(&self) -> f64 {
+        self.max.unwrap_or_else(|| {
+            self.bars
+                .iter()
+                .map(|&(_, value)| value)
+                .filter(|value| value.is_finite())
+                .fold(0.0, f64::max)
+        })
+    }
+
+    /// Split `width` columns into the start and width of each bar
+    ///
+    /// Columns left over after dividing evenly go one each to the first bars. Bars
+    /// that don't fit even at one column wide are left out.
+    #[must_use]
+    pub fn bar_columns(&self, width: u16) -> Vec<(u16, u16)> {
+        let count = u16::try_from(self.bars.len())
+            .unwrap_or(u16::MAX)
+            .min(width);
+        if count == 0 {
+            return Vec::new();
+        }
+        let gap = if count + (count - 1) * self.gap <= width {
+            self.gap
+        } else {
+            0
+        };
+        let space = width - (count - 1) * gap;
+        let (base, extra) = (space / count, space % count);
+
+        let mut x = 0;
+        (0..count)
+            .map(|index| {
+                let bar_width = base + u16::from(index < extra);
+                let column = (x, bar_width);
+                x += bar_width + gap;
+                column
+            })
+            .collect()
+    }
+
+    /// Text of the axis tick for a value
+    fn tick_label(value: f64) -> String {
+        let (scaled, suffix) = match value.abs() {
+            v if v >= 1e6 => (value / 1e6, "M"),
+            v if v >= 1e3 => (value / 1e3, "k"),
+            _ => (value, ""),
+        };
+        if scaled.fract().abs() < 0.05 {
+            format!("{scaled:.0}{suffix}")
+        } else {
+            format!("{scaled:.1}{suffix}")
+        }
+    }
+
+    /// Draw the tick labels and axis line, returning the columns they take up
+    fn render_axis(&self, chart: Rect, max: f64, buf: &mut Buffer) -> u16 {
+        let ticks = [
+            (chart.top(), max),
+            (chart.top() + chart.height / 2, max / 2.0),
+            (chart.bottom() - 1, 0.0),
+        ];
+        let labels = ticks.map(|(y, value)| (y, Self::tick_label(value)));
+        let label_width = labels
+            .iter()
+            .map(|(_, label)| label.width())
+            .max()
+            .unwrap_or(0);
+        let axis_width = u16::try_from(label_width + 1).unwrap_or(u16::MAX);
+        if axis_width >= chart.width {
+            return 0;
+        }
+
+        let line = CharsetProfile::current().pick("│", "|");
+        for y in chart.top()..chart.bottom() {
+            buf[(chart.x + axis_width - 1, y)]
+                .set_symbol(line)
+                .set_style(self.style);
+        }
+        for (y, label) in labels {
+            buf.set_string(chart.x, y, format!("{label:>label_width$}"), self.style);
+        }
+        axis_width
+    }
+}
+
+impl Widget for BarChart<'_> {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = area.intersection(buf.area);
+        buf.set_style(area, self.style);
+        if area.height < 2 || area.width == 0 || self.bars.is_empty() {
+            return;
+        }
+        let chart = Rect::new(area.x, area.y, area.width, area.height - 1);
+        let max = self.scale_max();
+        let axis_width = if self.axis {
+            self.render_axis(chart, max, buf)
+        } else {
+            0
+        };
+        let left = chart.x + axis_width;
+
+        let total_eighths = u32::from(chart.height) * 8;
+        let style = self.style.fg(self.status.into());
+        let glyphs = CharsetProfile::current().pick(&BAR_GLYPHS, &ASCII_BAR_GLYPHS);
+        let columns = self.bar_columns(chart.width - axis_width);
+        for (&(label, value), (offset, width)) in self.bars.iter().zip(columns) {
+            let x = left + offset;
+            let eighths = if max > 0.0 && value.is_finite() {
+                (value / max * f64::from(total_eighths))
+                    .round()
+                    .clamp(0.0, f64::from(total_eighths)) as u32
+            } else {
+                0
+            };
+            for (row, y) in (chart.top()..chart.bottom()).rev().enumerate() {
+                let filled = eighths.saturating_sub(u32::try_from(row).unwrap_or(u32::MAX) * 8);
+                if filled == 0 {
+                    break;
+                }
+                let glyph = glyphs[(filled.min(8) - 1) as usize];
+                for column in x..x + width {
+                    buf[(column, y)].set_char(glyph).set_style(style);
+                }
+            }
+
+            let label = if width == 1 {
+                Span::raw(truncate_to_width(label, width))
+            } else {
+                fit_span(&Span::raw(label), width)
+            };
+            let padding = width.saturating_sub(u16::try_from(label.width()).unwrap_or(width)) / 2;
+            buf.set_span(x + padding, area.bottom() - 1, &label, width);
+        }
+    }
+}
+
+/// A pie or donut chart of proportions, with an optional legend
+///
+/// Slices are laid out clockwise from twelve o'clock, each spanning an angle
+/// proportional to its value. The circle is scaled to fit the area, allowing for
+/// cells being about twice as tall as they are wide, and each cell takes the color of
+/// the slice its center falls in.
+#[derive(Debug, Clone)]
+pub struct PieChart<'a> {
+    slices: Vec<(&'a str, f64, Color)>,
+    hole: f64,
+    legend: bool,
+    style: Style,
+}
+
+impl<'a> PieChart<'a> {
+    /// Create a chart with a slice for each `(label, value, color)`
+    ///
+    /// Negative and non-finite values count as zero.
+    #[must_use]
+    pub fn new(slices: Vec<(&'a str, f64, Color)>) -> Self {
+        Self {
+            slices,
+            hole: 0.0,
+            legend: false,
+            style: Style::default(),
+        }
+    }
+
+    /// Cut a hole in the middle, as a fraction of the radius, to make a donut
+    #[must_use]
+    pub fn hole(mut self, hole: f64) -> Self {
+        self.hole = if hole.is_nan() {
+            0.0
+        } else {
+            hole.clamp(0.0, 1.0)
+        };
+        self
+    }
+
+    /// Set whether to list the slices with their percentages beside the chart
+    #[must_use]
+    pub fn legend(mut self, legend: bool) -> Self {
+        self.legend = legend;
+        self
+    }
+
+    /// Set the style of the chart and legend
+    #[must_use]
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Value of a slice as counted by the chart
+    fn value(value: f64) -> f64 {
+        if value.is_finite() {
+            value.max(0.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// Sum of every slice's value
+    fn total(&self) -> f64 {
+        self.slices
+            .iter()
+            .map(|&(_, value, _)| Self::value(value))
+            .sum()
+    }
+
+    /// Index of the slice covering a fraction of the way around the circle
+    fn slice_at(&self, fraction: f64, total: f64) -> Option<usize> {
+        let mut end = 0.0;
+        let last_lit = self
+            .slices
+            .iter()
+            .rposition(|&(_, value, _)| Self::value(value) > 0.0)?;
+        for (index, &(_, value, _)) in self.slices.iter().enumerate() {
+            end += Self::value(value) / total;
+            if fraction < end && Self::value(value) > 0.0 {
+                return Some(index);
+            }
+        }
+        // Rounding can leave the very end of the circle past the last boundary
+        Some(last_lit)
+    }
+
+    /// Legend rows and the width they need
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn legend_lines(&self, total: f64) -> (Vec<Line<'a>>, u16) {
+        let marker = CharsetProfile::current().pick("■", "#");
+        let lines: Vec<Line> = self
+            .slices
+            .iter()
+            .map(|&(label, value, color)| {
+                let percent = if total > 0.0 {
+                    (Self::value(value) / total * 100.0).round() as u32
+                } else {
+                    0
+                };
+                Line::from(vec![
+                    Span::styled(marker, Style::new().fg(color)),
+                    Span::raw(format!(" {label} {percent}%")),
+                ])
+            })
+            .collect();
+        let width = lines.iter().map(Line::width).max().unwrap_or(0);
+        (lines, u16::try_from(width).unwrap_or(u16::MAX))
+    }
+}
+
+impl Widget for PieChart<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = area.intersection(buf.area);
+        buf.set_style(area, self.style);
+        if area.is_empty() {
+            return;
+        }
+        let total = self.total();
+
+        let mut chart = area;
+        if self.legend {
+            let (lines, width) = self.legend_lines(total);
+            let width = width.min(area.width);
+            let legend = Rect::new(area.right() - width, area.y, width, area.height);
+            Paragraph::new(lines).style(self.style).render(legend, buf);
+            chart.width = area.width.saturating_sub(width + 1);
+        }
+        if chart.is_empty() || total <= 0.0 {
+            return;
+        }
+
+        // Cells are about twice as tall as they are wide
+        let radius_y = (f64::from(chart.height) / 2.0).min(f64::from(chart.width) / 4.0);
+        let radius_x = radius_y * 2.0;
+        let center_x = f64::from(chart.x) + f64::from(chart.width) / 2.0;
+        let center_y = f64::from(chart.y) + f64::from(chart.height) / 2.0;
+        let glyph = CharsetProfile::current().pick("█", "#");
+
+        for position in chart.positions() {
+            let dx = (f64::from(position.x) + 0.5 - center_x) / radius_x;
+            let dy = (f64::from(position.y) + 0.5 - center_y) / radius_y;
+            let distance = dx.hypot(dy);
+            if distance > 1.0 || distance < self.hole {
+                continue;
+            }
+            // Clockwise from twelve o'clock
+            let angle = dx.atan2(-dy).rem_euclid(std::f64::consts::TAU);
+            if let Some(index) = self.slice_at(angle / std::f64::consts::TAU, total) {
+                buf[position].set_symbol(glyph).set_fg(self.slices[index].2);
+            }
+        }
+    }
+}
+
+/// A semicircular gauge showing a single ratio with its percentage in the center
+///
+/// The arc is drawn with braille dots, at two by four dots per cell, falling back to
+/// one `#` or `.` per cell with the ASCII charset.
+#[derive(Debug, Clone)]
+pub struct Gauge {
+    ratio: f64,
+    status: Option<StatusColor>,
+    style: Style,
+}
+
+impl Gauge {
+    /// Create a new gauge for a ratio between 0.0 and 1.0
+    #[must_use]
+    pub fn new(ratio: f64) -> Self {
+        Self {
+            ratio: if ratio.is_nan() {
+                0.0
+            } else {
+                ratio.clamp(0.0, 1.0)
+            },
+            status: None,
+            style: Style::default(),
+        }
+    }
+
+    /// Use a fixed status color for the filled arc
+    ///
+    /// Without a status the fill goes from green to yellow to red as the ratio rises.
+    #[must_use]
+    pub fn status(mut self, status: StatusColor) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Pick the status color of the filled arc from thresholds on the ratio
+    #[must_use]
+    pub fn thresholds(mut self, thresholds: &Thresholds) -> Self {
+        self.status = Some(thresholds.color_for(self.ratio));
+        self
+    }
+
+    /// Set the style of the gauge
+    #[must_use]
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// The color used for the filled part of the arc
+    fn fill_color(&self) -> Color {
+        self.status.map_or_else(
+            || {
+                if self.ratio < 0.5 {
+                    Color::Green
+                } else if self.ratio < 0.8 {
+                    Color::Yellow
+                } else {
+                    Color::Red
+                }
+            },
+            Color::from,
+        )
+    }
+
+    /// Check a point `dx` columns right of and `dy` rows above the arc's center
+    ///
+    /// Returns `None` off the arc, otherwise whether the point is in the filled part.
+    fn arc_point(&self, dx: f64, dy: f64, (inner, outer): (f64, f64)) -> Option<bool> {
+        // Terminal cells are roughly twice as tall as they are wide
+        let dx = dx / 2.0;
+        let distance = dx.hypot(dy);
+        if dy < 0.0 || distance > outer || distance < inner {
+            return None;
+        }
+        // Sweep clockwise from the left end of the arc to the right end
+        let progress = 1.0 - dy.atan2(dx) / std::f64::consts::PI;
+        Some(progress <= self.ratio && self.ratio > 0.0)
+    }
+}
+
+/// Braille dot bits by column and row within a cell
+const BRAILLE_DOTS: [[u8; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+impl Widget for Gauge {
+    /// We suppress these Clippy warnings because the percentage is clamped to `0..=100`
+    /// before the conversion.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        // Terminal cells are roughly twice as tall as they are wide, so the arc spans
+        // two columns for every row of radius
+        let radius = area
+            .height
+            .saturating_sub(1)
+            .min(area.width.saturating_sub(1) / 4);
+        let center_x = area.x + area.width / 2;
+        let center_y = area.y + (area.height - (radius + 1)) / 2 + radius;
+
+        if radius > 0 {
+            let band = (
+                (f64::from(radius) - f64::from(radius / 3).max(1.0)).max(0.0),
+                f64::from(radius) + 0.5,
+            );
+            let fill_style = self.style.fg(self.fill_color());
+            let empty_style = self.style.fg(Color::DarkGray);
+            let ascii = CharsetProfile::current().is_ascii();
+
+            for y in center_y - radius..=center_y {
+                for x in center_x - radius * 2..=center_x + radius * 2 {
+                    let dx = f64::from(x) - f64::from(center_x);
+                    let dy = f64::from(center_y) - f64::from(y);
+                    if ascii {
+                        match self.arc_point(dx, dy, band) {
+                            Some(true) => buf[(x, y)].set_char('#').set_style(fill_style),
+                            Some(false) => buf[(x, y)].set_char('.').set_style(empty_style),
+                            None => continue,
+                        };
+                        continue;
+                    }
+
+                    // Sample each dot at its offset from the middle of the cell
+                    let (mut filled, mut empty) = (0_u8, 0_u8);
+                    for (column, bits) in (0_u8..).zip(BRAILLE_DOTS) {
+                        for (row, bit) in (0_u8..).zip(bits) {
+                            let dot_x = dx + (f64::from(column) - 0.5) * 0.5;
+                            let dot_y = dy - (f64::from(row) - 1.5) * 0.25;
+                            match self.arc_point(dot_x, dot_y, band) {
+                                Some(true) => filled |= bit,
+                                Some(false) => empty |= bit,
+                                None => {}
+                            }
+                        }
+                    }
+                    // A cell holds one color, so the filled dots win where both meet
+                    let (dots, style) = if filled != 0 {
+                        (filled, fill_style)
+                    } else {
+                        (empty, empty_style)
+                    };
+                    if dots != 0 {
+                        let glyph = char::from_u32(0x2800 + u32::from(dots)).unwrap_or(' ');
+                        buf[(x, y)].set_char(glyph).set_style(style);
+                    }
+                }
+            }
+        }
+
+        let label = format!("{}%", (self.ratio * 100.0).round().clamp(0.0, 100.0) as u8);
+        let label_width = u16::try_from(label.len()).unwrap_or(u16::MAX);
+        let label_x = center_x.saturating_sub(label_width / 2).max(area.x);
+        buf.set_stringn(
+            label_x,
+            center_y,
+            &label,
+            usize::from(area.right() - label_x),
+            self.style,
+        );
+    }
+}
+
+/// A grid of values drawn as colored cells
+///
+/// Values are scaled from the smallest to the largest finite value onto a color
+/// gradient. When the grid is larger than the area, each display cell shows the
+/// average of the block of source cells it covers; smaller grids are stretched.
+/// Rows may be ragged, and missing or non-finite cells are left empty.
+#[derive(Debug, Clone)]
+pub struct Heatmap<'a> {
+    rows: Vec<&'a [f64]>,
+    gradient: Gradient,
+    range: Option<(f64, f64)>,
+}
+
+impl<'a> Heatmap<'a> {
+    /// Create a heatmap from rows of values
+    #[must_use]
+    pub fn new(rows: &'a [Vec<f64>]) -> Self {
+        Self {
+            rows: rows.iter().map(Vec::as_slice).collect(),
+            gradient: Gradient::evenly(&[Color::Rgb(0, 0, 255), Color::Rgb(255, 0, 0)]),
+            range: None,
+        }
+    }
+
+    /// Create a heatmap from a flat slice of values laid out `width` to a row
+    #[must_use]
+    pub fn from_flat(values: &'a [f64], width: usize) -> Self {
+        Self {
+            rows: values.chunks(width.max(1)).collect(),
+            ..Self::new(&[])
+        }
+    }
+
+    /// Set the colors running from the lowest value to the highest
+    ///
+    /// The colors are spread evenly and mixed as by [`Gradient`]; an empty list keeps
+    /// the current gradient.
+    #[must_use]
+    pub fn gradient(mut self, colors: &[Color]) -> Self {
+        if !colors.is_empty() {
+            self.gradient = Gradient::evenly(colors);
+        }
+        self
+    }
+
+    /// Scale values between fixed bounds instead of the data's own extremes
+    #[must_use]
+    pub fn range(mut self, min: f64, max: f64) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    /// Average the finite values in a block of rows and columns, if there are any
+    #[allow(clippy::cast_precision_loss)]
+    fn block_average(
+        &self,
+        rows: std::ops::Range<usize>,
+        columns: std::ops::Range<usize>,
+    ) -> Option<f64> {
+        let (sum, count) = self.rows[rows]
+            .iter()
+            .flat_map(|row| &row[columns.start.min(row.len())..columns.end.min(row.len())])
+            .filter(|value| value.is_finite())
+            .fold((0.0, 0_usize), |(sum, count), value| {
+                (sum + value, count + 1)
+            });
+        (count > 0).then(|| sum / count as f64)
+    }
+}
+
+/// Get the source indices covered by display cell `index` of `cells` when showing `len` items
+fn source_block(index: usize, cells: usize, len: usize) -> std::ops::Range<usize> {
+    let start = index * len / cells;
+    let end = ((index + 1) * len / cells).max(start + 1);
+    start..end.min(len)
+}
+
+impl Widget for Heatmap<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let columns = self.rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        if area.is_empty() || columns == 0 {
+            return;
+        }
+
+        let width = usize::from(area.width);
+        let height = usize::from(area.height);
+        let averages: Vec<Vec<Option<f64>>> = (0..height)
+            .map(|y| {
+                let rows = source_block(y, height, self.rows.len());
+                (0..width)
+                    .map(|x| self.block_average(rows.clone(), source_block(x, width, columns)))
+                    .collect()
+            })
+            .collect();
+
+        let (min, max) = self.range.unwrap_or_else(|| {
+            averages
+                .iter()
+                .flatten()
+                .flatten()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &value| {
+                    (min.min(value), max.max(value))
+                })
+        });
+        let span = max - min;
+
+        for (y, row) in (area.top()..area.bottom()).zip(&averages) {
+            for (x, value) in (area.left()..area.right()).zip(row) {
+                let Some(value) = value else {
+                    continue;
+                };
+                let t = if span > f64::EPSILON {
+                    (value - min) / span
+                } else {
+                    0.5
+                };
+                buf[(x, y)].set_char(' ').set_bg(self.gradient.sample(t));
+            }
+        }
+    }
+}
+
+/// Selection state for lists that allow selecting several rows at once
+///
+/// Tracks the set of selected indices plus an anchor, so that shift-arrow style
+/// range selection can extend from the last toggled row.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MultiSelectState {
+    selected: BTreeSet<usize>,
+    anchor: Option<usize>,
+}
+
+impl MultiSelectState {
+    /// Create an empty selection
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Toggle whether `index` is selected and make it the range anchor
+    pub fn toggle(&mut self, index: usize) {
+        if !self.selected.remove(&index) {
+            self.selected.insert(index);
+        }
+        self.anchor = Some(index);
+    }
+
+    /// Select every index between `from` and `to` inclusive, in either order
+    ///
+    /// `from` becomes the range anchor.
+    pub fn select_range(&mut self, from: usize, to: usize) {
+        self.selected.extend(from.min(to)..=from.max(to));
+        self.anchor = Some(from);
+    }
+
+    /// Select from the anchor to `index`, as when shift-moving the cursor
+    ///
+    /// Without an anchor only `index` is selected and it becomes the anchor.
+    pub fn extend_to(&mut self, index: usize) {
+        let anchor = self.anchor.unwrap_or(index);
+        self.select_range(anchor, index);
+    }
+
+    /// Check whether `index` is selected
+    #[must_use]
+    pub fn is_selected(&self, index: usize) -> bool {
+        self.selected.contains(&index)
+    }
+
+    /// Get the selected indices in ascending order
+    #[must_use]
+    pub fn selected_indices(&self) -> Vec<usize> {
+        self.selected.iter().copied().collect()
+    }
+
+    /// Get the current range anchor
+    #[must_use]
+    pub fn anchor(&self) -> Option<usize> {
+        self.anchor
+    }
+
+    /// Deselect everything and forget the anchor
+    pub fn clear(&mut self) {
+        self.selected.clear();
+        self.anchor = None;
+    }
+}
+
+/// Splits a large dataset into fixed-size pages, so only one page is held and drawn
+///
+/// Pages are numbered from zero, and [`range`](Self::range) gives the indices of the
+/// current page for slicing the items or fetching them. The last page may be short.
+/// An empty dataset still has one, empty, page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Paginator {
+    total: usize,
+    page_size: usize,
+    page: usize,
+}
+
+impl Paginator {
+    /// Create a paginator on the first page, with pages of at least one item
+    #[must_use]
+    pub fn new(total: usize, page_size: usize) -> Self {
+        Self {
+            total,
+            page_size: page_size.max(1),
+            page: 0,
+        }
+    }
+
+    /// Get the current page
+    #[must_use]
+    pub fn page(&self) -> usize {
+        self.page
+    }
+
+    /// Get the number of pages
+    #[must_use]
+    pub fn page_count(&self) -> usize {
+        self.total.div_ceil(self.page_size).max(1)
+    }
+
+    /// Get the number of items per page
+    #[must_use]
+    pub fn page_size(&self) -> usize {
+        self.page_size
+    }
+
+    /// Get the total number of items
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Get the indices of the items on the current page
+    #[must_use]
+    pub fn range(&self) -> Range<usize> {
+        let start = (self.page * self.page_size).min(self.total);
+        start..(start + self.page_size).min(self.total)
+    }
+
+    /// Move to the next page, returning whether the page changed
+    pub fn next_page(&mut self) -> bool {
+        self.goto(self.page + 1)
+    }
+
+    /// Move to the previous page, returning whether the page changed
+    pub fn prev_page(&mut self) -> bool {
+        self.goto(self.page.saturating_sub(1))
+    }
+
+    /// Move to a page, clamped to the last one, returning whether the page changed
+    pub fn goto(&mut self, page: usize) -> bool {
+        let page = page.min(self.page_count() - 1);
+        let changed = page != self.page;
+        self.page = page;
+        changed
+    }
+
+    /// Move to the page holding an item
+    pub fn goto_item(&mut self, index: usize) -> bool {
+        self.goto(index / self.page_size)
+    }
+
+    /// Change the total number of items, staying on the current page if it still exists
+    pub fn set_total(&mut self, total: usize) {
+        self.total = total;
+        self.goto(self.page);
+    }
+
+    /// Get the current page and the page count for display, like "3 / 12"
+    #[must_use]
+    pub fn page_indicator(&self) -> String {
+        format!("{} / {}", self.page + 1, self.page_count())
+    }
+}
+
+/// A list of lines with a selected row that scrolls to keep the selection visible
+///
+/// Render it through a mutable reference so the scroll offset persists between frames.
+/// With [`smooth_scroll`](Self::smooth_scroll) enabled, the view glides toward the new
+/// offset over several [`tick`](Self::tick)s instead of jumping straight to it.
+///
+/// With [`multi_select`](Self::multi_select) enabled, each row also has a checkbox
+/// marker. The checked rows are tracked apart from the highlighted cursor row, so the
+/// cursor can move over rows without changing which of them are checked.
+#[derive(Debug, Clone)]
+pub struct ScrollableList<'a> {
+    items: Vec<Line<'a>>,
+    selected: Option<usize>,
+    offset: usize,
+    style: Style,
+    highlight_style: Style,
+    wrap: bool,
+    smooth_scroll: bool,
+    scroll_easing: f64,
+    position: f64,
+    viewport_height: Option<usize>,
+    follow_selection: bool,
+    /// Checked rows, present only with multi-select enabled
+    checked: Option<MultiSelectState>,
+}
+
+impl<'a> ScrollableList<'a> {
+    /// Create a new list with nothing selected
+    #[must_use]
+    pub fn new(items: Vec<Line<'a>>) -> Self {
+        Self {
+            items,
+            selected: None,
+            offset: 0,
+            style: Style::default(),
+            highlight_style: Style::default().add_modifier(Modifier::REVERSED),
+            wrap: false,
+            smooth_scroll: false,
+            scroll_easing: 12.0,
+            position: 0.0,
+            viewport_height: None,
+            follow_selection: true,
+            checked: None,
+        }
+    }
+
+    /// Set the style of the list
+    #[must_use]
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the style used to highlight the selected row
+    #[must_use]
+    pub fn highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = style;
+        self
+    }
+
+    /// Set whether moving past either end wraps around to the other
+    #[must_use]
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Set whether scrolling glides toward the new offset instead of jumping to it
+    ///
+    /// The view only moves when [`tick`](Self::tick) is called. Off by default.
+    #[must_use]
+    pub fn smooth_scroll(mut self, smooth: bool) -> Self {
+        self.smooth_scroll = smooth;
+        self
+    }
+
+    /// Set how quickly smooth scrolling catches up with its target
+    ///
+    /// Each second the view covers all but `e^-easing` of the remaining distance, so
+    /// the default of 12 settles in about a quarter of a second.
+    #[must_use]
+    pub fn scroll_easing(mut self, easing: f64) -> Self {
+        self.scroll_easing = easing.max(0.0);
+        self
+    }
+
+    /// Set whether rows can be checked, showing a `[x]` or `[ ]` marker on each
+    ///
+    /// Space toggles the cursor row in [`handle_key`](Self::handle_key). Off by
+    /// default.
+    #[must_use]
+    pub fn multi_select(mut self, multi_select: bool) -> Self {
+        self.checked = multi_select.then(MultiSelectState::new);
+        self
+    }
+
+    /// Get the items in the list
+    #[must_use]
+    pub fn items(&self) -> &[Line<'a>] {
+        &self.items
+    }
+
+    /// Get the selected index
+    #[must_use]
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Get the index of the first visible row
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn offset(&self) -> usize {
+        if self.smooth_scroll {
+            self.position.round() as usize
+        } else {
+            self.offset
+        }
+    }
+
+    /// Scroll the view by a number of rows without moving the selection
+    ///
+    /// The target is clamped so the last row never scrolls above the bottom of the
+    /// viewport last rendered. The view stays put until the selection next changes.
+    pub fn scroll_by(&mut self, delta_rows: isize) {
+        let max = self
+            .items
+            .len()
+            .saturating_sub(self.viewport_height.unwrap_or(1));
+        self.offset = self.offset.saturating_add_signed(delta_rows).min(max);
+        self.follow_selection = false;
+    }
+
+    /// Advance smooth scrolling by `delta`
+    #[allow(clippy::cast_precision_loss)]
+    pub fn tick(&mut self, delta: Duration) {
+        let target = self.offset as f64;
+        let step = 1.0 - (-self.scroll_easing * delta.as_secs_f64()).exp();
+        self.position += (target - self.position) * step;
+        if (target - self.position).abs() < 0.01 {
+            self.position = target;
+        }
+    }
+
+    /// Toggle whether the cursor row is checked
+    ///
+    /// Does nothing when no row is selected or multi-select is off.
+    pub fn toggle_current(&mut self) {
+        if let (Some(index), Some(checked)) = (self.selected, &mut self.checked) {
+            checked.toggle(index);
+        }
+    }
+
+    /// Get the checked rows in ascending order
+    #[must_use]
+    pub fn selected_indices(&self) -> Vec<usize> {
+        self.checked
+            .as_ref()
+            .map(MultiSelectState::selected_indices)
+            .unwrap_or_default()
+    }
+
+    /// Check whether a row is checked
+    #[must_use]
+    pub fn is_checked(&self, index: usize) -> bool {
+        self.checked
+            .as_ref()
+            .is_some_and(|checked| checked.is_selected(index))
+    }
+
+    /// Check every row, if multi-select is on
+    pub fn select_all(&mut self) {
+        if let (Some(last), Some(checked)) = (self.items.len().checked_sub(1), &mut self.checked) {
+            checked.select_range(0, last);
+        }
+    }
+
+    /// Uncheck every row, leaving the cursor where it is
+    pub fn clear_selection(&mut self) {
+        if let Some(checked) = &mut self.checked {
+            checked.clear();
+        }
+    }
+
+    /// Select an index, clamped to the last item
+    pub fn select(&mut self, index: Option<usize>) {
+        self.selected = index
+            .filter(|_| !self.items.is_empty())
+            .map(|index| index.min(self.items.len() - 1));
+        self.follow_selection = true;
+    }
+
+    /// Move the selection down one row
+    ///
+    /// Selects the first row when nothing is selected.
+    pub fn select_next(&mut self) {
+        let last = self.items.len().checked_sub(1);
+        self.selected = match (self.selected, last) {
+            (_, None) => None,
+            (None, Some(_)) => Some(0),
+            (Some(index), Some(last)) if index >= last => Some(if self.wrap { 0 } else { last }),
+            (Some(index), Some(_)) => Some(index + 1),
+        };
+        self.follow_selection = true;
+    }
+
+    /// Move the selection up one row
+    ///
+    /// Selects the last row when nothing is selected.
+    pub fn select_prev(&mut self) {
+        let last = self.items.len().checked_sub(1);
+        self.selected = match (self.selected, last) {
+            (_, None) => None,
+            (None, Some(last)) => Some(last),
+            (Some(0), Some(last)) => Some(if self.wrap { last } else { 0 }),
+            (Some(index), Some(last)) => Some((index - 1).min(last)),
+        };
+        self.follow_selection = true;
+    }
+
+    /// Move the selection and view for navigation keys, given the viewport height
+    ///
+    /// Up and Down move one row, Page Up and Page Down move the selection and the view
+    /// by a whole viewport, and Home and End jump to the first and last rows. Paging
+    /// stops at either end rather than wrapping. With multi-select enabled, Space
+    /// toggles the cursor row. Returns whether the key was handled; an empty list
+    /// handles none.
+    pub fn handle_key(&mut self, key: &KeyEvent, viewport_height: usize) -> bool {
+        if key.kind == KeyEventKind::Release {
+            return false;
+        }
+        let page = viewport_height.max(1);
+        let Some(last) = self.items.len().checked_sub(1) else {
+            return false;
+        };
+        let max_offset = self.items.len().saturating_sub(page);
+        let current = self.selected.unwrap_or(0);
+        match key.code {
+            KeyCode::Up => self.select_prev(),
+            KeyCode::Down => self.select_next(),
+            KeyCode::PageUp => {
+                self.offset = self.offset.saturating_sub(page);
+                self.select(Some(current.saturating_sub(page)));
+            }
+            KeyCode::PageDown => {
+                self.offset = (self.offset + page).min(max_offset);
+                self.select(Some((current + page).min(last)));
+            }
+            KeyCode::Home => {
+                self.offset = 0;
+                self.select(Some(0));
+            }
+            KeyCode::End => {
+                self.offset = max_offset;
+                self.select(Some(last));
+            }
+            KeyCode::Char(' ') if self.checked.is_some() => {
+                self.toggle_current();
+                return true;
+            }
+            _ => return false,
+        }
+        self.viewport_height = Some(page);
+        self.scroll_to_selection(page);
+        true
+    }
+
+    /// Adjust the scroll offset so the selected row is inside a viewport of `height` rows
+    fn scroll_to_selection(&mut self, height: usize) {
+        if let Some(selected) = self.selected.filter(|_| self.follow_selection) {
+            if selected < self.offset {
+                self.offset = selected;
+            } else if height > 0 && selected >= self.offset + height {
+                self.offset = selected + 1 - height;
+            }
+        }
+        self.offset = self.offset.min(self.items.len().saturating_sub(height));
+    }
+}
+
+impl Widget for &mut ScrollableList<'_> {
+    #[allow(clippy::cast_precision_loss)]
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        buf.set_style(area, self.style);
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        let height = usize::from(area.height);
+        self.viewport_height = Some(height);
+        self.scroll_to_selection(height);
+        if !self.smooth_scroll {
+            self.position = self.offset as f64;
+        }
+
+        // The glide can lag behind a target that has since been clamped
+        let offset = self.offset().min(self.items.len().saturating_sub(height));
+        let visible = self.items.iter().enumerate().skip(offset);
+        for (y, (index, item)) in (area.top()..area.bottom()).zip(visible) {
+            let row = Rect::new(area.x, y, area.width, 1);
+            if let Some(checked) = &self.checked {
+                let marker = if checked.is_selected(index) {
+                    "[x] "
+                } else {
+                    "[ ] "
+                };
+                let (x, _) = buf.set_stringn(row.x, row.y, marker, row.width.into(), self.style);
+                buf.set_line(x, row.y, item, row.right().saturating_sub(x));
+            } else {
+                buf.set_line(row.x, row.y, item, row.width);
+            }
+            if self.selected == Some(index) {
+                buf.set_style(row, self.highlight_style);
+            }
+        }
+    }
+}
+
+/// A scrolling log pane that keeps at most a fixed number of lines
+///
+/// Appending past the capacity drops the oldest line, so memory stays bounded however
+/// long the log runs. While following, the view stays on the newest lines; scrolling
+/// up detaches it so new lines don't move what is being read, and scrolling back to
+/// the bottom follows again. Render it through a mutable reference so scrolling knows
+/// the height of the pane.
+#[derive(Debug, Clone)]
+pub struct LogView {
+    lines: VecDeque<Line<'static>>,
+    capacity: usize,
+    follow: bool,
+    offset: usize,
+    height: usize,
+    style: Style,
+}
+
+impl LogView {
+    /// Create an empty log that keeps up to `capacity` lines, at least one
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            lines: VecDeque::new(),
+            capacity: capacity.max(1),
+            follow: true,
+            offset: 0,
+            height: 0,
+            style: Style::default(),
+        }
+    }
+
+    /// Set the style of the pane
+    #[must_use]
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Add a line at the bottom, dropping the oldest line if the log is full
+    ///
+    /// A detached view keeps showing the same lines while they are still in the log.
+    pub fn append(&mut self, line: impl Into<Line<'static>>) {
+        self.lines.push_back(line.into());
+        if self.lines.len() > self.capacity {
+            self.lines.pop_front();
+            self.offset = self.offset.saturating_sub(1);
+        }
+    }
+
+    /// Scroll towards older lines, detaching the view from the newest line
+    pub fn scroll_up(&mut self, lines: usize) {
+        if self.max_offset() == 0 {
+            // Everything is already visible, so there is nothing to detach from
+            return;
+        }
+        self.offset = self.top().saturating_sub(lines);
+        self.follow = false;
+    }
+
+    /// Scroll towards newer lines, following again on reaching the bottom
+    pub fn scroll_down(&mut self, lines: usize) {
+        self.offset = self.top().saturating_add(lines);
+        if self.offset >= self.max_offset() {
+            self.scroll_to_bottom();
+        }
+    }
+
+    /// Jump to the newest line and follow new lines from now on
+    pub fn scroll_to_bottom(&mut self) {
+        self.follow = true;
+        self.offset = self.max_offset();
+    }
+
+    /// Check whether the view follows new lines
+    #[must_use]
+    pub fn is_following(&self) -> bool {
+        self.follow
+    }
+
+    /// Index of the first visible line
+    #[must_use]
+    pub fn top(&self) -> usize {
+        if self.follow {
+            self.max_offset()
+        } else {
+            self.offset.min(self.max_offset())
+        }
+    }
+
+    /// Iterate over the stored lines, oldest first
+    pub fn lines(&self) -> impl Iterator<Item = &Line<'static>> {
+        self.lines.iter()
+    }
+
+    /// Number of stored lines
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Check whether the log has no lines
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Most lines the log keeps
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Remove every line and follow new ones
+    pub fn clear(&mut self) {
+        self.lines.clear();
+        self.scroll_to_bottom();
+    }
+
+    /// Top line index that puts the newest line at the bottom of the pane
+    fn max_offset(&self) -> usize {
+        self.lines.len().saturating_sub(self.height.max(1))
+    }
+}
+
+impl Widget for &mut LogView {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        buf.set_style(area, self.style);
+        if area.is_empty() {
+            return;
+        }
+
+        self.height = usize::from(area.height);
+        let visible = self.lines.iter().skip(self.top());
+        for (y, line) in (area.top()..area.bottom()).zip(visible) {
+            buf.set_line(area.x, y, line, area.width);
+        }
+    }
+}
+
+/// Where an [`AnsiBlock`] gets its content from
+#[derive(Debug, Clone, Copy)]
+enum AnsiSource<'a> {
+    Text(&'a str),
+    Pixels(&'a [Vec<Color>]),
+}
+
+/// Pre-rendered terminal output, such as an image preview, copied into the buffer
+///
+/// The content is either text containing ANSI color escapes, as written by tools like
+/// `chafa` in symbol mode, or a grid of pixel colors drawn with half blocks, two pixels
+/// to a cell. Anything outside the area is clipped. Only SGR (color and attribute)
+/// sequences are understood; cursor movement and other sequences are skipped, and
+/// sixel or other image protocols aren't supported.
+#[derive(Debug, Clone)]
+pub struct AnsiBlock<'a> {
+    source: AnsiSource<'a>,
+    style: Style,
+}
+
+impl<'a> AnsiBlock<'a> {
+    /// Create a block from text with ANSI escape sequences, one row per line
+    #[must_use]
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            source: AnsiSource::Text(text),
+            style: Style::default(),
+        }
+    }
+
+    /// Create a block from rows of pixel colors, each cell showing two rows
+    ///
+    /// Each cell is a `▀` with the upper pixel as its foreground and the lower pixel
+    /// as its background. Short rows and a missing last row are filled with
+    /// [`Color::Reset`]. Under the ASCII [`CharsetProfile`] only the upper pixel of
+    /// each pair is shown, as the cell background.
+    #[must_use]
+    pub fn from_pixels(pixels: &'a [Vec<Color>]) -> Self {
+        Self {
+            source: AnsiSource::Pixels(pixels),
+            style: Style::default(),
+        }
+    }
+
+    /// Set the style that escape sequences start from and reset to
+    #[must_use]
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Size of the content in cells as `(width, height)`
+    #[must_use]
+    pub fn size(&self) -> (u16, u16) {
+        let clamp = |n: usize| u16::try_from(n).unwrap_or(u16::MAX);
+        match self.source {
+            AnsiSource::Text(text) => {
+                let width = text
+                    .lines()
+                    .map(|line| strip_ansi(line).width())
+                    .max()
+                    .unwrap_or(0);
+                (clamp(width), clamp(text.lines().count()))
+            }
+            AnsiSource::Pixels(pixels) => {
+                let width = pixels.iter().map(Vec::len).max().unwrap_or(0);
+                (clamp(width), clamp(pixels.len().div_ceil(2)))
+            }
+        }
+    }
+
+    /// Draw rows of text, applying the SGR sequences in them
+    fn render_text(text: &str, base: Style, area: Rect, buf: &mut Buffer) {
+        let mut style = base;
+        for (y, line) in (area.top()..area.bottom()).zip(text.lines()) {
+            let mut x = area.left();
+            let mut chars = line.chars().peekable();
+            while let Some(c) = chars.next() {
+                if c == '\x1b' {
+                    if let Some(params) = read_escape(&mut chars) {
+                        style = apply_sgr(style, base, &params);
+                    }
+                    continue;
+                }
+                let c = if c == '\t' { ' ' } else { c };
+                let width = u16::try_from(c.width().unwrap_or(0)).unwrap_or(0);
+                if c.is_control() || width == 0 {
+                    continue;
+                }
+                // Styles still apply after the clip edge, to later rows
+                if x.saturating_add(width) <= area.right() {
+                    buf[(x, y)].set_char(c).set_style(style);
+                }
+                x = x.saturating_add(width);
+            }
+        }
+    }
+
+    /// Draw pairs of pixel rows as half blocks
+    fn render_pixels(pixels: &[Vec<Color>], base: Style, area: Rect, buf: &mut Buffer) {
+        let depth = ColorDepth::current();
+        let ascii = CharsetProfile::current().is_ascii();
+        let pixel = |row: usize, column: usize| {
+            pixels
+                .get(row)
+                .and_then(|row| row.get(column))
+                .map_or(Color::Reset, |&color| depth.apply(color))
+        };
+        for (y, pair) in (area.top()..area.bottom()).zip((0..pixels.len()).step_by(2)) {
+            let columns = pixels[pair]
+                .len()
+                .max(pixels.get(pair + 1).map_or(0, Vec::len));
+            for (x, column) in (area.left()..area.right()).zip(0..columns) {
+                let (top, bottom) = (pixel(pair, column), pixel(pair + 1, column));
+                let cell = &mut buf[(x, y)];
+                cell.set_style(base);
+                if ascii {
+                    cell.set_char(' ').set_bg(top);
+                } else {
+                    cell.set_char('▀').set_fg(top).set_bg(bottom);
+                }
+            }
+        }
+    }
+}
+
+impl Widget for AnsiBlock<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = area.intersection(buf.area);
+        match self.source {
+            AnsiSource::Text(text) => Self::render_text(text, self.style, area, buf),
+            AnsiSource::Pixels(pixels) => Self::render_pixels(pixels, self.style, area, buf),
+        }
+    }
+}
+
+/// Consume an escape sequence after its ESC, returning its parameters if it is SGR
+fn read_escape(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> Option<String> {
+    match chars.next()? {
+        // CSI: parameters, then a final byte from '@' to '~'
+        '[' => {
+            let mut params = String::new();
+            for c in chars.by_ref() {
+                if ('@'..='~').contains(&c) {
+                    return (c == 'm').then_some(params);
+                }
+                params.push(c);
+            }
+            None
+        }
+        // OSC: runs until BEL or ESC \
+        ']' => {
+            while let Some(c) = chars.next() {
+                if c == '\x07' || (c == '\x1b' && chars.next_if_eq(&'\\').is_some()) {
+                    break;
+                }
+            }
+            None
+        }
+        // Two-character sequences such as ESC 7 (save cursor)
+        _ => None,
+    }
+}
+
+/// Text of a line with its escape sequences removed
+fn strip_ansi(line: &str) -> String {
+    let mut chars = line.chars().peekable();
+    let mut plain = String::new();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            read_escape(&mut chars);
+        } else if !c.is_control() {
+            plain.push(c);
+        }
+    }
+    plain
+}
+
+/// Update a style with the parameters of an SGR sequence
+fn apply_sgr(mut style: Style, base: Style, params: &str) -> Style {
+    const NAMED: [Color; 8] = [
+        Color::Black,
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+        Color::Gray,
+    ];
+    const BRIGHT: [Color; 8] = [
+        Color::DarkGray,
+        Color::LightRed,
+        Color::LightGreen,
+        Color::LightYellow,
+        Color::LightBlue,
+        Color::LightMagenta,
+        Color::LightCyan,
+        Color::White,
+    ];
+
+    let mut codes = params
+        .split(';')
+        .map(|code| code.parse::<u16>().unwrap_or(0));
+    while let Some(code) = codes.next() {
+        style = match code {
+            0 => base,
+            1 => style.add_modifier(Modifier::BOLD),
+            2 => style.add_modifier(Modifier::DIM),
+            3 => style.add_modifier(Modifier::ITALIC),
+            4 => style.add_modifier(Modifier::UNDERLINED),
+            7 => style.add_modifier(Modifier::REVERSED),
+            22 => style.remove_modifier(Modifier::BOLD | Modifier::DIM),
+            23 => style.remove_modifier(Modifier::ITALIC),
+            24 => style.remove_modifier(Modifier::UNDERLINED),
+            27 => style.remove_modifier(Modifier::REVERSED),
+            30..=37 => style.fg(NAMED[usize::from(code - 30)]),
+            40..=47 => style.bg(NAMED[usize::from(code - 40)]),
+            90..=97 => style.fg(BRIGHT[usize::from(code - 90)]),
+            100..=107 => style.bg(BRIGHT[usize::from(code - 100)]),
+            39 => style.fg(base.fg.unwrap_or(Color::Reset)),
+            49 => style.bg(base.bg.unwrap_or(Color::Reset)),
+            38 | 48 => {
+                let color = match codes.next() {
+                    Some(5) => codes
+                        .next()
+                        .and_then(|index| u8::try_from(index).ok())
+                        .map(Color::Indexed),
+                    Some(2) => {
+                        let mut channel =
+                            || codes.next().and_then(|value| u8::try_from(value).ok());
+                        match (channel(), channel(), channel()) {
+                            (Some(r), Some(g), Some(b)) => Some(Color::Rgb(r, g, b)),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                };
+                match color.map(|color| ColorDepth::current().apply(color)) {
+                    Some(color) if code == 38 => style.fg(color),
+                    Some(color) => style.bg(color),
+                    None => style,
+                }
+            }
+            _ => style,
+        };
+    }
+    style
+}
+
+/// Two panes side by side or stacked, with a divider the user can drag to resize them
+///
+/// [`split`](Self::split) gives the areas of the two panes for the app to render
+/// into, and rendering the split pane draws the divider between them. Forward mouse
+/// events to [`handle_mouse`](Self::handle_mouse) to let the divider be dragged; the
+/// terminal only reports them once mouse capture is enabled, for example with
+/// crossterm's `EnableMouseCapture`. Both panes keep at least the minimum size when
+/// the area has room for it.
+#[derive(Debug, Clone)]
+pub struct SplitPane {
+    direction: Direction,
+    ratio: f64,
+    min_size: u16,
+    dragging: bool,
+    style: Style,
+    drag_style: Style,
+}
+
+impl SplitPane {
+    /// Create a split pane with the divider in the middle
+    ///
+    /// [`Direction::Horizontal`] puts the panes side by side with a vertical divider,
+    /// and [`Direction::Vertical`] stacks them with a horizontal one.
+    #[must_use]
+    pub fn new(direction: Direction) -> Self {
+        Self {
+            direction,
+            ratio: 0.5,
+            min_size: 3,
+            dragging: false,
+            style: Style::default().fg(Color::DarkGray),
+            drag_style: Style::default().fg(Color::Yellow),
+        }
+    }
+
+    /// Set the share of the space given to the first pane, from 0.0 to 1.0
+    #[must_use]
+    pub fn ratio(mut self, ratio: f64) -> Self {
+        self.set_ratio(ratio);
+        self
+    }
+
+    /// Set the smallest width or height either pane can be dragged to
+    #[must_use]
+    pub fn min_size(mut self, min_size: u16) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    /// Set the style of the divider
+    #[must_use]
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the style of the divider while it is being dragged
+    #[must_use]
+    pub fn drag_style(mut self, style: Style) -> Self {
+        self.drag_style = style;
+        self
+    }
+
+    /// Get the share of the space given to the first pane
+    #[must_use]
+    pub fn current_ratio(&self) -> f64 {
+        self.ratio
+    }
+
+    /// Change the share of the space given to the first pane, clamped to 0.0..=1.0
+    pub fn set_ratio(&mut self, ratio: f64) {
+        self.ratio = if ratio.is_nan() {
+            0.5
+        } else {
+            ratio.clamp(0.0, 1.0)
+        };
+    }
+
+    /// Check whether the divider is being dragged
+    #[must_use]
+    pub fn is_dragging(&self) -> bool {
+        self.dragging
+    }
+
+    /// Split `area` into the first and second pane, leaving a line for the divider
+    #[must_use]
+    pub fn split(&self, area: Rect) -> (Rect, Rect) {
+        let divider = self.divider(area);
+        match self.direction {
+            Direction::Horizontal => (
+                Rect::new(area.x, area.y, divider.x - area.x, area.height),
+                Rect::new(
+                    divider.right(),
+                    area.y,
+                    area.right() - divider.right(),
+                    area.height,
+                ),
+            ),
+            Direction::Vertical => (
+                Rect::new(area.x, area.y, area.width, divider.y - area.y),
+                Rect::new(
+                    area.x,
+                    divider.bottom(),
+                    area.width,
+                    area.bottom() - divider.bottom(),
+                ),
+            ),
+        }
+    }
+
+    /// Get the line of `area` the divider occupies
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn divider(&self, area: Rect) -> Rect {
+        let length = self.length(area);
+        let first = self.clamp_first((f64::from(length) * self.ratio).round() as u16, length);
+        match self.direction {
+            Direction::Horizontal if area.width > 0 => {
+                Rect::new(area.x + first, area.y, 1, area.height)
+            }
+            Direction::Vertical if area.height > 0 => {
+                Rect::new(area.x, area.y + first, area.width, 1)
+            }
+            _ => Rect::new(area.x, area.y, 0, 0),
+        }
+    }
+
+    /// Start, follow, and end drags of the divider, returning whether the event was used
+    ///
+    /// A left press on the divider starts a drag, dragging moves the divider to the
+    /// pointer, and releasing the button ends the drag. `area` must be the area the
+    /// split pane was last rendered into.
+    pub fn handle_mouse(&mut self, event: &MouseEvent, area: Rect) -> bool {
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let position = Position::new(event.column, event.row);
+                self.dragging = self.divider(area).contains(position);
+                self.dragging
+            }
+            MouseEventKind::Drag(MouseButton::Left) if self.dragging => {
+                let length = self.length(area);
+                let first = match self.direction {
+                    Direction::Horizontal => event.column.saturating_sub(area.x),
+                    Direction::Vertical => event.row.saturating_sub(area.y),
+                };
+                if length > 0 {
+                    let first = self.clamp_first(first, length);
+                    self.ratio = f64::from(first) / f64::from(length);
+                }
+                true
+            }
+            MouseEventKind::Up(MouseButton::Left) if self.dragging => {
+                self.dragging = false;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Space shared by the two panes along the split direction
+    fn length(&self, area: Rect) -> u16 {
+        match self.direction {
+            Direction::Horizontal => area.width.saturating_sub(1),
+            Direction::Vertical => area.height.saturating_sub(1),
+        }
+    }
+
+    /// Keep the first pane's size within `length` and both panes at the minimum size
+    fn clamp_first(&self, first: u16, length: u16) -> u16 {
+        let first = first.min(length);
+        if length < self.min_size.saturating_mul(2) {
+            return first;
+        }
+        first.clamp(self.min_size, length - self.min_size)
+    }
+}
+
+impl Widget for &SplitPane {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let divider = self.divider(area);
+        let style = if self.dragging {
+            self.drag_style
+        } else {
+            self.style
+        };
+        let charset = CharsetProfile::current();
+        let glyph = match self.direction {
+            Direction::Horizontal => charset.pick("│", "|"),
+            Direction::Vertical => charset.pick("─", "-"),
+        };
+        for position in divider.positions() {
+            buf[position].set_symbol(glyph).set_style(style);
+        }
+    }
+}
+
+impl Widget for SplitPane {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        (&self).render(area, buf);
+    }
+}
+
+/// Content larger than the screen, drawn off-screen and shown through a scrolled window
+///
+/// Widgets render into the viewport at the content's full size with
+/// [`render_content`](Self::render_content), and rendering the viewport copies the
+/// window starting at the scroll offset into the visible area. Render it through a
+/// mutable reference so the scroll offset is clamped to what the area can show. When
+/// the area is larger than the content, the content sits in its top-left corner.
+#[derive(Debug, Clone)]
+pub struct Viewport {
+    content: Buffer,
+    scroll_x: u16,
+    scroll_y: u16,
+}
+
+impl Viewport {
+    /// Create a viewport over empty content of the given size
+    #[must_use]
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            content: Buffer::empty(Rect::new(0, 0, width, height)),
+            scroll_x: 0,
+            scroll_y: 0,
+        }
+    }
+
+    /// Area of the content, starting at the origin
+    #[must_use]
+    pub fn content_area(&self) -> Rect {
+        self.content.area
+    }
+
+    /// Change the size of the content, clearing it
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.content = Buffer::empty(Rect::new(0, 0, width, height));
+        self.scroll_x = self.scroll_x.min(width.saturating_sub(1));
+        self.scroll_y = self.scroll_y.min(height.saturating_sub(1));
+    }
+
+    /// Reset every cell of the content
+    pub fn clear(&mut self) {
+        self.content.reset();
+    }
+
+    /// Buffer holding the content, for drawing into directly
+    pub fn buffer_mut(&mut self) -> &mut Buffer {
+        &mut self.content
+    }
+
+    /// Render a widget over the whole content area
+    pub fn render_content<W: Widget>(&mut self, widget: W) {
+        widget.render(self.content.area, &mut self.content);
+    }
+
+    /// Column and row of the content shown in the top-left corner
+    #[must_use]
+    pub fn scroll_offset(&self) -> (u16, u16) {
+        (self.scroll_x, self.scroll_y)
+    }
+
+    /// Scroll so the given column and row are in the top-left corner
+    ///
+    /// The offset is kept inside the content, and reduced further on render if the
+    /// area would otherwise show past its edges.
+    pub fn scroll_to(&mut self, x: u16, y: u16) {
+        self.scroll_x = x.min(self.content.area.width.saturating_sub(1));
+        self.scroll_y = y.min(self.content.area.height.saturating_sub(1));
+    }
+
+    /// Scroll by the given number of columns and rows, negative values moving back
+    pub fn scroll_by(&mut self, dx: i32, dy: i32) {
+        let shift = |offset: u16, delta: i32| {
+            u16::try_from((i32::from(offset) + delta).max(0)).unwrap_or(u16::MAX)
+        };
+        self.scroll_to(shift(self.scroll_x, dx), shift(self.scroll_y, dy));
+    }
+
+    /// Largest scroll offset that keeps an area of the given size filled with content
+    #[must_use]
+    pub fn max_scroll(&self, width: u16, height: u16) -> (u16, u16) {
+        (
+            self.content.area.width.saturating_sub(width),
+            self.content.area.height.saturating_sub(height),
+        )
+    }
+}
+
+impl Widget for &mut Viewport {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = area.intersection(buf.area);
+        if area.is_empty() {
+            return;
+        }
+        let (max_x, max_y) = self.max_scroll(area.width, area.height);
+        self.scroll_x = self.scroll_x.min(max_x);
+        self.scroll_y = self.scroll_y.min(max_y);
+
+        let content = self.content.area;
+        let width = area.width.min(content.width - self.scroll_x);
+        let height = area.height.min(content.height - self.scroll_y);
+        for y in 0..height {
+            for x in 0..width {
+                let cell = &self.content[(self.scroll_x + x, self.scroll_y + y)];
+                buf[(area.x + x, area.y + y)] = cell.clone();
+            }
+        }
+    }
+}
+
+/// A table with a bold header row and columns sized by [`Constraint`]s
+///
+/// Cells wider than their column are cut short with an ellipsis. When a row is
+/// selected, the body scrolls just far enough to keep it on screen.
+#[derive(Debug, Clone)]
+pub struct SimpleTable<'a> {
+    header: Vec<&'a str>,
+    rows: Vec<Vec<Span<'a>>>,
+    widths: Vec<Constraint>,
+    column_spacing: u16,
+    separator: bool,
+    selected: Option<usize>,
+    style: Style,
+    highlight_style: Style,
+}
+
+impl<'a> SimpleTable<'a> {
+    /// Create a table with one constraint per column
+    #[must_use]
+    pub fn new(header: Vec<&'a str>, widths: Vec<Constraint>) -> Self {
+        Self {
+            header,
+            rows: Vec::new(),
+            widths,
+            column_spacing: 1,
+            separator: false,
+            selected: None,
+            style: Style::default(),
+            highlight_style: Style::default().add_modifier(Modifier::REVERSED),
+        }
+    }
+
+    /// Set the body rows
+    #[must_use]
+    pub fn rows(mut self, rows: Vec<Vec<Span<'a>>>) -> Self {
+        self.rows = rows;
+        self
+    }
+
+    /// Set the number of blank columns between adjacent columns
+    #[must_use]
+    pub fn column_spacing(mut self, spacing: u16) -> Self {
+        self.column_spacing = spacing;
+        self
+    }
+
+    /// Draw a line between the header and the body
+    #[must_use]
+    pub fn separator(mut self, separator: bool) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Set the selected row
+    #[must_use]
+    pub fn selected(mut self, selected: Option<usize>) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    /// Set the style of the table
+    #[must_use]
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the style used to highlight the selected row
+    #[must_use]
+    pub fn highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = style;
+        self
+    }
+
+    /// Resolve the column constraints into an x offset and width per column
+    #[must_use]
+    pub fn column_layout(&self, width: u16) -> Vec<(u16, u16)> {
+        Layout::horizontal(self.widths.iter().copied())
+            .spacing(self.column_spacing)
+            .split(Rect::new(0, 0, width, 1))
+            .iter()
+            .map(|column| (column.x, column.width))
+            .collect()
+    }
+
+    /// Draw one row of cells, truncating any that overflow their column
+    fn render_row(columns: &[(u16, u16)], cells: &[Span], area: Rect, y: u16, buf: &mut Buffer) {
+        for (&(x, width), cell) in columns.iter().zip(cells) {
+            let cell = fit_span(cell, width);
+            buf.set_span(area.x + x, y, &cell, width);
+        }
+    }
+}
+
+/// Cut a span down to `width` columns, ending it with an ellipsis if anything was cut
+fn fit_span<'a>(span: &Span<'a>, width: u16) -> Span<'a> {
+    if text_width(&span.content) <= width {
+        return span.clone();
+    }
+    if width == 0 {
+        return Span::styled("", span.style);
+    }
+    let ellipsis = CharsetProfile::current().pick("…", ".");
+    let kept = truncate_to_width(&span.content, width - 1);
+    Span::styled(format!("{kept}{ellipsis}"), span.style)
+}
+
+impl Widget for SimpleTable<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        buf.set_style(area, self.style);
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        let columns = self.column_layout(area.width);
+        let header: Vec<Span> = self
+            .header
+            .iter()
+            .map(|title| Span::styled(*title, Style::default().add_modifier(Modifier::BOLD)))
+            .collect();
+        Self::render_row(&columns, &header, area, area.y, buf);
+
+        let mut body = area;
+        body.y += 1;
+        body.height -= 1;
+        if self.separator && body.height > 0 {
+            let rule = CharsetProfile::current().pick("─", "-");
+            buf.set_string(
+                body.x,
+                body.y,
+                rule.repeat(usize::from(body.width)),
+                self.style,
+            );
+            body.y += 1;
+            body.height -= 1;
+        }
+
+        let height = usize::from(body.height);
+        let offset = self
+            .selected
+            .map_or(0, |selected| (selected + 1).saturating_sub(height));
+        let visible = self.rows.iter().enumerate().skip(offset);
+        for (y, (index, cells)) in (body.top()..body.bottom()).zip(visible) {
+            let row = Rect::new(body.x, y, body.width, 1);
+            if self.selected == Some(index) {
+                buf.set_style(row, self.highlight_style);
+            }
+            Self::render_row(&columns, cells, row, y, buf);
+        }
+    }
+}
+
+/// A node of a [`Tree`], given in depth-first order along with its depth
+#[derive(Debug, Clone)]
+pub struct TreeNode<'a> {
+    label: Line<'a>,
+    depth: usize,
+    expanded: bool,
+}
+
+impl<'a> TreeNode<'a> {
+    /// Create an expanded node at `depth`, where top-level nodes have depth 0
+    #[must_use]
+    pub fn new(label: impl Into<Line<'a>>, depth: usize) -> Self {
+        Self {
+            label: label.into(),
+            depth,
+            expanded: true,
+        }
+    }
+
+    /// Set whether the node's descendants are shown
+    #[must_use]
+    pub fn expanded(mut self, expanded: bool) -> Self {
+        self.expanded = expanded;
+        self
+    }
+}
+
+/// A tree of nodes joined by box-drawing connectors
+///
+/// Nodes are given as a flat depth-first list. Each child is drawn with `├─`, or
+/// `└─` if it is the last of its siblings, and `│` continues the line of every
+/// ancestor that still has siblings below. Collapsed nodes hide their descendants.
+#[derive(Debug, Clone)]
+pub struct Tree<'a> {
+    nodes: Vec<TreeNode<'a>>,
+    selected: Option<usize>,
+    style: Style,
+    highlight_style: Style,
+}
+
+impl<'a> Tree<'a> {
+    /// Create a tree from nodes in depth-first order
+    #[must_use]
+    pub fn new(nodes: Vec<TreeNode<'a>>) -> Self {
+        Self {
+            nodes,
+            selected: None,
+            style: Style::default(),
+            highlight_style: Style::default().add_modifier(Modifier::REVERSED),
+        }
+    }
+
+    /// Set the selected node by its index in the node list
+    #[must_use]
+    pub fn selected(mut self, selected: Option<usize>) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    /// Set the style of the tree
+    #[must_use]
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the style used to highlight the selected node
+    #[must_use]
+    pub fn highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = style;
+        self
+    }
+
+    /// Get the indices of the nodes not hidden by a collapsed ancestor, in display order
+    #[must_use]
+    pub fn visible_indices(&self) -> Vec<usize> {
+        let mut visible = Vec::with_capacity(self.nodes.len());
+        let mut hidden_below: Option<usize> = None;
+        for (index, node) in self.nodes.iter().enumerate() {
+            if hidden_below.is_some_and(|depth| node.depth > depth) {
+                continue;
+            }
+            hidden_below = (!node.expanded).then_some(node.depth);
+            visible.push(index);
+        }
+        visible
+    }
+
+    /// Find, for every node, whether another sibling follows it under the same parent
+    fn has_next_sibling(&self) -> Vec<bool> {
+        let mut has_next = vec![false; self.nodes.len()];
+        // Whether a node has been seen at each depth since the last shallower node
+        let mut seen: Vec<bool> = Vec::new();
+        for (index, node) in self.nodes.iter().enumerate().rev() {
+            has_next[index] = seen.get(node.depth).copied().unwrap_or(false);
+            seen.resize(node.depth + 1, false);
+            seen[node.depth] = true;
+        }
+        has_next
+    }
+
+    /// Build the connector prefix for every node
+    fn prefixes(&self) -> Vec<String> {
+        let charset = CharsetProfile::current();
+        let has_next = self.has_next_sibling();
+        let mut ancestors: Vec<bool> = Vec::new();
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| {
+                ancestors.truncate(node.depth);
+                let mut prefix = String::new();
+                if node.depth > 0 {
+                    for &continues in ancestors.iter().skip(1) {
+                        prefix.push_str(charset.pick(
+                            if continues { "│ " } else { "  " },
+                            if continues { "| " } else { "  " },
+                        ));
+                    }
+                    prefix.push_str(if has_next[index] {
+                        charset.pick("├─", "|-")
+                    } else {
+                        charset.pick("└─", "`-")
+                    });
+                }
+                let has_children = self
+                    .nodes
+                    .get(index + 1)
+                    .is_some_and(|next| next.depth > node.depth);
+                prefix.push_str(match (has_children, node.expanded) {
+                    (false, _) => "  ",
+                    (true, true) => charset.pick("▾ ", "- "),
+                    (true, false) => charset.pick("▸ ", "+ "),
+                });
+                ancestors.resize(node.depth, false);
+                ancestors.push(has_next[index]);
+                prefix
+            })
+            .collect()
+    }
+}
+
+impl Widget for Tree<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        buf.set_style(area, self.style);
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        let prefixes = self.prefixes();
+        let visible = self.visible_indices();
+        let height = usize::from(area.height);
+        let offset = self
+            .selected
+            .and_then(|selected| visible.iter().position(|&index| index == selected))
+            .map_or(0, |row| (row + 1).saturating_sub(height));
+
+        for (y, &index) in (area.top()..area.bottom()).zip(visible.iter().skip(offset)) {
+            let row = Rect::new(area.x, y, area.width, 1);
+            let (x, _) = buf.set_stringn(
+                row.x,
+                y,
+                &prefixes[index],
+                usize::from(row.width),
+                self.style,
+            );
+            buf.set_line(
+                x,
+                y,
+                &self.nodes[index].label,
+                row.right().saturating_sub(x),
+            );
+            if self.selected == Some(index) {
+                buf.set_style(row, self.highlight_style);
+            }
+        }
+    }
+}
+
+/// A single-line tab strip with the selected tab highlighted
+///
+/// When the titles don't fit, the visible window scrolls to keep the selected tab on
+/// screen and `‹`/`›` mark the hidden tabs on either side.
+#[derive(Debug, Clone)]
+pub struct Tabs<'a> {
+    titles: Vec<&'a str>,
+    selected: usize,
+    status: StatusColor,
+    style: Style,
+    divider: &'a str,
+}
+
+impl<'a> Tabs<'a> {
+    /// Create a new tab strip with the first tab selected
+    #[must_use]
+    pub fn new(titles: Vec<&'a str>) -> Self {
+        Self {
+            titles,
+            selected: 0,
+            status: StatusColor::Info,
+            style: Style::default(),
+            divider: CharsetProfile::current().pick("│", "|"),
+        }
+    }
+
+    /// Select a tab, clamped to the last one
+    #[must_use]
+    pub fn select(mut self, index: usize) -> Self {
+        self.selected = index.min(self.titles.len().saturating_sub(1));
+        self
+    }
+
+    /// Set the status color of the selected tab
+    #[must_use]
+    pub fn status(mut self, status: StatusColor) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Set the style of the tab strip
+    #[must_use]
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the divider drawn between tabs
+    #[must_use]
+    pub fn divider(mut self, divider: &'a str) -> Self {
+        self.divider = divider;
+        self
+    }
+
+    /// Get the selected tab index
+    #[must_use]
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Select the next tab, wrapping to the first
+    pub fn next(&mut self) {
+        if !self.titles.is_empty() {
+            self.selected = (self.selected + 1) % self.titles.len();
+        }
+    }
+
+    /// Select the previous tab, wrapping to the last
+    pub fn prev(&mut self) {
+        if !self.titles.is_empty() {
+            self.selected = self
+                .selected
+                .checked_sub(1)
+                .unwrap_or(self.titles.len() - 1);
+        }
+    }
+
+    /// Get the area below the tab strip where the active pane should be rendered
+    #[must_use]
+    pub fn content_area(area: Rect) -> Rect {
+        Rect {
+            y: area.y.saturating_add(1).min(area.bottom()),
+            height: area.height.saturating_sub(1),
+            ..area
+        }
+    }
+
+    /// Find the range of tabs to show so the selected tab fits in `width` columns
+    fn visible_range(&self, widths: &[usize], width: usize) -> (usize, usize) {
+        let divider = Span::raw(self.divider).width();
+        let total = widths.iter().sum::<usize>() + divider * widths.len().saturating_sub(1);
+        if total <= width {
+            return (0, widths.len());
+        }
+
+        // Reserve a column on each side for the overflow indicators
+        let budget = width.saturating_sub(2);
+        let (mut start, mut end) = (self.selected, self.selected + 1);
+        let mut used = widths[self.selected];
+        while end < widths.len() && used + divider + widths[end] <= budget {
+            used += divider + widths[end];
+            end += 1;
+        }
+        while start > 0 && used + divider + widths[start - 1] <= budget {
+            used += divider + widths[start - 1];
+            start -= 1;
+        }
+        (start, end)
+    }
+}
+
+impl Widget for Tabs<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width == 0 || area.height == 0 || self.titles.is_empty() {
+            return;
+        }
+
+        let strip = Rect { height: 1, ..area };
+        buf.set_style(strip, self.style);
+
+        let labels: Vec<String> = self
+            .titles
+            .iter()
+            .map(|title| format!(" {title} "))
+            .collect();
+        let widths: Vec<usize> = labels
+            .iter()
+            .map(|label| Span::raw(label).width())
+            .collect();
+        let (start, end) = self.visible_range(&widths, usize::from(area.width));
+        let overflowing = start > 0 || end < labels.len();
+
+        let charset = CharsetProfile::current();
+        let mut x = strip.x;
+        if overflowing {
+            if start > 0 {
+                buf.set_string(x, strip.y, charset.pick("‹", "<"), self.style);
+            }
+            x += 1;
+        }
+
+        let right_edge = strip.right() - u16::from(overflowing);
+        let active_style = self
+            .style
+            .fg(self.status.into())
+            .add_modifier(Modifier::BOLD | Modifier::REVERSED);
+        for (index, label) in labels.iter().enumerate().take(end).skip(start) {
+            if index > start {
+                (x, _) = buf.set_stringn(
+                    x,
+                    strip.y,
+                    self.divider,
+                    usize::from(right_edge.saturating_sub(x)),
+                    self.style,
+                );
+            }
+            let style = if index == self.selected {
+                active_style
+            } else {
+                self.style
+            };
+            (x, _) = buf.set_stringn(
+                x,
+                strip.y,
+                label,
+                usize::from(right_edge.saturating_sub(x)),
+                style,
+            );
+        }
+
+        if end < labels.len() {
+            buf.set_string(
+                strip.right() - 1,
+                strip.y,
+                charset.pick("›", ">"),
+                self.style,
+            );
+        }
+    }
+}
+
+/// Dim every cell in `area` while keeping the glyphs already drawn there
+///
+/// Used to push existing content into the background behind overlays.
+pub fn dim_area(area: Rect, buf: &mut Buffer) {
+    dim_area_by(area, buf, 0.5);
+}
+
+/// Dim every cell in `area` by `factor`, from 0.0 (unchanged) to 1.0 (black)
+///
+/// RGB colors are darkened proportionally. Named and indexed colors can't be scaled,
+/// so their foreground falls back to dark gray.
+pub fn dim_area_by(area: Rect, buf: &mut Buffer, factor: f64) {
+    let factor = if factor.is_nan() {
+        0.0
+    } else {
+        factor.clamp(0.0, 1.0)
+    };
+    if factor == 0.0 {
+        return;
+    }
+
+    let area = area.intersection(buf.area);
+    for position in area.positions() {
+        let cell = &mut buf[position];
+        let fg = match cell.fg {
+            Color::Rgb(..) => darken(cell.fg, factor),
+            _ => Color::DarkGray,
+        };
+        let bg = darken(cell.bg, factor);
+        cell.set_fg(fg).set_bg(bg);
+        cell.modifier.insert(Modifier::DIM);
+    }
+}
+
+/// Color `text` with a gradient running through `colors` from the first char to the last
+///
+/// The colors are spread evenly over the text and mixed as by [`Gradient`]. Runs of
+/// chars that end up with the same color share a span.
+#[must_use]
+pub fn gradient_line(text: &str, colors: &[Color]) -> Line<'static> {
+    if colors.is_empty() {
+        return Line::from(text.to_owned());
+    }
+    let chars: Vec<char> = text.chars().collect();
+
+    let gradient = Gradient::evenly(colors);
+    let last = chars.len().saturating_sub(1).max(1);
+    let mut spans = Vec::new();
+    let mut run = String::new();
+    let mut run_color = colors[0];
+    for (i, c) in chars.into_iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let color = gradient.sample(i as f64 / last as f64);
+        if color != run_color && !run.is_empty() {
+            spans.push(Span::styled(
+                std::mem::take(&mut run),
+                Style::new().fg(run_color),
+            ));
+        }
+        run_color = color;
+        run.push(c);
+    }
+    if !run.is_empty() {
+        spans.push(Span::styled(run, Style::new().fg(run_color)));
+    }
+    Line::from(spans)
+}
+
+/// Build a styled line from lightweight inline markup
+///
+/// Supported syntax:
+/// - `*bold*` and `_italic_`
+/// - `` `code` ``, inside which no other markup is recognized
+/// - `{color:red}text{/}`, taking any color name or `#rrggbb` value that
+///   [`Color`] parses; colors nest and `{/}` restores the previous one
+///
+/// A backslash escapes the next character. Markers that are never closed apply to the
+/// rest of the line, and `{...}` sequences that aren't valid tags are kept as text.
+#[must_use]
+pub fn markup(text: &str) -> Line<'static> {
+    let mut spans = Vec::new();
+    let mut current = String::new();
+    let mut state = MarkupState::default();
+
+    let mut chars = text.char_indices();
+    while let Some((index, c)) = chars.next() {
+        // Work out the state change this character makes, if any
+        let mut next = state.clone();
+        match c {
+            '\\' => {
+                current.extend(chars.next().map(|(_, escaped)| escaped));
+                continue;
+            }
+            '`' => next.code = !next.code,
+            '*' if !state.code => next.bold = !next.bold,
+            '_' if !state.code => next.italic = !next.italic,
+            '{' if !state.code => {
+                let rest = &text[index + 1..];
+                let Some((tag, parsed)) = rest
+                    .find('}')
+                    .map(|end| &rest[..end])
+                    .and_then(|tag| Some((tag, parse_markup_tag(tag)?)))
+                else {
+                    current.push(c);
+                    continue;
+                };
+                match parsed {
+                    MarkupTag::Color(color) => next.colors.push(color),
+                    MarkupTag::Close => {
+                        next.colors.pop();
+                    }
+                }
+                // Skip the rest of the tag and its closing brace
+                chars.nth(tag.chars().count());
+            }
+            _ => {
+                current.push(c);
+                continue;
+            }
+        }
+
+        if !current.is_empty() {
+            spans.push(Span::styled(std::mem::take(&mut current), state.style()));
+        }
+        state = next;
+    }
+    if !current.is_empty() {
+        spans.push(Span::styled(current, state.style()));
+    }
+    Line::from(spans)
+}
+
+/// Styling in effect at a point in [`markup`] text
+#[derive(Debug, Clone, Default)]
+struct MarkupState {
+    bold: bool,
+    italic: bool,
+    code: bool,
+    colors: Vec<Color>,
+}
+
+impl MarkupState {
+    fn style(&self) -> Style {
+        let mut style = Style::new();
+        if self.bold {
+            style = style.add_modifier(Modifier::BOLD);
+        }
+        if self.italic {
+            style = style.add_modifier(Modifier::ITALIC);
+        }
+        if self.code {
+            style = style.fg(Color::Cyan);
+        } else if let Some(&color) = self.colors.last() {
+            style = style.fg(color);
+        }
+        style
+    }
+}
+
+/// A `{...}` tag in [`markup`] text
+enum MarkupTag {
+    Color(Color),
+    Close,
+}
+
+/// Parse the inside of a `{...}` markup tag, or `None` if it is not a tag
+fn parse_markup_tag(tag: &str) -> Option<MarkupTag> {
+    if tag == "/" {
+        return Some(MarkupTag::Close);
+    }
+    let color = tag.strip_prefix("color:")?.trim().parse().ok()?;
+    Some(MarkupTag::Color(color))
+}
+
+/// Braille spinner frames, advanced ten times per second
+const SPINNER_FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// ASCII stand-ins for [`SPINNER_FRAMES`], also advanced ten times per second
+const ASCII_SPINNER_FRAMES: [char; 4] = ['|', '/', '-', '\\'];
+
+/// Get the spinner frame to show after `elapsed` time
+#[allow(clippy::cast_possible_truncation)]
+fn spinner_frame(elapsed: Duration) -> char {
+    let frame = (elapsed.as_millis() / 100) as usize;
+    let frames: &[char] = CharsetProfile::current().pick(&SPINNER_FRAMES, &ASCII_SPINNER_FRAMES);
+    frames[frame % frames.len()]
+}
+
+/// Dims an area and shows a centered spinner with an optional message on top
+#[derive(Debug, Clone)]
+pub struct LoadingOverlay<'a> {
+    message: Option<&'a str>,
+    elapsed: Duration,
+    dim_factor: f64,
+    style: Style,
+}
+
+impl<'a> LoadingOverlay<'a> {
+    /// Create a new loading overlay
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            message: None,
+            elapsed: Duration::ZERO,
+            dim_factor: 0.5,
+            style: Style::default(),
+        }
+    }
+
+    /// Set the message shown below the spinner
+    #[must_use]
+    pub fn message(mut self, message: &'a str) -> Self {
+        self.message = Some(message);
+        self
+    }
+
+    /// Set the time since loading started, which drives the spinner animation
+    #[must_use]
+    pub fn elapsed(mut self, elapsed: Duration) -> Self {
+        self.elapsed = elapsed;
+        self
+    }
+
+    /// Set how strongly the underlying content is dimmed (0.0 to 1.0)
+    #[must_use]
+    pub fn dim_factor(mut self, factor: f64) -> Self {
+        self.dim_factor = factor;
+        self
+    }
+
+    /// Set the style of the spinner and message
+    #[must_use]
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+impl Widget for LoadingOverlay<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        dim_area_by(area, buf, self.dim_factor);
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        let center_x = area.x + area.width / 2;
+        let center_y = area.y + area.height / 2;
+        buf[(center_x, center_y)]
+            .set_char(spinner_frame(self.elapsed))
+            .set_style(self.style);
+
+        if let Some(message) = self.message
+            && center_y + 1 < area.bottom()
+        {
+            let row = Rect::new(area.x, center_y + 1, area.width, 1);
+            Line::styled(message, self.style)
+                .alignment(Alignment::Center)
+                .render(row, buf);
+        }
+    }
+}
+
+impl Default for LoadingOverlay<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Glyphs the leading edge of a [`ProgressRing`] pulses through, ten per second
+const RING_EDGE_FRAMES: [char; 4] = ['▒', '▓', '█', '▓'];
+
+/// ASCII stand-ins for [`RING_EDGE_FRAMES`]
+const ASCII_RING_EDGE_FRAMES: [char; 4] = ['o', 'O', '@', 'O'];
+
+/// A spinner for work with known progress: a ring of cells that fills clockwise
+///
+/// The ring runs around the edge of a small box, starting from the middle of its top
+/// row, and each cell is one segment. The share of segments given by the ratio are
+/// solid, and the next one pulses with the animation phase set by
+/// [`elapsed`](Self::elapsed), so the ring keeps moving while progress stalls. The
+/// percentage is shown inside the ring when it fits.
+#[derive(Debug, Clone)]
+pub struct ProgressRing {
+    ratio: f64,
+    elapsed: Duration,
+    size: (u16, u16),
+    label: bool,
+    status: StatusColor,
+    style: Style,
+}
+
+impl ProgressRing {
+    /// Create a ring for a ratio between 0.0 and 1.0, 5 columns by 3 rows in size
+    #[must_use]
+    pub fn new(ratio: f64) -> Self {
+        Self {
+            ratio: if ratio.is_nan() {
+                0.0
+            } else {
+                ratio.clamp(0.0, 1.0)
+            },
+            elapsed: Duration::ZERO,
+            size: (5, 3),
+            label: true,
+            status: StatusColor::Info,
+            style: Style::default(),
+        }
+    }
+
+    /// Set the time since the work started, which drives the leading edge animation
+    #[must_use]
+    pub fn elapsed(mut self, elapsed: Duration) -> Self {
+        self.elapsed = elapsed;
+        self
+    }
+
+    /// Set the size of the box the ring runs around, at least 3 by 3
+    #[must_use]
+    pub fn size(mut self, width: u16, height: u16) -> Self {
+        self.size = (width.max(3), height.max(3));
+        self
+    }
+
+    /// Set whether the percentage is shown inside the ring
+    #[must_use]
+    pub fn label(mut self, label: bool) -> Self {
+        self.label = label;
+        self
+    }
+
+    /// Set the status color of the solid segments
+    #[must_use]
+    pub fn status(mut self, status: StatusColor) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Set the style of the ring and label
+    #[must_use]
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Offsets of the ring segments from the top-left corner, in clockwise order
+    fn segments(&self) -> Vec<(u16, u16)> {
+        let (width, height) = self.size;
+        let (right, bottom) = (width - 1, height - 1);
+        let top = (0..width).map(|x| (x, 0));
+        let side = (1..bottom).map(|y| (right, y));
+        let base = (0..width).rev().map(|x| (x, bottom));
+        let other_side = (1..bottom).rev().map(|y| (0, y));
+        let mut cells: Vec<_> = top.chain(side).chain(base).chain(other_side).collect();
+        cells.rotate_left(usize::from(width / 2));
+        cells
+    }
+
+    /// Number of solid segments out of `count`
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    fn filled(&self, count: usize) -> usize {
+        ((self.ratio * count as f64).floor() as usize).min(count)
+    }
+}
+
+impl Widget for ProgressRing {
+    /// We suppress these Clippy warnings because the percentage is clamped to `0..=100`
+    /// before the conversion.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = area.intersection(buf.area);
+        let charset = CharsetProfile::current();
+        let segments = self.segments();
+        let filled = self.filled(segments.len());
+        let edge_frames = charset.pick(&RING_EDGE_FRAMES, &ASCII_RING_EDGE_FRAMES);
+        let frame = (self.elapsed.as_millis() / 100) as usize % edge_frames.len();
+
+        let fill_style = self.style.fg(self.status.into());
+        let empty_style = self.style.fg(Color::DarkGray);
+        for (index, &(dx, dy)) in segments.iter().enumerate() {
+            let position = Position::new(area.x + dx, area.y + dy);
+            if !area.contains(position) {
+                continue;
+            }
+            let (glyph, style) = match index.cmp(&filled) {
+                std::cmp::Ordering::Less => (charset.pick('█', '#'), fill_style),
+                std::cmp::Ordering::Equal => (edge_frames[frame], fill_style),
+                std::cmp::Ordering::Greater => (charset.pick('░', '.'), empty_style),
+            };
+            buf[position].set_char(glyph).set_style(style);
+        }
+
+        let (width, height) = self.size;
+        let inner = Rect::new(area.x + 1, area.y + 1, width - 2, height - 2).intersection(area);
+        if self.label && !inner.is_empty() {
+            let label = format!("{}%", (self.ratio * 100.0).round().clamp(0.0, 100.0) as u8);
+            if text_width(&label) <= inner.width {
+                let row = Rect::new(inner.x, inner.y + inner.height / 2, inner.width, 1);
+                Line::styled(label, self.style)
+                    .alignment(Alignment::Center)
+                    .render(row, buf);
+            }
+        }
+    }
+}
+
+impl MinSize for ProgressRing {
+    /// The whole ring; the label is left out when it doesn't fit inside
+    fn min_size(&self) -> (u16, u16) {
+        self.size
+    }
+}
+
+/// A centered dialog drawn over a dimmed background, with a row of buttons
+#[derive(Debug, Clone)]
+pub struct Modal<'a> {
+    title: &'a str,
+    body: Vec<Line<'a>>,
+    buttons: Vec<&'a str>,
+    selected_button: usize,
+    style: Style,
+    border_style: Style,
+    button_style: Style,
+}
+
+impl<'a> Modal<'a> {
+    /// Create a new modal with a title and no body or buttons
+    #[must_use]
+    pub fn new(title: &'a str) -> Self {
+        Self {
+            title,
+            body: Vec::new(),
+            buttons: Vec::new(),
+            selected_button: 0,
+            style: Style::default(),
+            border_style: Style::default(),
+            button_style: Style::default().add_modifier(Modifier::REVERSED),
+        }
+    }
+
+    /// Set the body lines of the modal
+    #[must_use]
+    pub fn body(mut self, body: Vec<Line<'a>>) -> Self {
+        self.body = body;
+        self
+    }
+
+    /// Set the button labels, selecting the first one
+    #[must_use]
+    pub fn buttons(mut self, buttons: Vec<&'a str>) -> Self {
+        self.buttons = buttons;
+        self.selected_button = 0;
+        self
+    }
+
+    /// Set the style of the modal content
+    #[must_use]
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the style of the modal border
+    #[must_use]
+    pub fn border_style(mut self, style: Style) -> Self {
+        self.border_style = style;
+        self
+    }
+
+    /// Set the style used to highlight the selected button
+    #[must_use]
+    pub fn button_style(mut self, style: Style) -> Self {
+        self.button_style = style;
+        self
+    }
+
+    /// Select the next button, wrapping to the first
+    pub fn select_next_button(&mut self) {
+        if !self.buttons.is_empty() {
+            self.selected_button = (self.selected_button + 1) % self.buttons.len();
+        }
+    }
+
+    /// Select the previous button, wrapping to the last
+    pub fn select_prev_button(&mut self) {
+        if !self.buttons.is_empty() {
+            self.selected_button = self
+                .selected_button
+                .checked_sub(1)
+                .unwrap_or(self.buttons.len() - 1);
+        }
+    }
+
+    /// Get the index of the selected button
+    #[must_use]
+    pub fn selected_button_index(&self) -> Option<usize> {
+        (!self.buttons.is_empty()).then_some(self.selected_button)
+    }
+
+    /// Get the label of the selected button
+    #[must_use]
+    pub fn selected_button(&self) -> Option<&'a str> {
+        self.buttons.get(self.selected_button).copied()
+    }
+
+    /// Build the button row with one span per label
+    fn button_line(&self) -> Line<'a> {
+        let mut spans = Vec::with_capacity(self.buttons.len() * 2);
+        for (index, label) in self.buttons.iter().enumerate() {
+            if index > 0 {
+                spans.push(Span::raw("  "));
+            }
+            let style = if index == self.selected_button {
+                self.button_style
+            } else {
+                self.style
+            };
+            spans.push(Span::styled(format!("[ {label} ]"), style));
+        }
+        Line::from(spans).alignment(Alignment::Center)
+    }
+}
+
+impl Widget for &Modal<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        dim_area(area, buf);
+
+        let buttons = self.button_line();
+        let content_width = self
+            .body
+            .iter()
+            .map(Line::width)
+            .chain([buttons.width(), Span::raw(self.title).width()])
+            .max()
+            .unwrap_or(0);
+        let button_rows = if self.buttons.is_empty() { 0 } else { 2 };
+        let width = u16::try_from(content_width + 4).unwrap_or(u16::MAX);
+        let height = u16::try_from(self.body.len() + button_rows + 2).unwrap_or(u16::MAX);
+        let dialog = centered_rect_with_size(width, height, area);
+
+        Clear.render(dialog, buf);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_set(CharsetProfile::current().border_set())
+            .border_style(self.border_style)
+            .title(self.title)
+            .style(self.style);
+        let inner = block.inner(dialog).inner(Margin::new(1, 0));
+        block.render(dialog, buf);
+
+        Paragraph::new(Text::from(self.body.clone()))
+            .style(self.style)
+            .render(inner, buf);
+
+        if !self.buttons.is_empty() && inner.height > 0 {
+            let row = Rect {
+                y: inner.bottom() - 1,
+                height: 1,
+                ..inner
+            };
+            buttons.render(row, buf);
+        }
+    }
+}
+
+impl Widget for Modal<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        (&self).render(area, buf);
+    }
+}
+
+/// A short-lived notification shown by a [`ToastStack`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Toast {
+    message: String,
+    status: StatusColor,
+    created: Instant,
+    count: u32,
+}
+
+impl Toast {
+    /// Get the message text
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Get the status that colors the toast
+    #[must_use]
+    pub fn status(&self) -> StatusColor {
+        self.status
+    }
+
+    /// Get how many times the message was pushed, counting coalesced repeats
+    #[must_use]
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Text shown in the toast, with a `(xN)` suffix for repeated messages
+    fn label(&self) -> Cow<'_, str> {
+        if self.count > 1 {
+            Cow::Owned(format!("{} (x{})", self.message, self.count))
+        } else {
+            Cow::Borrowed(&self.message)
+        }
+    }
+}
+
+/// What a [`ToastStack`] does with a toast pushed while it is full
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ToastOverflow {
+    /// Drop the oldest visible toast to make room
+    #[default]
+    DropOldest,
+    /// Hold the toast back until a visible one expires
+    Queue,
+}
+
+/// A stack of toasts drawn in the top-right corner, newest first
+///
+/// Toasts disappear once they outlive the stack's lifetime, which happens when
+/// [`expire`](Self::expire) is called. `run_app` does this every frame for a stack
+/// returned from [`TerminalApp::toasts`](crate::TerminalApp::toasts).
+///
+/// Pushing a message that matches a visible or queued toast within the dedup window
+/// bumps that toast's repeat counter instead of adding another, so a flood of the
+/// same error shows as one toast.
+#[derive(Debug, Clone)]
+pub struct ToastStack {
+    toasts: VecDeque<Toast>,
+    queued: VecDeque<Toast>,
+    lifetime: Duration,
+    max_toasts: usize,
+    dedup_window: Duration,
+    overflow: ToastOverflow,
+}
+
+impl ToastStack {
+    /// Create an empty stack showing up to five toasts for four seconds each
+    ///
+    /// Repeats within two seconds are coalesced, and overflow drops the oldest toast.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            toasts: VecDeque::new(),
+            queued: VecDeque::new(),
+            lifetime: Duration::from_secs(4),
+            max_toasts: 5,
+            dedup_window: Duration::from_secs(2),
+            overflow: ToastOverflow::DropOldest,
+        }
+    }
+
+    /// Set how long each toast stays visible
+    #[must_use]
+    pub fn lifetime(mut self, lifetime: Duration) -> Self {
+        self.lifetime = lifetime;
+        self
+    }
+
+    /// Set the maximum number of toasts visible at once
+    ///
+    /// Toasts over the limit are handled according to the [`ToastOverflow`] policy.
+    #[must_use]
+    pub fn max_toasts(mut self, max_toasts: usize) -> Self {
+        self.max_toasts = max_toasts.max(1);
+        let kept = self.max_toasts.min(self.toasts.len());
+        for toast in self.toasts.drain(kept..).rev() {
+            if self.overflow == ToastOverflow::Queue {
+                self.queued.push_front(toast);
+            }
+        }
+        self
+    }
+
+    /// Set how soon a repeated message must arrive to be coalesced, zero to disable
+    #[must_use]
+    pub fn dedup_window(mut self, window: Duration) -> Self {
+        self.dedup_window = window;
+        self
+    }
+
+    /// Set what happens to toasts pushed while the stack is full
+    #[must_use]
+    pub fn overflow(mut self, overflow: ToastOverflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Show a toast, timing its lifetime from now
+    pub fn push(&mut self, message: impl Into<String>, status: StatusColor) {
+        self.push_at(message, status, Instant::now());
+    }
+
+    /// Show a toast created at `now`
+    ///
+    /// A repeat of a visible toast within the dedup window moves it to the top with
+    /// its counter bumped and its lifetime restarted; a repeat of a queued toast only
+    /// bumps the counter.
+    pub fn push_at(&mut self, message: impl Into<String>, status: StatusColor, now: Instant) {
+        let message = message.into();
+        if !self.dedup_window.is_zero() {
+            let repeats = |toast: &Toast| {
+                toast.message == message
+                    && toast.status == status
+                    && now.saturating_duration_since(toast.created) < self.dedup_window
+            };
+            if let Some(index) = self.toasts.iter().position(repeats)
+                && let Some(mut toast) = self.toasts.remove(index)
+            {
+                toast.count = toast.count.saturating_add(1);
+                toast.created = now;
+                self.toasts.push_front(toast);
+                return;
+            }
+            if let Some(toast) = self.queued.iter_mut().find(|toast| repeats(toast)) {
+                toast.count = toast.count.saturating_add(1);
+                toast.created = now;
+                return;
+            }
+        }
+
+        let toast = Toast {
+            message,
+            status,
+            created: now,
+            count: 1,
+        };
+        if self.toasts.len() < self.max_toasts {
+            self.toasts.push_front(toast);
+            return;
+        }
+        match self.overflow {
+            ToastOverflow::DropOldest => {
+                self.toasts.push_front(toast);
+                self.toasts.truncate(self.max_toasts);
+            }
+            ToastOverflow::Queue => self.queued.push_back(toast),
+        }
+    }
+
+    /// Remove toasts that have outlived the lifetime at `now`
+    ///
+    /// Queued toasts then fill the freed places, with their lifetime starting at `now`.
+    /// Returns whether any toast was removed.
+    pub fn expire(&mut self, now: Instant) -> bool {
+        let lifetime = self.lifetime;
+        let before = self.toasts.len();
+        self.toasts
+            .retain(|toast| now.saturating_duration_since(toast.created) < lifetime);
+        let expired = self.toasts.len() < before;
+        while self.toasts.len() < self.max_toasts {
+            let Some(mut toast) = self.queued.pop_front() else {
+                break;
+            };
+            toast.created = now;
+            self.toasts.push_front(toast);
+        }
+        expired
+    }
+
+    /// Iterate over the visible toasts, newest first
+    pub fn iter(&self) -> impl Iterator<Item = &Toast> {
+        self.toasts.iter()
+    }
+
+    /// Number of visible toasts
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.toasts.len()
+    }
+
+    /// Check whether there are no visible toasts
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.toasts.is_empty()
+    }
+
+    /// Number of toasts waiting for a visible place
+    #[must_use]
+    pub fn queued(&self) -> usize {
+        self.queued.len()
+    }
+
+    /// Remove all toasts, including queued ones
+    pub fn clear(&mut self) {
+        self.toasts.clear();
+        self.queued.clear();
+    }
+}
+
+impl Widget for &ToastStack {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut y = area.y;
+        for toast in &self.toasts {
+            if y + 3 > area.bottom() {
+                break;
+            }
+            let label = toast.label();
+            let text_width = u16::try_from(label.width()).unwrap_or(u16::MAX);
+            let width = text_width.saturating_add(4).min(area.width);
+            let rect = Rect::new(area.right() - width, y, width, 3);
+
+            Clear.render(rect, buf);
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .border_set(CharsetProfile::current().border_set())
+                .border_style(Style::default().fg(toast.status.into()));
+            let inner = block.inner(rect).inner(Margin::new(1, 0));
+            block.render(rect, buf);
+            buf.set_stringn(
+                inner.x,
+                inner.y,
+                &label,
+                usize::from(inner.width),
+                Style::default(),
+            );
+            y += 3;
+        }
+    }
+}
+
+impl Widget for ToastStack {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        (&self).render(area, buf);
+    }
+}
+
+impl Default for ToastStack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Identifies an overlay pushed onto an [`OverlayStack`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OverlayId(u64);
+
+/// Draws into its area of the frame buffer
+type OverlayRender = Box<dyn Fn(Rect, &mut Buffer)>;
+
+/// An entry in an [`OverlayStack`]
+struct Overlay {
+    id: OverlayId,
+    z: i32,
+    area: Rect,
+    render: OverlayRender,
+}
+
+/// Overlays such as tooltips and popups drawn above the rest of the UI
+///
+/// Each overlay has an area and a closure that draws into it. Overlays are drawn
+/// from the lowest z-index to the highest, so higher ones cover lower ones, and
+/// overlays with the same z-index are drawn in the order they were pushed. `run_app`
+/// draws the stack returned from [`TerminalApp::overlays`](crate::TerminalApp::overlays)
+/// into the same frame, after [`TerminalApp::ui`](crate::TerminalApp::ui).
+#[derive(Default)]
+pub struct OverlayStack {
+    overlays: Vec<Overlay>,
+    next_id: u64,
+}
+
+impl OverlayStack {
+    /// Create an empty overlay stack
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an overlay drawn by `render` into `area` at the given z-index
+    pub fn push(
+        &mut self,
+        z: i32,
+        area: Rect,
+        render: impl Fn(Rect, &mut Buffer) + 'static,
+    ) -> OverlayId {
+        let id = OverlayId(self.next_id);
+        self.next_id += 1;
+        let index = self.overlays.partition_point(|overlay| overlay.z <= z);
+        self.overlays.insert(
+            index,
+            Overlay {
+                id,
+                z,
+                area,
+                render: Box::new(render),
+            },
+        );
+        id
+    }
+
+    /// Remove the topmost overlay, returning its id
+    pub fn pop(&mut self) -> Option<OverlayId> {
+        self.overlays.pop().map(|overlay| overlay.id)
+    }
+
+    /// Remove an overlay, returning whether it was in the stack
+    pub fn remove(&mut self, id: OverlayId) -> bool {
+        let before = self.overlays.len();
+        self.overlays.retain(|overlay| overlay.id != id);
+        self.overlays.len() != before
+    }
+
+    /// Move an overlay to a new area
+    pub fn set_area(&mut self, id: OverlayId, area: Rect) {
+        if let Some(overlay) = self.overlays.iter_mut().find(|overlay| overlay.id == id) {
+            overlay.area = area;
+        }
+    }
+
+    /// Remove every overlay
+    pub fn clear(&mut self) {
+        self.overlays.clear();
+    }
+
+    /// Number of overlays in the stack
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.overlays.len()
+    }
+
+    /// Check whether the stack has no overlays
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.overlays.is_empty()
+    }
+}
+
+impl std::fmt::Debug for OverlayStack {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let entries = self
+            .overlays
+            .iter()
+            .map(|overlay| (overlay.id, overlay.z, overlay.area));
+        f.debug_list().entries(entries).finish()
+    }
+}
+
+impl Widget for &OverlayStack {
+    /// Draw every overlay, clipped to `area`
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        for overlay in &self.overlays {
+            let clipped = overlay.area.intersection(area);
+            if !clipped.is_empty() {
+                (overlay.render)(clipped, buf);
+            }
+        }
+    }
+}
+
+/// Direction that [`Marquee`] text travels as its offset grows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MarqueeDirection {
+    #[default]
+    Left,
+    Right,
+}
+
+/// Single-line text that scrolls horizontally, wrapping around with a gap
+///
+/// The app advances the offset, typically from an [`AnimationTimer`](crate::AnimationTimer).
+/// Text that fits in the area is drawn without scrolling.
+#[derive(Debug, Clone)]
+pub struct Marquee<'a> {
+    text: &'a str,
+    offset: usize,
+    direction: MarqueeDirection,
+    separator: &'a str,
+    style: Style,
+}
+
+impl<'a> Marquee<'a> {
+    /// Create a marquee for the given text
+    #[must_use]
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            offset: 0,
+            direction: MarqueeDirection::Left,
+            separator: "   ",
+            style: Style::default(),
+        }
+    }
+
+    /// Set how many characters the text has scrolled
+    #[must_use]
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Set the direction the text travels
+    #[must_use]
+    pub fn direction(mut self, direction: MarqueeDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Set the gap shown between the end of the text and its next repetition
+    #[must_use]
+    pub fn separator(mut self, separator: &'a str) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Set the style of the text
+    #[must_use]
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+impl Widget for Marquee<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.is_empty() {
+            return;
+        }
+        let width = usize::from(area.width);
+        let text: Vec<char> = self.text.chars().collect();
+        if text.len() <= width {
+            buf.set_stringn(area.x, area.y, self.text, width, self.style);
+            return;
+        }
+
+        let cycle: Vec<char> = text.into_iter().chain(self.separator.chars()).collect();
+        let shift = self.offset % cycle.len();
+        let start = match self.direction {
+            MarqueeDirection::Left => shift,
+            MarqueeDirection::Right => (cycle.len() - shift) % cycle.len(),
+        };
+        let window: String = cycle.iter().cycle().skip(start).take(width).collect();
+        buf.set_stringn(area.x, area.y, window, width, self.style);
+    }
+}
+
+/// How a [`Banner`] colors its art
+#[derive(Debug, Clone)]
+enum BannerColors {
+    Plain,
+    Lines(Vec<Color>),
+    Gradient(Gradient),
+}
+
+/// Multi-line ASCII art centered in its area, such as [`GAEROS_ASCII`](crate::GAEROS_ASCII)
+///
+/// Blank lines around the art are ignored and the widest line sets its width. Art
+/// larger than the area is clipped evenly on both sides, keeping its middle visible.
+/// Spaces in the art are transparent, leaving whatever is underneath.
+#[derive(Debug, Clone)]
+pub struct Banner<'a> {
+    art: &'a str,
+    style: Style,
+    colors: BannerColors,
+}
+
+impl<'a> Banner<'a> {
+    /// Create a banner showing the given art
+    #[must_use]
+    pub fn new(art: &'a str) -> Self {
+        Self {
+            art,
+            style: Style::default(),
+            colors: BannerColors::Plain,
+        }
+    }
+
+    /// Set the style of the art
+    #[must_use]
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Color each line of the art in turn, cycling through `colors`
+    #[must_use]
+    pub fn line_colors(mut self, colors: Vec<Color>) -> Self {
+        self.colors = if colors.is_empty() {
+            BannerColors::Plain
+        } else {
+            BannerColors::Lines(colors)
+        };
+        self
+    }
+
+    /// Color the art with a gradient running from its left edge to its right edge
+    #[must_use]
+    pub fn gradient(mut self, colors: &[Color]) -> Self {
+        self.colors = if colors.is_empty() {
+            BannerColors::Plain
+        } else {
+            BannerColors::Gradient(Gradient::evenly(colors))
+        };
+        self
+    }
+
+    /// The lines of the art without the blank lines around it
+    #[must_use]
+    pub fn lines(&self) -> Vec<&'a str> {
+        let lines: Vec<&str> = self.art.lines().collect();
+        let is_blank = |line: &&str| line.trim().is_empty();
+        let start = lines.iter().position(|line| !is_blank(line));
+        let end = lines.iter().rposition(|line| !is_blank(line));
+        match (start, end) {
+            (Some(start), Some(end)) => lines[start..=end].to_vec(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Width and height of the art's bounding box
+    #[must_use]
+    pub fn size(&self) -> (usize, usize) {
+        let lines = self.lines();
+        let width = lines.iter().map(|line| line.width()).max().unwrap_or(0);
+        (width, lines.len())
+    }
+
+    /// Color of the art at the given line and column
+    fn color_at(&self, line: usize, column: usize, width: usize) -> Option<Color> {
+        match &self.colors {
+            BannerColors::Plain => None,
+            BannerColors::Lines(colors) => Some(colors[line % colors.len()]),
+            #[allow(clippy::cast_precision_loss)]
+            BannerColors::Gradient(gradient) => {
+                Some(gradient.sample(column as f64 / width.saturating_sub(1).max(1) as f64))
+            }
+        }
+    }
+}
+
+impl Widget for Banner<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = area.intersection(buf.area);
+        if area.is_empty() {
+            return;
+        }
+        let lines = self.lines();
+        let (width, height) = self.size();
+        let (area_width, area_height) = (usize::from(area.width), usize::from(area.height));
+
+        // Either pad the art to the middle of the area or skip its overflowing edges
+        let (pad_x, skip_x) = if width <= area_width {
+            ((area_width - width) / 2, 0)
+        } else {
+            (0, (width - area_width) / 2)
+        };
+        let (pad_y, skip_y) = if height <= area_height {
+            ((area_height - height) / 2, 0)
+        } else {
+            (0, (height - area_height) / 2)
+        };
+
+        for (row, line) in lines.iter().enumerate().skip(skip_y).take(area_height) {
+            let y = area.y + u16::try_from(pad_y + row - skip_y).unwrap_or(u16::MAX);
+            let mut column = 0;
+            for grapheme in line.graphemes(true) {
+                let start = column;
+                column += grapheme.width();
+                if start < skip_x || grapheme.trim().is_empty() {
+                    continue;
+                }
+                let offset = pad_x + start - skip_x;
+                if offset + grapheme.width() > area_width {
+                    break;
+                }
+                let x = area.x + u16::try_from(offset).unwrap_or(u16::MAX);
+                let mut style = self.style;
+                if let Some(color) = self.color_at(row, start, width) {
+                    style = style.fg(color);
+                }
+                buf[(x, y)].set_symbol(grapheme).set_style(style);
+            }
+        }
+    }
+}
+
+/// Number of characters a typewriter typing `chars_per_second` has typed after `elapsed`
+#[must_use]
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn chars_at(elapsed: Duration, chars_per_second: f64) -> usize {
+    let typed = (elapsed.as_secs_f64() * chars_per_second).floor();
+    if typed.is_finite() && typed > 0.0 {
+        typed as usize
+    } else {
+        0
+    }
+}
+
+/// Text revealed a character at a time, as if being typed
+///
+/// The app sets how much is revealed, either directly with
+/// [`revealed`](Self::revealed) or from the time since typing started with
+/// [`elapsed`](Self::elapsed). A cursor follows the revealed text, blinking when the
+/// elapsed time is known.
+#[derive(Debug, Clone)]
+pub struct Typewriter<'a> {
+    text: &'a str,
+    revealed: usize,
+    chars_per_second: f64,
+    elapsed: Option<Duration>,
+    cursor: Option<char>,
+    style: Style,
+    cursor_style: Style,
+}
+
+impl<'a> Typewriter<'a> {
+    /// Create a typewriter for the given text with nothing revealed yet
+    #[must_use]
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            revealed: 0,
+            chars_per_second: 20.0,
+            elapsed: None,
+            cursor: Some(CharsetProfile::current().pick('▌', '_')),
+            style: Style::default(),
+            cursor_style: Style::default(),
+        }
+    }
+
+    /// Set how many characters are revealed, up to the length of the text
+    ///
+    /// Has no effect once [`elapsed`](Self::elapsed) is set, which reveals by time instead.
+    #[must_use]
+    pub fn revealed(mut self, revealed: usize) -> Self {
+        self.revealed = revealed;
+        self
+    }
+
+    /// Set the typing speed used by [`elapsed`](Self::elapsed)
+    #[must_use]
+    pub fn chars_per_second(mut self, chars_per_second: f64) -> Self {
+        self.chars_per_second = chars_per_second;
+        self
+    }
+
+    /// Reveal as much as has been typed `elapsed` after typing started
+    ///
+    /// The time also drives the cursor's blinking.
+    #[must_use]
+    pub fn elapsed(mut self, elapsed: Duration) -> Self {
+        self.elapsed = Some(elapsed);
+        self
+    }
+
+    /// Set the cursor shown after the revealed text, or hide it with `None`
+    #[must_use]
+    pub fn cursor(mut self, cursor: Option<char>) -> Self {
+        self.cursor = cursor;
+        self
+    }
+
+    /// Set the style of the text
+    #[must_use]
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the style of the cursor
+    #[must_use]
+    pub fn cursor_style(mut self, style: Style) -> Self {
+        self.cursor_style = style;
+        self
+    }
+
+    /// How many characters are revealed, from the elapsed time if it is set
+    fn revealed_count(&self) -> usize {
+        let revealed = self.elapsed.map_or(self.revealed, |elapsed| {
+            chars_at(elapsed, self.chars_per_second)
+        });
+        revealed.min(self.text.chars().count())
+    }
+
+    /// The revealed part of the text
+    #[must_use]
+    pub fn visible_text(&self) -> &'a str {
+        let end = self
+            .text
+            .char_indices()
+            .nth(self.revealed_count())
+            .map_or(self.text.len(), |(index, _)| index);
+        &self.text[..end]
+    }
+
+    /// Check whether the whole text has been revealed
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.revealed_count() >= self.text.chars().count()
+    }
+
+    /// Check whether the cursor is in the visible half of its blink
+    ///
+    /// The cursor stays solid while text is still being typed.
+    fn cursor_visible(&self) -> bool {
+        match self.elapsed {
+            Some(elapsed) if self.is_finished() => elapsed.as_millis() / 500 % 2 == 0,
+            _ => true,
+        }
+    }
+}
+
+impl Widget for Typewriter<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut text = Text::styled(self.visible_text(), self.style);
+        if let Some(cursor) = self.cursor.filter(|_| self.cursor_visible()) {
+            let cursor = Span::styled(cursor.to_string(), self.cursor_style);
+            match text.lines.last_mut() {
+                // A trailing newline leaves the cursor at the start of a new line
+                Some(line) if !self.visible_text().ends_with('\n') => line.push_span(cursor),
+                _ => text.push_line(Line::from(cursor)),
+            }
+        }
+        Paragraph::new(text).render(area, buf);
+    }
+}
+
+/// A single-row bar with groups of segments on the left, center, and right
+///
+/// The left and right groups hug their edges and the center group sits in the
+/// middle of the row, moved aside if it would touch either of them. When space runs
+/// short the center is cut down first, then dropped entirely, and only then is the
+/// right group cut, keeping the left group whole the longest.
+#[derive(Debug, Clone, Default)]
+pub struct StatusBar<'a> {
+    left: Vec<Span<'a>>,
+    center: Vec<Span<'a>>,
+    right: Vec<Span<'a>>,
+    style: Style,
+}
+
+impl<'a> StatusBar<'a> {
+    /// Create an empty status bar
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the segments on the left
+    #[must_use]
+    pub fn left(mut self, segments: Vec<Span<'a>>) -> Self {
+        self.left = segments;
+        self
+    }
+
+    /// Set the segments in the center
+    #[must_use]
+    pub fn center(mut self, segments: Vec<Span<'a>>) -> Self {
+        self.center = segments;
+        self
+    }
+
+    /// Set the segments on the right
+    #[must_use]
+    pub fn right(mut self, segments: Vec<Span<'a>>) -> Self {
+        self.right = segments;
+        self
+    }
+
+    /// Set the style of the bar, which also fills the gaps between groups
+    #[must_use]
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Work out where each group goes in a row `width` columns wide
+    ///
+    /// Returns the `(x, width)` of the left, center, and right groups in that order,
+    /// with a width of zero for a group that is dropped. Groups are kept a column
+    /// apart.
+    #[must_use]
+    pub fn layout(&self, width: u16) -> [(u16, u16); 3] {
+        let group_width = |spans: &[Span]| {
+            spans
+                .iter()
+                .map(|span| text_width(&span.content))
+                .fold(0, u16::saturating_add)
+        };
+        let gap = |before: u16, after: u16| u16::from(before > 0 && after > 0);
+
+        let left = group_width(&self.left).min(width);
+        let right_space = width.saturating_sub(left + gap(left, 1));
+        let right = group_width(&self.right).min(right_space);
+        let right_x = width - right;
+
+        // The center takes what is left between the two, a column from each
+        let from = left + gap(left, 1);
+        let to = right_x.saturating_sub(gap(right, 1));
+        let space = to.saturating_sub(from);
+        let wanted = group_width(&self.center);
+        let center = if space >= wanted || space >= 2 {
+            wanted.min(space)
+        } else {
+            0
+        };
+        let centered = width.saturating_sub(center) / 2;
+        let center_x = centered.clamp(from, to.saturating_sub(center).max(from));
+        [(0, left), (center_x, center), (right_x, right)]
+    }
+}
+
+impl Widget for StatusBar<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = area.intersection(buf.area);
+        if area.is_empty() {
+            return;
+        }
+        let row = Rect::new(area.x, area.y, area.width, 1);
+        buf.set_style(row, self.style);
+
+        let groups = [&self.left, &self.center, &self.right];
+        for (spans, (x, width)) in groups.into_iter().zip(self.layout(area.width)) {
+            if width == 0 {
+                continue;
+            }
+            let line = Line::from(spans.clone()).style(self.style);
+            buf.set_line(row.x + x, row.y, &line, width);
+            if line.width() > usize::from(width) {
+                let ellipsis = CharsetProfile::current().pick("…", ".");
+                buf[(row.x + x + width - 1, row.y)].set_symbol(ellipsis);
+            }
+        }
+    }
+}
+
+/// A bordered panel that presents an error and the chain of errors that caused it
+///
+/// The top-level message is shown first, followed by each source error indented one
+/// level deeper than the last. A dismiss hint sits on the bottom border.
+#[derive(Debug, Clone, Copy)]
+pub struct ErrorView<'a> {
+    error: &'a dyn std::error::Error,
+    title: &'a str,
+    dismiss_hint: Option<&'a str>,
+}
+
+impl<'a> ErrorView<'a> {
+    /// Create a view of an error
+    ///
+    /// An [`anyhow::Error`] can be shown by passing `error.as_ref()`.
+    #[must_use]
+    pub fn new(error: &'a dyn std::error::Error) -> Self {
+        Self {
+            error,
+            title: "Error",
+            dismiss_hint: Some("Esc to dismiss"),
+        }
+    }
+
+    /// Set the title shown on the border
+    #[must_use]
+    pub fn title(mut self, title: &'a str) -> Self {
+        self.title = title;
+        self
+    }
+
+    /// Set the hint on the bottom border, or hide it with `None`
+    #[must_use]
+    pub fn dismiss_hint(mut self, hint: Option<&'a str>) -> Self {
+        self.dismiss_hint = hint;
+        self
+    }
+
+    /// Build the message line followed by one indented line per source error
+    #[must_use]
+    pub fn lines(&self) -> Vec<Line<'static>> {
+        let mut lines = vec![Line::styled(
+            self.error.to_string(),
+            Style::default().add_modifier(Modifier::BOLD),
+        )];
+        let mut source = self.error.source();
+        let mut depth = 0;
+        while let Some(error) = source {
+            if depth == 0 {
+                lines.push(Line::styled("Caused by:", Style::default().fg(Color::Gray)));
+            }
+            let branch = CharsetProfile::current().pick("└", "`-");
+            lines.push(Line::raw(format!("{}{branch} {error}", "  ".repeat(depth))));
+            source = error.source();
+            depth += 1;
+        }
+        lines
+    }
+}
+
+impl Widget for ErrorView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut card = Card::new()
+            .title(self.title)
+            .border_style(Style::default().fg(StatusColor::Error.into()))
+            .content(self.lines());
+        if let Some(hint) = self.dismiss_hint {
+            card = card.bottom_right(hint);
+        }
+        card.render(area, buf);
+    }
+}
+
+/// Format a duration as `MM:SS`, or `HH:MM:SS` once it reaches an hour
+///
+/// With `millis` set, milliseconds are appended as `.mmm`. Partial units are truncated
+/// rather than rounded, so a countdown never shows a second it has not reached.
+#[must_use]
+pub fn format_duration(duration: Duration, millis: bool) -> String {
+    let total = duration.as_secs();
+    let (hours, minutes, seconds) = (total / 3600, total / 60 % 60, total % 60);
+    let mut text = if hours > 0 {
+        format!("{hours:02}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes:02}:{seconds:02}")
+    };
+    if millis {
+        text = format!("{text}.{:03}", duration.subsec_millis());
+    }
+    text
+}
+
+/// A widget that shows a duration as a clock, such as a stopwatch or countdown
+///
+/// Pass [`AnimationTimer::elapsed`](crate::AnimationTimer::elapsed) for a stopwatch or
+/// [`CountdownTimer::remaining`](crate::CountdownTimer::remaining) for a countdown.
+#[derive(Debug, Clone, Copy)]
+pub struct TimerDisplay {
+    duration: Duration,
+    millis: bool,
+    status: StatusColor,
+    warning_threshold: Option<Duration>,
+    warning_status: StatusColor,
+    style: Style,
+    theme: Theme,
+}
+
+impl TimerDisplay {
+    /// Create a display for the given duration
+    #[must_use]
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            millis: false,
+            status: StatusColor::Neutral,
+            warning_threshold: None,
+            warning_status: StatusColor::Warning,
+            style: Style::default(),
+            theme: Theme::default(),
+        }
+    }
+
+    /// Show milliseconds after the seconds
+    #[must_use]
+    pub fn millis(mut self, millis: bool) -> Self {
+        self.millis = millis;
+        self
+    }
+
+    /// Set the status color used above the warning threshold
+    #[must_use]
+    pub fn status(mut self, status: StatusColor) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Switch to the warning status once the duration drops below `threshold`
+    #[must_use]
+    pub fn warning_threshold(mut self, threshold: Duration) -> Self {
+        self.warning_threshold = Some(threshold);
+        self
+    }
+
+    /// Set the status color used below the warning threshold
+    #[must_use]
+    pub fn warning_status(mut self, status: StatusColor) -> Self {
+        self.warning_status = status;
+        self
+    }
+
+    /// Pick the status color from thresholds on the duration in seconds
+    ///
+    /// Replaces any status and warning threshold set before. Use descending
+    /// thresholds for a countdown that turns red as it runs out.
+    #[must_use]
+    pub fn thresholds(mut self, thresholds: &Thresholds) -> Self {
+        self.status = thresholds.color_for(self.duration.as_secs_f64());
+        self.warning_threshold = None;
+        self
+    }
+
+    /// Set the base style of the display
+    #[must_use]
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Take the status colors from a theme
+    #[must_use]
+    pub fn themed(mut self, theme: &Theme) -> Self {
+        self.theme = *theme;
+        self
+    }
+
+    /// Get the formatted time
+    #[must_use]
+    pub fn text(&self) -> String {
+        format_duration(self.duration, self.millis)
+    }
+
+    /// Get the status color for the current duration
+    #[must_use]
+    pub fn current_status(&self) -> StatusColor {
+        match self.warning_threshold {
+            Some(threshold) if self.duration < threshold => self.warning_status,
+            _ => self.status,
+        }
+    }
+}
+
+impl Widget for TimerDisplay {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+        let style = self
+            .style
+            .fg(self.theme.status_color(self.current_status()));
+        buf.set_stringn(area.x, area.y, self.text(), usize::from(area.width), style);
+    }
+}
+
+/// How a text widget draws its cursor into the buffer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CursorShape {
+    /// Reverse the colors of the whole cursor cell
+    #[default]
+    Block,
+    /// Underline the cursor cell
+    Underline,
+    /// Draw a thin vertical bar in an empty cursor cell
+    ///
+    /// Over a character the cell keeps its symbol and is reversed like [`Block`](Self::Block),
+    /// so the text under the cursor stays readable.
+    Bar,
+}
+
+impl CursorShape {
+    /// Draw the cursor into a cell
+    fn apply(self, cell: &mut ratatui::buffer::Cell) {
+        match self {
+            Self::Underline => {
+                cell.modifier.insert(Modifier::UNDERLINED);
+            }
+            Self::Bar if cell.symbol().trim().is_empty() => {
+                cell.set_char(CharsetProfile::current().pick('▏', '|'));
+            }
+            Self::Block | Self::Bar => {
+                cell.modifier.insert(Modifier::REVERSED);
+            }
+        }
+    }
+}
+
+/// A single-line text field in a bordered box
+///
+/// The cursor moves by grapheme cluster, so accented letters and emoji are treated as
+/// single characters. Long values scroll horizontally to keep the cursor in view.
+#[derive(Debug, Clone)]
+pub struct TextInput<'a> {
+    value: String,
+    cursor: usize,
+    scroll: Cell<usize>,
+    history: InputHistory,
+    title: Option<&'a str>,
+    style: Style,
+    border_style: Style,
+    cursor_style: Option<CursorShape>,
+}
+
+impl<'a> TextInput<'a> {
+    /// Create an empty text input
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            value: String::new(),
+            cursor: 0,
+            scroll: Cell::new(0),
+            history: InputHistory::new(),
+            title: None,
+            style: Style::default(),
+            border_style: Style::default(),
+            cursor_style: None,
+        }
+    }
+
+    /// Set the title shown on the border
+    #[must_use]
+    pub fn title(mut self, title: &'a str) -> Self {
+        self.title = Some(title);
+        self
+    }
+
+    /// Set the style of the text
+    #[must_use]
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the style of the border
+    #[must_use]
+    pub fn border_style(mut self, style: Style) -> Self {
+        self.border_style = style;
+        self
+    }
+
+    /// Draw the cursor into the buffer with the given shape
+    ///
+    /// By default the cursor is left to the terminal, placed by
+    /// [`render_with_cursor`](Self::render_with_cursor). With a shape set, the widget
+    /// draws its own cursor and leaves the terminal cursor hidden.
+    #[must_use]
+    pub fn cursor_style(mut self, shape: CursorShape) -> Self {
+        self.cursor_style = Some(shape);
+        self
+    }
+
+    /// Set the history used for Up/Down recall
+    #[must_use]
+    pub fn history(mut self, history: InputHistory) -> Self {
+        self.history = history;
+        self
+    }
+
+    /// Get the current value
+    #[must_use]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Replace the value and move the cursor to the end
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        self.value = value.into();
+        self.cursor = self.value.len();
+    }
+
+    /// Get the cursor position as a byte index into the value
+    #[must_use]
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Insert a character at the cursor
+    pub fn insert_char(&mut self, c: char) {
+        self.value.insert(self.cursor, c);
+        self.cursor += c.len_utf8();
+    }
+
+    /// Delete the grapheme before the cursor
+    pub fn delete_backward(&mut self) {
+        let start = self.prev_boundary();
+        self.value.replace_range(start..self.cursor, "");
+        self.cursor = start;
+    }
+
+    /// Delete the grapheme after the cursor
+    pub fn delete_forward(&mut self) {
+        let end = self.next_boundary();
+        self.value.replace_range(self.cursor..end, "");
+    }
+
+    /// Move the cursor one grapheme to the left
+    pub fn move_cursor_left(&mut self) {
+        self.cursor = self.prev_boundary();
+    }
+
+    /// Move the cursor one grapheme to the right
+    pub fn move_cursor_right(&mut self) {
+        self.cursor = self.next_boundary();
+    }
+
+    /// Move the cursor to the start of the value
+    pub fn move_cursor_home(&mut self) {
+        self.cursor = 0;
+    }
+
+    /// Move the cursor to the end of the value
+    pub fn move_cursor_end(&mut self) {
+        self.cursor = self.value.len();
+    }
+
+    /// Take the value, recording it in the history and leaving the input empty
+    pub fn submit(&mut self) -> String {
+        let value = std::mem::take(&mut self.value);
+        self.cursor = 0;
+        self.history.push_history(value.clone());
+        value
+    }
+
+    /// Record a value in the history without submitting the input
+    pub fn push_history(&mut self, value: impl Into<String>) {
+        self.history.push_history(value);
+    }
+
+    /// Apply the common editing keys, returning whether the key was used
+    ///
+    /// Handles printable characters, Backspace, Delete, Left, Right, Home, End, and
+    /// Up/Down for history recall. Enter is left to the caller, usually to call
+    /// [`submit`](Self::submit).
+    pub fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        if key.kind == KeyEventKind::Release {
+            return false;
+        }
+        match key.code {
+            KeyCode::Char(c)
+                if !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                self.insert_char(c);
+            }
+            KeyCode::Backspace => self.delete_backward(),
+            KeyCode::Delete => self.delete_forward(),
+            KeyCode::Left => self.move_cursor_left(),
+            KeyCode::Right => self.move_cursor_right(),
+            KeyCode::Home => self.move_cursor_home(),
+            KeyCode::End => self.move_cursor_end(),
+            KeyCode::Up | KeyCode::Down => {
+                let Some(recalled) = self.history.handle_key(key, &self.value) else {
+                    return false;
+                };
+                self.set_value(recalled);
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Get where the terminal cursor belongs after rendering into `area`
+    #[must_use]
+    pub fn cursor_position(&self, area: Rect) -> Option<Position> {
+        let inner = self.block().inner(area);
+        if inner.is_empty() {
+            return None;
+        }
+        let column = self.value[..self.cursor]
+            .width()
+            .saturating_sub(self.scroll.get());
+        let column = u16::try_from(column).unwrap_or(u16::MAX);
+        Some(Position::new(
+            inner.x + column.min(inner.width - 1),
+            inner.y,
+        ))
+    }
+
+    /// Render the input and place the terminal cursor at its cursor
+    ///
+    /// The terminal cursor is not placed when a [`cursor_style`](Self::cursor_style)
+    /// is set, since the widget draws its own.
+    pub fn render_with_cursor(&self, frame: &mut Frame, area: Rect) {
+        frame.render_widget(self, area);
+        if self.cursor_style.is_some() {
+            return;
+        }
+        if let Some(position) = self.cursor_position(area) {
+            frame.set_cursor_position(position);
+        }
+    }
+
+    fn block(&self) -> Block<'a> {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_set(CharsetProfile::current().border_set())
+            .border_style(self.border_style);
+        match self.title {
+            Some(title) => block.title(title),
+            None => block,
+        }
+    }
+
+    fn prev_boundary(&self) -> usize {
+        self.value[..self.cursor]
+            .grapheme_indices(true)
+            .next_back()
+            .map_or(0, |(index, _)| index)
+    }
+
+    fn next_boundary(&self) -> usize {
+        self.value[self.cursor..]
+            .graphemes(true)
+            .next()
+            .map_or(self.cursor, |grapheme| self.cursor + grapheme.len())
+    }
+
+    /// Adjust the scroll offset so the cursor column is visible in `width` columns
+    fn scroll_to_cursor(&self, width: usize) {
+        let cursor_column = self.value[..self.cursor].width();
+        // Leave room for the cursor after the last character
+        let max_scroll = (self.value.width() + 1).saturating_sub(width);
+        let mut scroll = self.scroll.get().min(max_scroll);
+        if cursor_column < scroll {
+            scroll = cursor_column;
+        } else if cursor_column >= scroll + width {
+            scroll = cursor_column + 1 - width;
+        }
+        self.scroll.set(scroll);
+    }
+}
+
+impl Widget for &TextInput<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = self.block();
+        let inner = block.inner(area);
+        block.render(area, buf);
+        if inner.is_empty() {
+            return;
+        }
+        buf.set_style(inner, self.style);
+
+        let width = usize::from(inner.width);
+        self.scroll_to_cursor(width);
+        let scroll = self.scroll.get();
+
+        let mut column = 0;
+        for grapheme in self.value.graphemes(true) {
+            let start = column;
+            column += grapheme.width();
+            // Graphemes cut off by either edge are left blank
+            if start < scroll {
+                continue;
+            }
+            if column > scroll + width {
+                break;
+            }
+            let x = inner.x + u16::try_from(start - scroll).unwrap_or(u16::MAX);
+            buf.set_stringn(x, inner.y, grapheme, width, self.style);
+        }
+
+        if let Some(shape) = self.cursor_style
+            && let Some(position) = self.cursor_position(area)
+        {
+            shape.apply(&mut buf[position]);
+        }
+    }
+}
+
+impl Widget for TextInput<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        (&self).render(area, buf);
+    }
+}
+
+impl Default for TextInput<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A Ctrl-P style list of commands filtered by a fuzzy query
+///
+/// The query is shown on the first row and the matching commands below it, best
+/// match first, with the matched characters of each name highlighted. Commands are
+/// ranked by [`fuzzy_match`], keeping their original order among equal scores, and an
+/// empty query lists every command. The list scrolls to keep the selection visible.
+#[derive(Debug, Clone)]
+pub struct CommandPalette<'a> {
+    commands: Vec<(&'a str, &'a str)>,
+    query: String,
+    /// Indices into `commands` with the matched character indices, best first
+    matches: Vec<(usize, Vec<usize>)>,
+    selected: usize,
+    offset: Cell<usize>,
+    prompt: &'a str,
+    style: Style,
+    match_style: Style,
+    description_style: Style,
+    highlight_style: Style,
+}
+
+impl<'a> CommandPalette<'a> {
+    /// Create a palette of `(name, description)` commands with an empty query
+    #[must_use]
+    pub fn new(commands: Vec<(&'a str, &'a str)>) -> Self {
+        let mut palette = Self {
+            commands,
+            query: String::new(),
+            matches: Vec::new(),
+            selected: 0,
+            offset: Cell::new(0),
+            prompt: "> ",
+            style: Style::default(),
+            match_style: Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+            description_style: Style::default().add_modifier(Modifier::DIM),
+            highlight_style: Style::default().add_modifier(Modifier::REVERSED),
+        };
+        palette.refilter();
+        palette
+    }
+
+    /// Set the text shown before the query
+    #[must_use]
+    pub fn prompt(mut self, prompt: &'a str) -> Self {
+        self.prompt = prompt;
+        self
+    }
+
+    /// Set the style of the palette
+    #[must_use]
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the style of the name characters matched by the query
+    #[must_use]
+    pub fn match_style(mut self, style: Style) -> Self {
+        self.match_style = style;
+        self
+    }
+
+    /// Set the style of the descriptions
+    #[must_use]
+    pub fn description_style(mut self, style: Style) -> Self {
+        self.description_style = style;
+        self
+    }
+
+    /// Set the style used to highlight the selected row
+    #[must_use]
+    pub fn highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = style;
+        self
+    }
+
+    /// Get the query
+    #[must_use]
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Replace the query, re-ranking the commands and selecting the best match
+    pub fn set_query(&mut self, query: impl Into<String>) {
+        self.query = query.into();
+        self.refilter();
+    }
+
+    /// Get the number of commands matching the query
+    #[must_use]
+    pub fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    /// Get the matching commands, best first
+    pub fn matches(&self) -> impl Iterator<Item = (&'a str, &'a str)> + '_ {
+        self.matches.iter().map(|&(index, _)| self.commands[index])
+    }
+
+    /// Get the selected command as `(name, description)`, if any command matches
+    #[must_use]
+    pub fn selected_command(&self) -> Option<(&'a str, &'a str)> {
+        self.matches
+            .get(self.selected)
+            .map(|&(index, _)| self.commands[index])
+    }
+
+    /// Move the selection down one row, wrapping to the top
+    pub fn select_next(&mut self) {
+        if !self.matches.is_empty() {
+            self.selected = (self.selected + 1) % self.matches.len();
+        }
+    }
+
+    /// Move the selection up one row, wrapping to the bottom
+    pub fn select_prev(&mut self) {
+        if let Some(last) = self.matches.len().checked_sub(1) {
+            self.selected = self.selected.checked_sub(1).unwrap_or(last);
+        }
+    }
+
+    /// Apply query editing and selection keys, returning whether the key was used
+    ///
+    /// Printable characters and Backspace edit the query, and Up and Down move the
+    /// selection. Enter and Esc are left to the caller.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        if key.kind == KeyEventKind::Release {
+            return false;
+        }
+        match key.code {
+            KeyCode::Char(c)
+                if !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                self.query.push(c);
+                self.refilter();
+            }
+            KeyCode::Backspace => {
+                if self.query.pop().is_none() {
+                    return false;
+                }
+                self.refilter();
+            }
+            KeyCode::Up => self.select_prev(),
+            KeyCode::Down => self.select_next(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Rank the commands against the query and reset the selection
+    fn refilter(&mut self) {
+        let mut scored: Vec<_> = self
+            .commands
+            .iter()
+            .enumerate()
+            .filter_map(|(index, (name, _))| {
+                fuzzy_match(&self.query, name).map(|(score, indices)| (score, index, indices))
+            })
+            .collect();
+        // Stable, so equal scores keep the order the commands were given in
+        scored.sort_by_key(|&(score, ..)| std::cmp::Reverse(score));
+        self.matches = scored
+            .into_iter()
+            .map(|(_, index, indices)| (index, indices))
+            .collect();
+        self.selected = 0;
+        self.offset.set(0);
+    }
+
+    /// Build the row for a match, with its matched characters highlighted
+    fn match_line(&self, index: usize, matched: &[usize]) -> Line<'a> {
+        let (name, description) = self.commands[index];
+        let mut spans: Vec<Span<'a>> = Vec::new();
+        let mut run = String::new();
+        let mut run_matched = false;
+        for (position, c) in name.chars().enumerate() {
+            let is_match = matched.contains(&position);
+            if is_match != run_matched && !run.is_empty() {
+                let style = if run_matched {
+                    self.match_style
+                } else {
+                    Style::default()
+                };
+                spans.push(Span::styled(std::mem::take(&mut run), style));
+            }
+            run_matched = is_match;
+            run.push(c);
+        }
+        if !run.is_empty() {
+            let style = if run_matched {
+                self.match_style
+            } else {
+                Style::default()
+            };
+            spans.push(Span::styled(run, style));
+        }
+        if !description.is_empty() {
+            spans.push(Span::raw("  "));
+            spans.push(Span::styled(description, self.description_style));
+        }
+        Line::from(spans)
+    }
+}
+
+impl Widget for &CommandPalette<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        buf.set_style(area, self.style);
+        if area.is_empty() {
+            return;
+        }
+        let prompt = Line::from(vec![Span::raw(self.prompt), Span::raw(self.query.as_str())]);
+        buf.set_line(area.x, area.y, &prompt, area.width);
+
+        let list = Rect::new(area.x, area.y + 1, area.width, area.height - 1);
+        let height = usize::from(list.height);
+        if height == 0 {
+            return;
+        }
+        let mut offset = self.offset.get();
+        if self.selected < offset {
+            offset = self.selected;
+        } else if self.selected >= offset + height {
+            offset = self.selected + 1 - height;
+        }
+        self.offset.set(offset);
+
+        let visible = self.matches.iter().enumerate().skip(offset);
+        for (y, (position, (index, matched))) in (list.top()..list.bottom()).zip(visible) {
+            let row = Rect::new(list.x, y, list.width, 1);
+            buf.set_line(row.x, row.y, &self.match_line(*index, matched), row.width);
+            if position == self.selected {
+                buf.set_style(row, self.highlight_style);
+            }
+        }
+    }
+}
+
+impl Widget for CommandPalette<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        (&self).render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::charset::with_charset;
+
+    #[test]
+    fn test_status_indicator() {
+        let indicator = StatusIndicator::new(StatusColor::Success).label("Running");
+
+        // Create a test buffer and render the indicator
+        let area = Rect::new(0, 0, 10, 1);
+        let mut buffer = Buffer::empty(area);
+        indicator.render(area, &mut buffer);
+
+        // Check that the buffer contains the expected content
+        assert_eq!(buffer[(0, 0)].symbol(), "●");
+    }
+
+    #[test]
+    fn test_status_indicator_symbols() {
+        let area = Rect::new(0, 0, 4, 1);
+        let mut buffer = Buffer::empty(area);
+        StatusIndicator::new(StatusColor::Error)
+            .with_symbols(true)
+            .themed(&ColorProfile::ColorBlindSafe.theme())
+            .render(area, &mut buffer);
+        assert_eq!(buffer[(0, 0)].symbol(), "✕");
+        assert_eq!(buffer[(0, 0)].fg, Color::Rgb(213, 94, 0));
+
+        StatusIndicator::new(StatusColor::Success)
+            .with_symbols(true)
+            .render(area, &mut buffer);
+        assert_eq!(buffer[(0, 0)].symbol(), "✓");
+        assert_eq!(buffer[(0, 0)].fg, Color::Green);
+    }
+
+    #[test]
+    fn test_ascii_charset_fallbacks() {
+        let area = Rect::new(0, 0, 10, 3);
+        let mut buffer = Buffer::empty(area);
+        with_charset(CharsetProfile::Ascii, || {
+            StatusIndicator::new(StatusColor::Success).render(Rect::new(1, 1, 8, 1), &mut buffer);
+            Card::new().render(area, &mut buffer);
+        });
+
+        assert_eq!(buffer[(0, 0)].symbol(), "+");
+        assert_eq!(buffer[(9, 2)].symbol(), "+");
+        assert_eq!(buffer[(0, 1)].symbol(), "|");
+        assert!(buffer.content.iter().all(|cell| cell.symbol().is_ascii()));
+    }
+
+    #[test]
+    fn test_badge_width() {
+        let badge = Badge::new("new").status(StatusColor::Success);
+        assert_eq!(badge.preferred_width(), 7);
+
+        let area = Rect::new(0, 0, 12, 3);
+        let mut buffer = Buffer::empty(area);
+        badge.render(area, &mut buffer);
+
+        // Drawn on the middle row, with the fill stopping at the right cap
+        assert_eq!(strip_text(&buffer, 1), "▐ new ▌     ");
+        assert_eq!(buffer[(3, 1)].bg, Color::Green);
+        assert_eq!(buffer[(7, 1)].bg, Color::Reset);
+        assert_eq!(strip_text(&buffer, 0), " ".repeat(12));
+
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 6, 1));
+        Badge::new("urgent").render(buffer.area, &mut buffer);
+        assert_eq!(strip_text(&buffer, 0), "▐ u… ▌");
+    }
+
+    #[test]
+    fn test_themed_widgets() {
+        let theme = Theme::light();
+        let area = Rect::new(0, 0, 10, 3);
+        let mut buffer = Buffer::empty(area);
+        Card::new().themed(&theme).render(area, &mut buffer);
+        StatusIndicator::new(StatusColor::Error)
+            .label("down")
+            .themed(&theme)
+            .render(Rect::new(1, 1, 8, 1), &mut buffer);
+
+        assert_eq!(buffer[(0, 0)].fg, theme.border);
+        assert_eq!(buffer[(0, 0)].bg, theme.background);
+        assert_eq!(buffer[(1, 1)].fg, theme.error);
+        assert_eq!(buffer[(3, 1)].fg, theme.text);
+    }
+
+    #[test]
+    fn test_status_color_cycle() {
+        let mut status = StatusColor::Success;
+        let mut visited = vec![status];
+        for _ in 0..4 {
+            status = status.cycle();
+            assert!(!visited.contains(&status), "{status:?} visited twice");
+            visited.push(status);
+        }
+
+        assert_eq!(
+            visited,
+            [
+                StatusColor::Success,
+                StatusColor::Warning,
+                StatusColor::Error,
+                StatusColor::Info,
+                StatusColor::Neutral,
+            ]
+        );
+        assert_eq!(status.cycle(), StatusColor::Success);
+        assert_eq!(
+            StatusColor::Custom(Color::Magenta).cycle(),
+            StatusColor::Success
+        );
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::ZERO, false), "00:00");
+        assert_eq!(
+            format_duration(Duration::from_secs(59 * 60 + 59), false),
+            "59:59"
+        );
+        assert_eq!(format_duration(Duration::from_hours(1), false), "01:00:00");
+        assert_eq!(
+            format_duration(Duration::from_secs(100 * 3600 + 61), false),
+            "100:01:01"
+        );
+        assert_eq!(
+            format_duration(Duration::from_millis(61_999), true),
+            "01:01.999"
+        );
+    }
+
+    #[test]
+    fn test_timer_display_warning() {
+        let timer =
+            TimerDisplay::new(Duration::from_secs(30)).warning_threshold(Duration::from_secs(10));
+        assert_eq!(timer.current_status(), StatusColor::Neutral);
+
+        let timer =
+            TimerDisplay::new(Duration::from_secs(9)).warning_threshold(Duration::from_secs(10));
+        assert_eq!(timer.current_status(), StatusColor::Warning);
+
+        let area = Rect::new(0, 0, 8, 1);
+        let mut buffer = Buffer::empty(area);
+        timer.render(area, &mut buffer);
+        assert_eq!(strip_text(&buffer, 0), "00:09   ");
+        assert_eq!(buffer[(0, 0)].fg, Color::Yellow);
+    }
+
+    #[test]
+    fn test_thresholds_ascending() {
+        let thresholds = Thresholds::new(StatusColor::Success)
+            .step(0.8, StatusColor::Error)
+            .step(0.5, StatusColor::Warning);
+        assert_eq!(thresholds.color_for(0.2), StatusColor::Success);
+        assert_eq!(thresholds.color_for(0.5), StatusColor::Warning);
+        assert_eq!(thresholds.color_for(0.79), StatusColor::Warning);
+        assert_eq!(thresholds.color_for(0.8), StatusColor::Error);
+        assert_eq!(thresholds.color_for(-3.0), StatusColor::Success);
+        assert_eq!(thresholds.color_for(f64::INFINITY), StatusColor::Error);
+        assert_eq!(thresholds.color_for(f64::NAN), StatusColor::Success);
+
+        let gauge = Gauge::new(0.9).thresholds(&thresholds);
+        assert_eq!(gauge.fill_color(), Color::Red);
+    }
+
+    #[test]
+    fn test_thresholds_descending() {
+        let thresholds = Thresholds::new(StatusColor::Neutral)
+            .step(30.0, StatusColor::Warning)
+            .step(10.0, StatusColor::Error)
+            .descending(true);
+        assert_eq!(thresholds.color_for(60.0), StatusColor::Neutral);
+        assert_eq!(thresholds.color_for(30.0), StatusColor::Warning);
+        assert_eq!(thresholds.color_for(10.0), StatusColor::Error);
+        assert_eq!(thresholds.color_for(-1.0), StatusColor::Error);
+
+        let timer = TimerDisplay::new(Duration::from_secs(20))
+            .warning_threshold(Duration::from_secs(45))
+            .thresholds(&thresholds);
+        assert_eq!(timer.current_status(), StatusColor::Warning);
+    }
+
+    #[test]
+    fn test_card() {
+        let card = Card::new()
+            .title("Test Card")
+            .add_line(Line::from("Test content"));
+
+        // Create a test buffer and render the card
+        let area = Rect::new(0, 0, 20, 5);
+        let mut buffer = Buffer::empty(area);
+        card.render(area, &mut buffer);
+
+        // Check that the buffer contains the title
+        let title_found = (0..area.width).any(|x| buffer[(x, 0)].symbol() == "T");
+        assert!(title_found, "Title not found in buffer");
+    }
+
+    #[test]
+    fn test_card_wrap() {
+        let area = Rect::new(0, 0, 12, 5);
+        let content = vec![Line::from("one two three")];
+
+        let mut buffer = Buffer::empty(area);
+        Card::new()
+            .content(content.clone())
+            .render(area, &mut buffer);
+        assert_eq!(strip_text(&buffer, 1), "│one two th│");
+        assert_eq!(strip_text(&buffer, 2), "│          │");
+
+        let mut buffer = Buffer::empty(area);
+        Card::new()
+            .content(content)
+            .wrap(true)
+            .render(area, &mut buffer);
+        assert_eq!(strip_text(&buffer, 1), "│one two   │");
+        assert_eq!(strip_text(&buffer, 2), "│three     │");
+    }
+
+    #[test]
+    fn test_card_alignment() {
+        let area = Rect::new(0, 0, 9, 3);
+        let mut buffer = Buffer::empty(area);
+        Card::new()
+            .add_line(Line::from("ok"))
+            .alignment(Alignment::Right)
+            .render(area, &mut buffer);
+        assert_eq!(strip_text(&buffer, 1), "│     ok│");
+    }
+
+    #[test]
+    fn test_card_corner_labels() {
+        let card = Card::new()
+            .title("Inbox")
+            .top_right("12")
+            .bottom_left("q quit")
+            .bottom_right("? help");
+        let area = Rect::new(0, 0, 16, 3);
+        let mut buffer = Buffer::empty(area);
+        card.render(area, &mut buffer);
+        assert_eq!(strip_text(&buffer, 0), "┌Inbox───────12┐");
+        assert_eq!(strip_text(&buffer, 2), "└q quit──? help┘");
+
+        // Labels that would collide are truncated, keeping the main title intact
+        let card = Card::new().title("Inbox").top_right("123456");
+        let area = Rect::new(0, 0, 11, 3);
+        let mut buffer = Buffer::empty(area);
+        card.render(area, &mut buffer);
+        assert_eq!(strip_text(&buffer, 0), "┌Inbox─123┐");
+
+        // A wide char that would overflow is dropped whole
+        let card = Card::new().title("受信箱");
+        let area = Rect::new(0, 0, 7, 3);
+        let mut buffer = Buffer::empty(area);
+        card.render(area, &mut buffer);
+        assert_eq!(buffer[(1, 0)].symbol(), "受");
+        assert_eq!(buffer[(3, 0)].symbol(), "信");
+        assert_eq!(buffer[(5, 0)].symbol(), "─");
+    }
+
+    #[test]
+    fn test_card_border_set() {
+        let custom = border::Set {
+            top_left: "╔",
+            top_right: "╗",
+            ..border::ROUNDED
+        };
+        let area = Rect::new(0, 0, 6, 3);
+        let mut buffer = Buffer::empty(area);
+        Card::new()
+            .title("Hi")
+            .border_set(custom)
+            .border_style(Style::new().fg(Color::Cyan))
+            .render(area, &mut buffer);
+        assert_eq!(buffer[(0, 0)].symbol(), "╔");
+        assert_eq!(buffer[(0, 0)].fg, Color::Cyan);
+        assert_eq!(strip_text(&buffer, 0), "╔Hi──╗");
+        assert_eq!(strip_text(&buffer, 2), "╰────╯");
+
+        let mut buffer = Buffer::empty(area);
+        Card::new().ascii().render(area, &mut buffer);
+        assert_eq!(strip_text(&buffer, 0), "+----+");
+    }
+
+    #[test]
+    fn test_placeholder_below_min_size() {
+        let card = || Card::new().title("Title").content(vec![Line::raw("body")]);
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 1, 1));
+        render_or_placeholder(card(), buffer.area, &mut buffer);
+        assert_eq!(buffer[(0, 0)].symbol(), "…");
+
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 3, 3));
+        render_or_placeholder(card(), buffer.area, &mut buffer);
+        assert_eq!(strip_text(&buffer, 1), "│b│");
+
+        assert_eq!(BorderedGauge::new(0.5).min_size(), (2, 2));
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 4, 1));
+        render_or_placeholder(
+            StatusIndicator::new(StatusColor::Info),
+            buffer.area,
+            &mut buffer,
+        );
+        assert_eq!(buffer[(0, 0)].symbol(), "●");
+    }
+
+    #[test]
+    fn test_card_render_centered() {
+        let card = Card::new().title("Hi").add_line(Line::from("Short"));
+
+        let container = Rect::new(0, 0, 40, 20);
+        let mut buffer = Buffer::empty(container);
+        let used = card.render_centered(container, &mut buffer, 30, 10);
+
+        // "Short" plus borders on each side, one line plus top and bottom borders
+        assert_eq!(used, Rect::new(16, 8, 7, 3));
+        assert!(used.width < container.width && used.height < container.height);
+        assert_eq!(buffer[(used.x, used.y)].symbol(), "┌");
+        assert_eq!(buffer[(used.x + 1, used.y + 1)].symbol(), "S");
+    }
+
+    #[test]
+    fn test_mini_chart_extremes() {
+        let data = [0.0, 1.0];
+        let area = Rect::new(0, 0, 2, 1);
+        let mut buffer = Buffer::empty(area);
+        MiniChart::new(&data).render(area, &mut buffer);
+
+        assert_eq!(buffer[(0, 0)].symbol(), "▁");
+        assert_eq!(buffer[(1, 0)].symbol(), "█");
+    }
+
+    #[test]
+    fn test_mini_chart_edge_cases() {
+        let area = Rect::new(0, 0, 4, 1);
+
+        let mut buffer = Buffer::empty(area);
+        MiniChart::new(&[]).render(area, &mut buffer);
+        assert!((0..area.width).all(|x| buffer[(x, 0)].symbol() == " "));
+
+        let mut buffer = Buffer::empty(area);
+        MiniChart::new(&[3.0, 3.0, 3.0, 3.0]).render(area, &mut buffer);
+        assert!((0..area.width).all(|x| buffer[(x, 0)].symbol() == "▄"));
+    }
+
+    #[test]
+    fn test_mini_chart_averages_extra_points() {
+        // Four points into two columns: averages are 0.5 and 2.5
+        let data = [0.0, 1.0, 2.0, 3.0];
+        let area = Rect::new(0, 0, 2, 1);
+        let mut buffer = Buffer::empty(area);
+        MiniChart::new(&data).render(area, &mut buffer);
+
+        assert_eq!(buffer[(0, 0)].symbol(), "▁");
+        assert_eq!(buffer[(1, 0)].symbol(), "█");
+    }
+
+    #[test]
+    fn test_trend_line_marks_extremes() {
+        let data: VecDeque<f64> = [9.0, 1.0, 4.0, 8.0, 2.0, 5.0].into();
+        let area = Rect::new(0, 0, 9, 1);
+        let mut buffer = Buffer::empty(area);
+        TrendLine::from_deque(&data)
+            .high_color(Color::Magenta)
+            .low_color(Color::Green)
+            .render(area, &mut buffer);
+
+        // The 9.0 has scrolled out, so 8.0 is the visible high
+        assert_eq!(strip_text(&buffer, 0), "▁▄█▂▅ 5.0");
+        assert_eq!(buffer[(2, 0)].fg, Color::Magenta);
+        assert_eq!(buffer[(0, 0)].fg, Color::Green);
+        assert_eq!(buffer[(4, 0)].fg, Color::Blue);
+    }
+
+    #[test]
+    fn test_trend_line_short_data_and_ties() {
+        let area = Rect::new(0, 0, 8, 1);
+        let mut buffer = Buffer::empty(area);
+        TrendLine::new(&[3.0, 1.0, 3.0])
+            .precision(0)
+            .render(area, &mut buffer);
+        assert_eq!(strip_text(&buffer, 0), "   █▁█ 3");
+        assert_eq!(buffer[(5, 0)].fg, Color::Yellow);
+        assert_eq!(buffer[(3, 0)].fg, Color::Blue);
+    }
+
+    #[test]
+    fn test_log_view_drops_oldest_past_capacity() {
+        let mut log = LogView::new(3);
+        for n in 1..=5 {
+            log.append(format!("line {n}"));
+        }
+        assert_eq!(log.len(), 3);
+        let kept: Vec<String> = log.lines().map(ToString::to_string).collect();
+        assert_eq!(kept, ["line 3", "line 4", "line 5"]);
+    }
+
+    #[test]
+    fn test_log_view_follow_and_detach() {
+        let area = Rect::new(0, 0, 6, 2);
+        let mut log = LogView::new(10);
+        for n in 1..=4 {
+            log.append(format!("line {n}"));
+        }
+        let render = |log: &mut LogView| {
+            let mut buffer = Buffer::empty(area);
+            log.render(area, &mut buffer);
+            [strip_text(&buffer, 0), strip_text(&buffer, 1)]
+        };
+        assert_eq!(render(&mut log), ["line 3", "line 4"]);
+
+        // Scrolling up detaches, so new lines don't move the view
+        log.scroll_up(1);
+        assert!(!log.is_following());
+        log.append("line 5");
+        assert_eq!(render(&mut log), ["line 2", "line 3"]);
+
+        // Reaching the bottom again follows new lines
+        log.scroll_down(5);
+        assert!(log.is_following());
+        log.append("line 6");
+        assert_eq!(render(&mut log), ["line 5", "line 6"]);
+    }
+
+    #[test]
+    fn test_ansi_block_pixel_pairs() {
+        let pixels = vec![
+            vec![
+                Color::Rgb(255, 0, 0),
+                Color::Rgb(0, 255, 0),
+                Color::Rgb(1, 2, 3),
+            ],
+            vec![Color::Rgb(0, 0, 255), Color::Rgb(9, 9, 9)],
+        ];
+        let block = AnsiBlock::from_pixels(&pixels);
+        assert_eq!(block.size(), (3, 1));
+
+        // The area clips the third column
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 4, 2));
+        block.render(Rect::new(1, 0, 2, 2), &mut buffer);
+        assert_eq!(strip_text(&buffer, 0), " ▀▀ ");
+        assert_eq!(buffer[(1, 0)].fg, Color::Rgb(255, 0, 0));
+        assert_eq!(buffer[(1, 0)].bg, Color::Rgb(0, 0, 255));
+        assert_eq!(buffer[(2, 0)].fg, Color::Rgb(0, 255, 0));
+        assert_eq!(buffer[(2, 0)].bg, Color::Rgb(9, 9, 9));
+        assert_eq!(strip_text(&buffer, 1), "    ");
+    }
+
+    #[test]
+    fn test_ansi_block_escapes() {
+        let text = "\x1b[31mred\x1b[0m \x1b[2K\x1b[38;2;1;2;3;1mrgb\n\x1b]0;title\x07plain";
+        let block = AnsiBlock::new(text);
+        assert_eq!(block.size(), (7, 2));
+
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 6, 2));
+        block.render(buffer.area, &mut buffer);
+        // The clear-line sequence is skipped and the line is clipped at the area edge
+        assert_eq!(strip_text(&buffer, 0), "red rg");
+        assert_eq!(buffer[(0, 0)].fg, Color::Red);
+        assert_eq!(buffer[(3, 0)].fg, Color::Reset);
+        assert_eq!(buffer[(4, 0)].fg, Color::Rgb(1, 2, 3));
+        assert!(buffer[(4, 0)].modifier.contains(Modifier::BOLD));
+        // Styles carry over to the next line, as in a terminal
+        assert_eq!(strip_text(&buffer, 1), "plain ");
+        assert_eq!(buffer[(0, 1)].fg, Color::Rgb(1, 2, 3));
+
+        // A line wider than the column range still parses through to its end
+        let long = format!("{}\x1b[32m\nok", "x".repeat(70_000));
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 4, 2));
+        AnsiBlock::new(&long).render(buffer.area, &mut buffer);
+        assert_eq!(strip_text(&buffer, 0), "xxxx");
+        assert_eq!(buffer[(0, 1)].fg, Color::Green);
+    }
+
+    #[test]
+    fn test_command_palette_ranks_prefix_first() {
+        let mut palette = CommandPalette::new(vec![
+            ("Other Panel", "Focus the other panel"),
+            ("Open File", "Open a file"),
+            ("Quit", ""),
+        ]);
+        assert_eq!(palette.match_count(), 3);
+
+        palette.set_query("op");
+        let names: Vec<_> = palette.matches().map(|(name, _)| name).collect();
+        assert_eq!(names, vec!["Open File", "Other Panel"]);
+        assert_eq!(
+            palette.selected_command(),
+            Some(("Open File", "Open a file"))
+        );
+
+        palette.select_next();
+        assert_eq!(palette.selected_command().unwrap().0, "Other Panel");
+        palette.select_next();
+        assert_eq!(palette.selected_command().unwrap().0, "Open File");
+        palette.select_prev();
+        assert_eq!(palette.selected_command().unwrap().0, "Other Panel");
+
+        palette.set_query("zzz");
+        assert_eq!(palette.selected_command(), None);
+    }
+
+    #[test]
+    fn test_command_palette_highlights_matches() {
+        let mut palette = CommandPalette::new(vec![("Other Panel", "Focus"), ("Quit", "")]);
+        palette.handle_key(&KeyEvent::from(KeyCode::Char('o')));
+        palette.handle_key(&KeyEvent::from(KeyCode::Char('p')));
+
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 20, 3));
+        (&palette).render(buffer.area, &mut buffer);
+        assert_eq!(strip_text(&buffer, 0), "> op                ");
+        assert_eq!(strip_text(&buffer, 1), "Other Panel  Focus  ");
+        assert_eq!(strip_text(&buffer, 2), "                    ");
+        assert_eq!(buffer[(0, 1)].fg, Color::Yellow);
+        assert_eq!(buffer[(1, 1)].fg, Color::Reset);
+        assert_eq!(buffer[(6, 1)].fg, Color::Yellow);
+        assert!(buffer[(13, 1)].modifier.contains(Modifier::DIM));
+        assert!(buffer[(19, 1)].modifier.contains(Modifier::REVERSED));
+    }
+
+    #[test]
+    fn test_frame_graph_stats() {
+        let mut graph = FrameGraph::new(4);
+        assert_eq!(graph.average(), None);
+        for millis in [40, 10, 20, 30, 20] {
+            graph.push(Duration::from_millis(millis));
+        }
+        // The 40ms frame has rolled out of the window
+        assert_eq!(graph.average(), Some(Duration::from_millis(20)));
+        assert_eq!(graph.average_fps(), Some(50.0));
+        assert_eq!(graph.p99(), Some(Duration::from_millis(30)));
+        assert_eq!(graph.latest(), Some(Duration::from_millis(20)));
+        assert!(graph.over_budget());
+    }
+
+    #[test]
+    fn test_frame_graph_marks_slow_frames() {
+        let mut graph = FrameGraph::new(10)
+            .budget(Duration::from_millis(25))
+            .width(28);
+        for millis in [10, 30, 20] {
+            graph.push(Duration::from_millis(millis));
+        }
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 40, 6));
+        (&graph).render(buffer.area, &mut buffer);
+
+        assert_eq!(
+            &strip_text(&buffer, 1)[12..],
+            "│50 fps  avg 50  p99 30.0ms│"
+        );
+        assert!(strip_text(&buffer, 2).ends_with(" ▃█▆│"));
+        // Only the 30ms frame is over the budget, and the latest frame is not
+        assert_eq!(buffer[(36, 2)].fg, Color::Green);
+        assert_eq!(buffer[(37, 2)].fg, Color::Red);
+        assert_eq!(buffer[(38, 2)].fg, Color::Green);
+        assert_eq!(buffer[(39, 2)].fg, Color::Green);
+    }
+
+    #[test]
+    fn test_split_pane_drag_moves_divider() {
+        let area = Rect::new(0, 0, 21, 5);
+        let mut pane = SplitPane::new(Direction::Horizontal);
+        assert_eq!(
+            pane.split(area),
+            (Rect::new(0, 0, 10, 5), Rect::new(11, 0, 10, 5))
+        );
+
+        let mouse = |kind, column| MouseEvent {
+            kind,
+            column,
+            row: 2,
+            modifiers: KeyModifiers::NONE,
+        };
+        let left = MouseButton::Left;
+        // Dragging only starts on the divider
+        assert!(!pane.handle_mouse(&mouse(MouseEventKind::Down(left), 4), area));
+        assert!(!pane.handle_mouse(&mouse(MouseEventKind::Drag(left), 15), area));
+        assert!(pane.handle_mouse(&mouse(MouseEventKind::Down(left), 10), area));
+        assert!(pane.handle_mouse(&mouse(MouseEventKind::Drag(left), 15), area));
+        assert!((pane.current_ratio() - 0.75).abs() < 1e-9);
+        assert_eq!(
+            pane.split(area),
+            (Rect::new(0, 0, 15, 5), Rect::new(16, 0, 5, 5))
+        );
+
+        // The second pane keeps its minimum size
+        pane.handle_mouse(&mouse(MouseEventKind::Drag(left), 20), area);
+        assert_eq!(pane.divider(area), Rect::new(17, 0, 1, 5));
+        assert!(pane.handle_mouse(&mouse(MouseEventKind::Up(left), 20), area));
+        assert!(!pane.is_dragging());
+
+        let mut buffer = Buffer::empty(area);
+        (&pane).render(area, &mut buffer);
+        assert_eq!(buffer[(17, 0)].symbol(), "│");
+        assert_eq!(buffer[(17, 4)].fg, Color::DarkGray);
+    }
+
+    #[test]
+    fn test_progress_ring_half_full() {
+        let area = Rect::new(0, 0, 5, 3);
+        let mut buffer = Buffer::empty(area);
+        ProgressRing::new(0.5).render(area, &mut buffer);
+        assert_eq!(strip_text(&buffer, 0), "░░███");
+        assert_eq!(strip_text(&buffer, 1), "░50%█");
+        assert_eq!(strip_text(&buffer, 2), "░░▒██");
+        let solid = buffer.content.iter().filter(|cell| cell.symbol() == "█");
+        assert_eq!(solid.count(), 6);
+    }
+
+    #[test]
+    fn test_progress_ring_leading_edge_animates() {
+        let area = Rect::new(0, 0, 5, 3);
+        let mut buffer = Buffer::empty(area);
+        ProgressRing::new(0.0)
+            .label(false)
+            .elapsed(Duration::from_millis(150))
+            .render(area, &mut buffer);
+        assert_eq!(strip_text(&buffer, 0), "░░▓░░");
+        assert_eq!(strip_text(&buffer, 1), "░   ░");
+
+        // A full ring has no leading edge, and "100%" is too wide for the inside
+        ProgressRing::new(1.0).render(area, &mut buffer);
+        assert_eq!(strip_text(&buffer, 0), "█████");
+        assert_eq!(strip_text(&buffer, 1), "█   █");
+    }
+
+    #[test]
+    fn test_gauge() {
+        let area = Rect::new(0, 0, 21, 6);
+        let mut buffer = Buffer::empty(area);
+        Gauge::new(0.42).render(area, &mut buffer);
+
+        // The label sits on the arc's baseline, centered horizontally
+        let center = buffer[(10, 5)].symbol();
+        assert!(center.chars().all(|c| c.is_ascii_digit()), "got {center:?}");
+        let label: String = (8..13).map(|x| buffer[(x, 5)].symbol()).collect();
+        assert_eq!(label.trim(), "42%");
+
+        // The left end of the arc is filled, the right end is not
+        assert_eq!(strip_text(&buffer, 0), "    ⢀⣠⣴⣶⣿⣿⣿⣿⣿⣶⣦⣄⡀    ");
+        assert_eq!(buffer[(0, 5)].symbol(), "⠛");
+        assert_eq!(buffer[(0, 5)].fg, Color::Green);
+        assert_eq!(buffer[(20, 5)].symbol(), "⠛");
+        assert_eq!(buffer[(20, 5)].fg, Color::DarkGray);
+
+        let mut buffer = Buffer::empty(area);
+        with_charset(CharsetProfile::Ascii, || {
+            Gauge::new(0.42).render(area, &mut buffer);
+        });
+        assert_eq!(buffer[(0, 5)].symbol(), "#");
+        assert_eq!(buffer[(20, 5)].symbol(), ".");
+    }
+
+    #[test]
+    fn test_heatmap_colors() {
+        let grid = vec![vec![0.0, 1.0], vec![2.0, 3.0]];
+        let area = Rect::new(0, 0, 2, 2);
+        let mut buffer = Buffer::empty(area);
+        Heatmap::new(&grid)
+            .gradient(&[Color::Rgb(0, 0, 0), Color::Rgb(255, 255, 255)])
+            .render(area, &mut buffer);
+
+        assert_eq!(buffer[(0, 0)].bg, Color::Rgb(0, 0, 0));
+        assert_eq!(buffer[(1, 0)].bg, Color::Rgb(85, 85, 85));
+        assert_eq!(buffer[(0, 1)].bg, Color::Rgb(170, 170, 170));
+        assert_eq!(buffer[(1, 1)].bg, Color::Rgb(255, 255, 255));
+    }
+
+    #[test]
+    fn test_heatmap_downsamples_ragged_grid() {
+        // Four source rows fold into two display rows; the short row leaves a gap
+        let grid = vec![
+            vec![0.0, 0.0, 4.0, 4.0],
+            vec![0.0, 0.0, 4.0, 4.0],
+            vec![8.0, 8.0],
+            vec![8.0, 8.0],
+        ];
+        let area = Rect::new(0, 0, 2, 2);
+        let mut buffer = Buffer::empty(area);
+        Heatmap::new(&grid)
+            .gradient(&[Color::Rgb(0, 0, 0), Color::Rgb(0, 0, 200)])
+            .render(area, &mut buffer);
+
+        assert_eq!(buffer[(0, 0)].bg, Color::Rgb(0, 0, 0));
+        assert_eq!(buffer[(1, 0)].bg, Color::Rgb(0, 0, 100));
+        assert_eq!(buffer[(0, 1)].bg, Color::Rgb(0, 0, 200));
+        assert_eq!(buffer[(1, 1)].bg, Color::Reset);
+    }
+
+    #[test]
+    fn test_multi_select_toggle() {
+        let mut state = MultiSelectState::new();
+        state.toggle(3);
+        state.toggle(1);
+        assert_eq!(state.selected_indices(), [1, 3]);
+
+        state.toggle(3);
+        assert_eq!(state.selected_indices(), [1]);
+        assert!(!state.is_selected(3));
+        assert_eq!(state.anchor(), Some(3));
+    }
+
+    #[test]
+    fn test_multi_select_range_from_anchor() {
+        let mut state = MultiSelectState::new();
+        state.toggle(5);
+        state.extend_to(2);
+        assert_eq!(state.selected_indices(), [2, 3, 4, 5]);
+        assert_eq!(state.anchor(), Some(5));
+
+        state.select_range(8, 7);
+        assert_eq!(state.selected_indices(), [2, 3, 4, 5, 7, 8]);
+    }
+
+    #[test]
+    fn test_multi_select_clear() {
+        let mut state = MultiSelectState::new();
+        state.select_range(0, 4);
+        state.clear();
+
+        assert!(state.selected_indices().is_empty());
+        assert_eq!(state.anchor(), None);
+
+        // With no anchor, extending selects just the target row
+        state.extend_to(2);
+        assert_eq!(state.selected_indices(), [2]);
+    }
+
+    #[test]
+    fn test_paginator_last_page_is_partial() {
+        let mut pages = Paginator::new(25, 10);
+        assert_eq!(pages.page_count(), 3);
+        assert_eq!(pages.range(), 0..10);
+        assert_eq!(pages.page_indicator(), "1 / 3");
+
+        assert!(pages.goto(7));
+        assert_eq!(pages.page(), 2);
+        assert_eq!(pages.range(), 20..25);
+        assert_eq!(pages.page_indicator(), "3 / 3");
+        assert!(!pages.next_page());
+
+        // Shrinking the data pulls the page back to the new last page
+        pages.set_total(12);
+        assert_eq!(pages.range(), 10..12);
+        assert!(pages.prev_page());
+        assert!(!pages.prev_page());
+        assert!(pages.goto_item(11));
+        assert_eq!(pages.page(), 1);
+    }
+
+    #[test]
+    fn test_paginator_empty_and_full_pages() {
+        let mut pages = Paginator::new(0, 0);
+        assert_eq!(pages.page_count(), 1);
+        assert_eq!(pages.range(), 0..0);
+        assert!(!pages.next_page());
+        assert_eq!(pages.page_indicator(), "1 / 1");
+
+        let mut pages = Paginator::new(30, 10);
+        assert_eq!(pages.page_count(), 3);
+        pages.goto(2);
+        assert_eq!(pages.range(), 20..30);
+    }
+
+    fn numbered_list(count: usize) -> ScrollableList<'static> {
+        ScrollableList::new((0..count).map(|i| Line::from(i.to_string())).collect())
+    }
+
+    #[test]
+    fn test_scrollable_list_multi_select() {
+        let items = vec![Line::from("a"), Line::from("b"), Line::from("c")];
+        let mut list = ScrollableList::new(items).multi_select(true);
+        let space = KeyEvent::from(KeyCode::Char(' '));
+        list.select(Some(0));
+        list.toggle_current();
+        list.select_next();
+        list.select_next();
+        assert!(list.handle_key(&space, 3));
+        assert_eq!(list.selected_indices(), vec![0, 2]);
+        assert_eq!(list.selected(), Some(2));
+
+        // The cursor row is highlighted whether or not it is checked
+        list.select_prev();
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 6, 3));
+        list.render(buffer.area, &mut buffer);
+        assert_eq!(strip_text(&buffer, 0), "[x] a ");
+        assert_eq!(strip_text(&buffer, 1), "[ ] b ");
+        assert_eq!(strip_text(&buffer, 2), "[x] c ");
+        assert!(buffer[(0, 1)].modifier.contains(Modifier::REVERSED));
+        assert!(!buffer[(0, 2)].modifier.contains(Modifier::REVERSED));
+
+        list.select_all();
+        assert_eq!(list.selected_indices(), vec![0, 1, 2]);
+        list.clear_selection();
+        assert!(list.selected_indices().is_empty());
+        assert_eq!(list.selected(), Some(1));
+    }
+
+    #[test]
+    fn test_scrollable_list_ends_without_wrap() {
+        let mut list = numbered_list(3);
+        list.select_prev();
+        assert_eq!(list.selected(), Some(2));
+
+        list.select_next();
+        assert_eq!(list.selected(), Some(2), "should stop at the last row");
+
+        list.select(Some(0));
+        list.select_prev();
+        assert_eq!(list.selected(), Some(0), "should stop at the first row");
+    }
+
+    #[test]
+    fn test_scrollable_list_ends_with_wrap() {
+        let mut list = numbered_list(3).wrap(true);
+        list.select(Some(2));
+        list.select_next();
+        assert_eq!(list.selected(), Some(0));
+
+        list.select_prev();
+        assert_eq!(list.selected(), Some(2));
+
+        let mut empty = numbered_list(0).wrap(true);
+        empty.select_next();
+        assert_eq!(empty.selected(), None);
+    }
+
+    #[test]
+    fn test_scrollable_list_keeps_selection_visible() {
+        let mut list = numbered_list(10);
+        let area = Rect::new(0, 0, 4, 3);
+
+        list.select(Some(7));
+        let mut buffer = Buffer::empty(area);
+        list.render(area, &mut buffer);
+        assert_eq!(list.offset(), 5);
+        assert_eq!(buffer[(0, 2)].symbol(), "7");
+        assert!(buffer[(0, 2)].modifier.contains(Modifier::REVERSED));
+
+        // Selecting above the viewport scrolls back up
+        list.select(Some(1));
+        let mut buffer = Buffer::empty(area);
+        list.render(area, &mut buffer);
+        assert_eq!(list.offset(), 1);
+        assert_eq!(buffer[(0, 0)].symbol(), "1");
+    }
+
+    #[test]
+    fn test_scrollable_list_page_keys() {
+        let press = |code| KeyEvent::from(code);
+        let mut list = numbered_list(10);
+
+        assert!(list.handle_key(&press(KeyCode::PageDown), 4));
+        assert_eq!((list.selected(), list.offset()), (Some(4), 4));
+        assert!(list.handle_key(&press(KeyCode::PageDown), 4));
+        assert_eq!((list.selected(), list.offset()), (Some(8), 6));
+        // Near the bottom the page stops at the last row instead of overshooting
+        assert!(list.handle_key(&press(KeyCode::PageDown), 4));
+        assert_eq!((list.selected(), list.offset()), (Some(9), 6));
+
+        assert!(list.handle_key(&press(KeyCode::PageUp), 4));
+        assert_eq!((list.selected(), list.offset()), (Some(5), 2));
+        assert!(list.handle_key(&press(KeyCode::Up), 4));
+        assert_eq!((list.selected(), list.offset()), (Some(4), 2));
+
+        assert!(list.handle_key(&press(KeyCode::Home), 4));
+        assert_eq!((list.selected(), list.offset()), (Some(0), 0));
+        assert!(list.handle_key(&press(KeyCode::End), 4));
+        assert_eq!((list.selected(), list.offset()), (Some(9), 6));
+        assert!(!list.handle_key(&press(KeyCode::Enter), 4));
+    }
+
+    #[test]
+    fn test_scrollable_list_smooth_scroll() {
+        let mut list = numbered_list(20).smooth_scroll(true);
+        let area = Rect::new(0, 0, 4, 5);
+        list.render(area, &mut Buffer::empty(area));
+
+        list.scroll_by(10);
+        assert_eq!(list.offset(), 0, "nothing moves until the next tick");
+        list.tick(Duration::from_millis(50));
+        let partway = list.offset();
+        assert!(partway > 0 && partway < 10, "offset {partway}");
+
+        let mut buffer = Buffer::empty(area);
+        list.render(area, &mut buffer);
+        assert_eq!(buffer[(0, 0)].symbol(), partway.to_string());
+
+        list.tick(Duration::from_secs(2));
+        assert_eq!(list.offset(), 10);
+
+        // The target stops at the last full page
+        list.scroll_by(100);
+        list.tick(Duration::from_secs(2));
+        assert_eq!(list.offset(), 15);
+        list.scroll_by(-100);
+        list.tick(Duration::from_secs(2));
+        assert_eq!(list.offset(), 0);
+    }
+
+    #[test]
+    fn test_scrollable_list_instant_scroll_by() {
+        let mut list = numbered_list(10);
+        let area = Rect::new(0, 0, 4, 3);
+        list.select(Some(0));
+        list.render(area, &mut Buffer::empty(area));
+
+        list.scroll_by(4);
+        assert_eq!(list.offset(), 4);
+        let mut buffer = Buffer::empty(area);
+        list.render(area, &mut buffer);
+        assert_eq!(
+            buffer[(0, 0)].symbol(),
+            "4",
+            "scrolling leaves the selection behind"
+        );
+
+        list.select_next();
+        list.render(area, &mut buffer);
+        assert_eq!(list.offset(), 1);
+    }
+
+    fn strip_text(buffer: &Buffer, y: u16) -> String {
+        (0..buffer.area.width)
+            .map(|x| buffer[(x, y)].symbol())
+            .collect()
+    }
+
+    #[test]
+    fn test_simple_table_clips_long_cell() {
+        let table = SimpleTable::new(
+            vec!["Name", "Status", "Notes"],
+            vec![
+                Constraint::Length(6),
+                Constraint::Length(7),
+                Constraint::Length(8),
+            ],
+        )
+        .separator(true)
+        .rows(vec![
+            vec![Span::raw("api"), Span::raw("ok"), Span::raw("fine")],
+            vec![
+                Span::raw("worker"),
+                Span::raw("failing"),
+                Span::raw("restarted twice today"),
+            ],
+        ])
+        .selected(Some(1));
+        assert_eq!(table.column_layout(23), [(0, 6), (7, 7), (15, 8)]);
+
+        let area = Rect::new(0, 0, 23, 4);
+        let mut buffer = Buffer::empty(area);
+        table.render(area, &mut buffer);
+
+        assert_eq!(strip_text(&buffer, 0), "Name   Status  Notes   ");
+        assert_eq!(strip_text(&buffer, 1), "─".repeat(23));
+        assert_eq!(strip_text(&buffer, 2), "api    ok      fine    ");
+        assert_eq!(strip_text(&buffer, 3), "worker failing restart…");
+        assert!(buffer[(0, 0)].modifier.contains(Modifier::BOLD));
+        assert!(buffer[(22, 3)].modifier.contains(Modifier::REVERSED));
+        assert!(!buffer[(0, 2)].modifier.contains(Modifier::REVERSED));
+    }
+
+    fn sample_tree() -> Vec<TreeNode<'static>> {
+        vec![
+            TreeNode::new("src", 0),
+            TreeNode::new("widgets", 1),
+            TreeNode::new("card.rs", 2),
+            TreeNode::new("lib.rs", 1),
+            TreeNode::new("README.md", 0),
+        ]
+    }
+
+    #[test]
+    fn test_tree_connectors() {
+        let area = Rect::new(0, 0, 16, 5);
+        let mut buffer = Buffer::empty(area);
+        Tree::new(sample_tree())
+            .selected(Some(3))
+            .render(area, &mut buffer);
+
+        assert_eq!(strip_text(&buffer, 0), "▾ src           ");
+        assert_eq!(strip_text(&buffer, 1), "├─▾ widgets     ");
+        assert_eq!(strip_text(&buffer, 2), "│ └─  card.rs   ");
+        assert_eq!(strip_text(&buffer, 3), "└─  lib.rs      ");
+        assert_eq!(strip_text(&buffer, 4), "  README.md     ");
+        assert!(buffer[(5, 3)].modifier.contains(Modifier::REVERSED));
+    }
+
+    #[test]
+    fn test_tree_collapsed_hides_descendants() {
+        let mut nodes = sample_tree();
+        nodes[1] = TreeNode::new("widgets", 1).expanded(false);
+        let tree = Tree::new(nodes);
+        assert_eq!(tree.visible_indices(), [0, 1, 3, 4]);
+
+        let area = Rect::new(0, 0, 16, 4);
+        let mut buffer = Buffer::empty(area);
+        tree.render(area, &mut buffer);
+        assert_eq!(strip_text(&buffer, 1), "├─▸ widgets     ");
+        assert_eq!(strip_text(&buffer, 2), "└─  lib.rs      ");
+    }
+
+    #[test]
+    fn test_tabs_next_prev_wrap() {
+        let mut tabs = Tabs::new(vec!["One", "Two", "Three"]);
+        tabs.prev();
+        assert_eq!(tabs.selected(), 2);
+        tabs.next();
+        assert_eq!(tabs.selected(), 0);
+        tabs.next();
+        assert_eq!(tabs.selected(), 1);
+    }
+
+    #[test]
+    fn test_tabs_render_and_content_area() {
+        let area = Rect::new(0, 0, 20, 5);
+        let mut buffer = Buffer::empty(area);
+        Tabs::new(vec!["One", "Two"])
+            .select(1)
+            .render(area, &mut buffer);
+
+        assert_eq!(strip_text(&buffer, 0), " One │ Two          ");
+        assert!(buffer[(7, 0)].modifier.contains(Modifier::REVERSED));
+        assert!(!buffer[(1, 0)].modifier.contains(Modifier::REVERSED));
+        assert_eq!(Tabs::content_area(area), Rect::new(0, 1, 20, 4));
+    }
+
+    #[test]
+    fn test_tabs_overflow_scrolls_to_selection() {
+        let titles = vec!["Alpha", "Beta", "Gamma", "Delta", "Omega"];
+        let area = Rect::new(0, 0, 18, 1);
+
+        let mut buffer = Buffer::empty(area);
+        Tabs::new(titles.clone()).render(area, &mut buffer);
+        assert_eq!(strip_text(&buffer, 0), "  Alpha │ Beta   ›");
+
+        let mut buffer = Buffer::empty(area);
+        Tabs::new(titles).select(4).render(area, &mut buffer);
+        assert_eq!(strip_text(&buffer, 0), "‹ Delta │ Omega   ");
+    }
+
+    #[test]
+    fn test_dim_area_keeps_glyphs() {
+        let area = Rect::new(0, 0, 3, 1);
+        let mut buffer = Buffer::with_lines(["abc"]);
+        dim_area(area, &mut buffer);
+
+        assert_eq!(strip_text(&buffer, 0), "abc");
+        assert!((0..3).all(|x| buffer[(x, 0)].modifier.contains(Modifier::DIM)));
+        assert!((0..3).all(|x| buffer[(x, 0)].fg == Color::DarkGray));
+    }
+
+    #[test]
+    fn test_modal() {
+        let mut modal = Modal::new("Quit?")
+            .body(vec![Line::from("Unsaved changes")])
+            .buttons(vec!["Yes", "No"]);
+        modal.select_next_button();
+        assert_eq!(modal.selected_button(), Some("No"));
+        modal.select_next_button();
+        assert_eq!(modal.selected_button(), Some("Yes"));
+        modal.select_prev_button();
+        assert_eq!(modal.selected_button_index(), Some(1));
+
+        let area = Rect::new(0, 0, 30, 9);
+        let mut buffer = Buffer::with_lines(vec!["x".repeat(30); 9]);
+        modal.render(area, &mut buffer);
+
+        // The background keeps its glyphs but is dimmed
+        assert_eq!(buffer[(0, 0)].symbol(), "x");
+        assert!(buffer[(0, 0)].modifier.contains(Modifier::DIM));
+
+        // The dialog is 19x5 and centered, with the body and buttons inside
+        assert_eq!(buffer[(5, 2)].symbol(), "┌");
+        assert_eq!(
+            strip_text(&buffer, 3).trim_matches('x'),
+            "│ Unsaved changes │"
+        );
+        assert_eq!(
+            strip_text(&buffer, 5).trim_matches('x'),
+            "│ [ Yes ]  [ No ] │"
+        );
+        assert!(buffer[(17, 5)].modifier.contains(Modifier::REVERSED));
+        assert!(!buffer[(8, 5)].modifier.contains(Modifier::REVERSED));
+        assert!(!buffer[(10, 3)].modifier.contains(Modifier::DIM));
+    }
+
+    #[test]
+    fn test_dim_area_by_scales_rgb() {
+        let area = Rect::new(0, 0, 1, 1);
+        let mut buffer = Buffer::empty(area);
+        buffer[(0, 0)]
+            .set_fg(Color::Rgb(200, 100, 50))
+            .set_bg(Color::Rgb(100, 100, 100));
+        dim_area_by(area, &mut buffer, 0.5);
+
+        assert_eq!(buffer[(0, 0)].fg, Color::Rgb(100, 50, 25));
+        assert_eq!(buffer[(0, 0)].bg, Color::Rgb(50, 50, 50));
+    }
+
+    #[test]
+    fn test_loading_overlay() {
+        let area = Rect::new(0, 0, 11, 5);
+        let mut buffer = Buffer::with_lines(vec!["x".repeat(11); 5]);
+        LoadingOverlay::new()
+            .message("Loading")
+            .elapsed(Duration::from_millis(250))
+            .render(area, &mut buffer);
+
+        // Third frame of the spinner, in the center cell
+        assert_eq!(buffer[(5, 2)].symbol(), "⠹");
+        assert_eq!(strip_text(&buffer, 3), "xxLoadingxx");
+
+        assert_eq!(buffer[(0, 0)].symbol(), "x");
+        assert!(buffer[(0, 0)].modifier.contains(Modifier::DIM));
+        assert_eq!(buffer[(0, 0)].fg, Color::DarkGray);
+    }
+
+    #[test]
+    fn test_perimeter_positions() {
+        let positions = perimeter_positions(Rect::new(0, 0, 3, 3));
+        let expected = [
+            (0, 0),
+            (1, 0),
+            (2, 0),
+            (2, 1),
+            (2, 2),
+            (1, 2),
+            (0, 2),
+            (0, 1),
+        ];
+        assert_eq!(positions, expected.map(Position::from));
+
+        assert_eq!(perimeter_positions(Rect::new(0, 0, 4, 1)).len(), 4);
+        assert_eq!(perimeter_positions(Rect::new(0, 0, 1, 3)).len(), 3);
+    }
+
+    #[test]
+    fn test_bordered_gauge() {
+        let area = Rect::new(0, 0, 6, 4);
+        let perimeter = perimeter_positions(area);
+        let filled = |ratio| {
+            let mut buffer = Buffer::empty(area);
+            BorderedGauge::new(ratio)
+                .title("CPU")
+                .status(StatusColor::Success)
+                .render(area, &mut buffer);
+            perimeter
+                .iter()
+                .filter(|&&position| buffer[position].fg == Color::Green)
+                .count()
+        };
+
+        assert_eq!(filled(0.0), 0);
+        assert_eq!(filled(1.0), perimeter.len());
+
+        // Each edge is a quarter of the ratio, however many cells it has
+        let lit = |ratio| {
+            let mut buffer = Buffer::empty(area);
+            BorderedGauge::new(ratio).render(area, &mut buffer);
+            move |x, y| buffer[(x, y)].fg == Color::Green
+        };
+        let quarter = lit(0.25);
+        assert!((0..6).all(|x| quarter(x, 0)));
+        assert!(!quarter(5, 1));
+        let half = lit(0.5);
+        assert!((1..4).all(|y| half(5, y)));
+        assert!(!half(4, 3));
+        let eighth = lit(0.125);
+        assert!((0..3).all(|x| eighth(x, 0)));
+        assert!(!eighth(3, 0));
+    }
+
+    #[test]
+    fn test_gradient_line() {
+        let red = Color::Rgb(255, 0, 0);
+        let blue = Color::Rgb(0, 0, 255);
+        let line = gradient_line("Hello", &[red, blue]);
+        let colors: Vec<_> = line.spans.iter().map(|span| span.style.fg).collect();
+        assert_eq!(line.spans.len(), 5);
+        assert_eq!(colors[0], Some(red));
+        assert_eq!(colors[2], Some(Color::Rgb(128, 0, 128)));
+        assert_eq!(colors[4], Some(blue));
+
+        // A single stop colors the whole text in one span
+        let line = gradient_line("Hello", &[red]);
+        assert_eq!(line.spans.len(), 1);
+        assert_eq!(line.spans[0].content, "Hello");
+
+        assert!(gradient_line("", &[red, blue]).spans.is_empty());
+    }
+
+    fn type_text(input: &mut TextInput, text: &str) {
+        for c in text.chars() {
+            input.handle_key(&KeyEvent::from(KeyCode::Char(c)));
+        }
+    }
+
+    #[test]
+    fn test_text_input_graphemes() {
+        let mut input = TextInput::new();
+        // "e" followed by a combining acute accent is a single grapheme
+        type_text(&mut input, "cafe\u{301}!");
+        assert_eq!(input.value(), "cafe\u{301}!");
+
+        input.handle_key(&KeyEvent::from(KeyCode::Left));
+        input.handle_key(&KeyEvent::from(KeyCode::Left));
+        assert_eq!(input.cursor(), 3);
+        input.handle_key(&KeyEvent::from(KeyCode::Delete));
+        assert_eq!(input.value(), "caf!");
+
+        input.handle_key(&KeyEvent::from(KeyCode::End));
+        input.handle_key(&KeyEvent::from(KeyCode::Backspace));
+        input.handle_key(&KeyEvent::from(KeyCode::Home));
+        type_text(&mut input, "\u{1F600}");
+        assert_eq!(input.value(), "\u{1F600}caf");
+        input.move_cursor_left();
+        assert_eq!(input.cursor(), 0);
+    }
+
+    #[test]
+    fn test_text_input_scrolls_to_cursor() {
+        let mut input = TextInput::new();
+        input.set_value("abcdefghij");
+        let area = Rect::new(0, 0, 6, 3);
+
+        let mut buffer = Buffer::empty(area);
+        (&input).render(area, &mut buffer);
+        assert_eq!(strip_text(&buffer, 1), "│hij │");
+        assert_eq!(input.cursor_position(area), Some(Position::new(4, 1)));
+
+        input.move_cursor_home();
+        let mut buffer = Buffer::empty(area);
+        (&input).render(area, &mut buffer);
+        assert_eq!(strip_text(&buffer, 1), "│abcd│");
+        assert_eq!(input.cursor_position(area), Some(Position::new(1, 1)));
+    }
+
+    #[test]
+    fn test_text_input_cursor_left_of_scroll() {
+        let mut input = TextInput::new();
+        input.set_value("abcdefghij");
+        let area = Rect::new(0, 0, 6, 3);
+        (&input).render(area, &mut Buffer::empty(area));
+
+        // The scroll offset only catches up with the cursor on the next render
+        input.move_cursor_home();
+        assert!(input.scroll.get() > 0);
+        assert_eq!(input.cursor_position(area), Some(Position::new(1, 1)));
+    }
+
+    #[test]
+    fn test_text_input_cursor_shapes() {
+        let area = Rect::new(0, 0, 8, 3);
+        let render_at = |shape, left| {
+            let mut input = TextInput::new().cursor_style(shape);
+            input.set_value("abc");
+            for _ in 0..left {
+                input.move_cursor_left();
+            }
+            let mut buffer = Buffer::empty(area);
+            (&input).render(area, &mut buffer);
+            buffer[(4 - left, 1)].clone()
+        };
+        let render = |shape| render_at(shape, 1);
+
+        let cell = render(CursorShape::Block);
+        assert_eq!(cell.symbol(), "c");
+        assert!(cell.modifier.contains(Modifier::REVERSED));
+
+        let cell = render(CursorShape::Underline);
+        assert_eq!(cell.symbol(), "c");
+        assert!(cell.modifier.contains(Modifier::UNDERLINED));
+
+        let cell = render(CursorShape::Bar);
+        assert_eq!(cell.symbol(), "c");
+        assert!(cell.modifier.contains(Modifier::REVERSED));
+
+        let cell = render_at(CursorShape::Bar, 0);
+        assert_eq!(cell.symbol(), "▏");
+    }
+
+    #[test]
+    fn test_text_input_history() {
+        let mut input = TextInput::new();
+        type_text(&mut input, "first");
+        assert_eq!(input.submit(), "first");
+        type_text(&mut input, "dra");
+
+        input.handle_key(&KeyEvent::from(KeyCode::Up));
+        assert_eq!(input.value(), "first");
+        input.handle_key(&KeyEvent::from(KeyCode::Down));
+        assert_eq!(input.value(), "dra");
+        assert_eq!(input.cursor(), 3);
+    }
+
+    #[test]
+    fn test_toast_stack() {
+        let start = Instant::now();
+        let mut toasts = ToastStack::new()
+            .lifetime(Duration::from_secs(2))
+            .max_toasts(2);
+        toasts.push_at("one", StatusColor::Info, start);
+        toasts.push_at("two", StatusColor::Warning, start + Duration::from_secs(1));
+        toasts.push_at("three", StatusColor::Error, start + Duration::from_secs(1));
+        let messages: Vec<_> = toasts.iter().map(Toast::message).collect();
+        assert_eq!(messages, ["three", "two"]);
+
+        let area = Rect::new(0, 0, 12, 6);
+        let mut buffer = Buffer::empty(area);
+        (&toasts).render(area, &mut buffer);
+        assert_eq!(strip_text(&buffer, 1), "   │ three │");
+        assert_eq!(strip_text(&buffer, 4), "     │ two │");
+        assert_eq!(buffer[(3, 0)].fg, Color::Red);
+
+        assert!(toasts.expire(start + Duration::from_secs(3)));
+        assert!(toasts.is_empty());
+        assert!(!toasts.expire(start + Duration::from_secs(4)));
+    }
+
+    #[test]
+    fn test_toast_stack_coalesces_repeats() {
+        let start = Instant::now();
+        let mut toasts = ToastStack::new();
+        for millis in [0, 100, 200] {
+            toasts.push_at(
+                "connection failed",
+                StatusColor::Error,
+                start + Duration::from_millis(millis),
+            );
+        }
+        assert_eq!(toasts.len(), 1);
+        assert_eq!(toasts.iter().next().unwrap().count(), 3);
+
+        let area = Rect::new(0, 0, 30, 3);
+        let mut buffer = Buffer::empty(area);
+        (&toasts).render(area, &mut buffer);
+        assert_eq!(strip_text(&buffer, 1), "    │ connection failed (x3) │");
+
+        // Outside the window the message is a new toast
+        toasts.push_at(
+            "connection failed",
+            StatusColor::Error,
+            start + Duration::from_secs(3),
+        );
+        assert_eq!(toasts.len(), 2);
+    }
+
+    #[test]
+    fn test_toast_stack_queues_overflow() {
+        let start = Instant::now();
+        let mut toasts = ToastStack::new()
+            .lifetime(Duration::from_secs(2))
+            .max_toasts(1)
+            .overflow(ToastOverflow::Queue);
+        toasts.push_at("one", StatusColor::Info, start);
+        toasts.push_at("two", StatusColor::Info, start);
+        toasts.push_at("two", StatusColor::Info, start);
+        assert_eq!((toasts.len(), toasts.queued()), (1, 1));
+
+        toasts.expire(start + Duration::from_secs(3));
+        let shown: Vec<_> = toasts
+            .iter()
+            .map(|toast| (toast.message(), toast.count()))
+            .collect();
+        assert_eq!(shown, [("two", 2)]);
+        assert_eq!(toasts.queued(), 0);
+    }
+
+    #[test]
+    fn test_overlay_stack_draws_higher_z_on_top() {
+        let fill = |symbol: &'static str| {
+            move |area: Rect, buf: &mut Buffer| {
+                for position in area.positions() {
+                    buf[position].set_symbol(symbol);
+                }
+            }
+        };
+        let mut overlays = OverlayStack::new();
+        overlays.push(5, Rect::new(1, 0, 2, 1), fill("b"));
+        let low = overlays.push(1, Rect::new(0, 0, 2, 1), fill("a"));
+        overlays.push(5, Rect::new(2, 0, 2, 1), fill("c"));
+
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 4, 1));
+        (&overlays).render(buffer.area, &mut buffer);
+        assert_eq!(strip_text(&buffer, 0), "abcc");
+
+        assert_eq!(overlays.pop().map(|id| overlays.remove(id)), Some(false));
+        assert!(overlays.remove(low));
+        assert_eq!(overlays.len(), 1);
+    }
+
+    #[test]
+    fn test_marquee_window_shifts() {
+        let render = |marquee: Marquee| {
+            let mut buffer = Buffer::empty(Rect::new(0, 0, 5, 1));
+            marquee.render(buffer.area, &mut buffer);
+            strip_text(&buffer, 0)
+        };
+
+        let marquee = || Marquee::new("héllo world").separator(" | ");
+        assert_eq!(render(marquee()), "héllo");
+        assert_eq!(render(marquee().offset(1)), "éllo ");
+        assert_eq!(render(marquee().offset(9)), "ld | ");
+        // Wraps back to the start after the text and the separator
+        assert_eq!(render(marquee().offset(14)), "héllo");
+        assert_eq!(
+            render(marquee().offset(1).direction(MarqueeDirection::Right)),
+            " héll"
+        );
+
+        // Short text stays put
+        assert_eq!(render(Marquee::new("hi").offset(3)), "hi   ");
+    }
+
+    #[test]
+    fn test_viewport_shows_scrolled_window() {
+        let mut viewport = Viewport::new(6, 4);
+        let rows: Vec<Line> = ["abcdef", "ghijkl", "mnopqr", "stuvwx"]
+            .into_iter()
+            .map(Line::raw)
+            .collect();
+        viewport.render_content(Text::from(rows));
+
+        let render = |viewport: &mut Viewport, width, height| {
+            let mut buffer = Buffer::empty(Rect::new(0, 0, width, height));
+            viewport.render(buffer.area, &mut buffer);
+            (0..height)
+                .map(|y| strip_text(&buffer, y))
+                .collect::<Vec<_>>()
+        };
+
+        viewport.scroll_to(2, 1);
+        assert_eq!(render(&mut viewport, 3, 2), ["ijk", "opq"]);
+
+        // Scrolling past the end stops with the last column and row in view
+        viewport.scroll_by(10, 10);
+        assert_eq!(render(&mut viewport, 3, 2), ["pqr", "vwx"]);
+        assert_eq!(viewport.scroll_offset(), (3, 2));
+    }
+
+    #[test]
+    fn test_viewport_larger_than_content() {
+        let mut viewport = Viewport::new(2, 1);
+        viewport.render_content(Line::raw("hi"));
+        viewport.scroll_to(1, 0);
+
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 4, 2));
+        viewport.render(buffer.area, &mut buffer);
+        assert_eq!(strip_text(&buffer, 0), "hi  ");
+        assert_eq!(viewport.scroll_offset(), (0, 0));
+    }
+
+    #[test]
+    fn test_bar_chart_heights_follow_values() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 5, 9));
+        BarChart::new(vec![("a", 10.0), ("b", 5.0)]).render(buffer.area, &mut buffer);
+
+        let height = |x| (0..8).filter(|&y| buffer[(x, y)].symbol() == "█").count();
+        assert_eq!((height(0), height(1)), (8, 8));
+        assert_eq!((height(3), height(4)), (4, 4));
+        assert_eq!(buffer[(2, 7)].symbol(), " ");
+        assert_eq!(strip_text(&buffer, 8), "a  b ");
+    }
+
+    #[test]
+    fn test_bar_chart_axis_and_narrow_labels() {
+        let chart = BarChart::new(vec![("alpha", 2000.0), ("beta", 500.0), ("gamma", 0.0)]);
+        assert_eq!(chart.bar_columns(10), [(0, 3), (4, 3), (8, 2)]);
+        // Too narrow for gaps
+        assert_eq!(chart.bar_columns(4), [(0, 2), (2, 1), (3, 1)]);
+
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 14, 5));
+        chart.axis(true).render(buffer.area, &mut buffer);
+        assert_eq!(strip_text(&buffer, 0), "2k│███        ");
+        assert_eq!(strip_text(&buffer, 2), "1k│███        ");
+        assert_eq!(strip_text(&buffer, 3), " 0│███ ███    ");
+        assert_eq!(strip_text(&buffer, 4), "   al… be… ga…");
+    }
+
+    #[test]
+    fn test_bar_chart_wide_label_in_narrow_bar() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 5, 4));
+        BarChart::new(vec![("日本", 1.0), ("b", 2.0), ("c", 3.0)]).render(buffer.area, &mut buffer);
+        // The first bar is one column wide, too narrow for any of its label
+        assert_eq!(strip_text(&buffer, 3), "  b c");
+    }
+
+    #[test]
+    fn test_pie_chart_equal_slices_split_the_circle() {
+        let area = Rect::new(0, 0, 40, 20);
+        let lit = |chart: PieChart| {
+            let mut buffer = Buffer::empty(area);
+            chart.render(area, &mut buffer);
+            let count = |color| {
+                buffer
+                    .content()
+                    .iter()
+                    .filter(|cell| cell.fg == color)
+                    .count()
+            };
+            (count(Color::Red), count(Color::Blue), buffer)
+        };
+
+        let (red, blue, buffer) = lit(PieChart::new(vec![
+            ("a", 1.0, Color::Red),
+            ("b", 1.0, Color::Blue),
+        ]));
+        assert!(
+            red > 100 && red.abs_diff(blue) <= red / 20,
+            "{red} vs {blue}"
+        );
+        // The first slice starts at twelve o'clock and runs clockwise
+        assert_eq!(buffer[(25, 5)].fg, Color::Red);
+        assert_eq!(buffer[(14, 5)].fg, Color::Blue);
+
+        let (donut_red, _, buffer) =
+            lit(PieChart::new(vec![("a", 1.0, Color::Red), ("b", 1.0, Color::Blue)]).hole(0.5));
+        assert!(donut_red < red);
+        assert_eq!(buffer[(20, 10)].symbol(), " ");
+    }
+
+    #[test]
+    fn test_pie_chart_legend() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 20, 2));
+        PieChart::new(vec![("cpu", 3.0, Color::Red), ("io", 1.0, Color::Blue)])
+            .legend(true)
+            .render(buffer.area, &mut buffer);
+        // The chart takes the columns left of the legend and a gap
+        assert_eq!(strip_text(&buffer, 0), "   ████    ■ cpu 75%");
+        assert_eq!(strip_text(&buffer, 1), "   ████    ■ io 25% ");
+        assert_eq!(buffer[(11, 1)].fg, Color::Blue);
+    }
+
+    #[test]
+    fn test_banner_is_centered() {
+        let banner = Banner::new("\n  ab\ncdef\n\n").line_colors(vec![Color::Red, Color::Blue]);
+        assert_eq!(banner.size(), (4, 2));
+
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 8, 20));
+        banner.render(buffer.area, &mut buffer);
+        // (20 - 2) / 2 rows down, with nothing above
+        assert!((0..9).all(|y| strip_text(&buffer, y).trim().is_empty()));
+        assert_eq!(strip_text(&buffer, 9), "    ab  ");
+        assert_eq!(strip_text(&buffer, 10), "  cdef  ");
+        assert_eq!(buffer[(4, 9)].fg, Color::Red);
+        assert_eq!(buffer[(2, 10)].fg, Color::Blue);
+    }
+
+    #[test]
+    fn test_banner_clips_to_its_middle() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 2, 1));
+        Banner::new("abcd\nefgh\nijkl").render(buffer.area, &mut buffer);
+        assert_eq!(strip_text(&buffer, 0), "fg");
+    }
+
+    #[test]
+    fn test_typewriter_reveals_by_chars() {
+        assert_eq!(chars_at(Duration::from_millis(250), 10.0), 2);
+        assert_eq!(chars_at(Duration::from_secs(1), 0.0), 0);
+
+        let typewriter = Typewriter::new("héllo")
+            .chars_per_second(10.0)
+            .elapsed(Duration::from_millis(250));
+        assert_eq!(typewriter.visible_text(), "hé");
+        assert!(!typewriter.is_finished());
+
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 6, 1));
+        typewriter
+            .cursor(Some('_'))
+            .render(buffer.area, &mut buffer);
+        assert_eq!(strip_text(&buffer, 0), "hé_   ");
+
+        let typewriter = Typewriter::new("héllo").revealed(99);
+        assert_eq!(typewriter.visible_text(), "héllo");
+        assert!(typewriter.is_finished());
+
+        // The speed applies however the builder calls are ordered
+        let typewriter = Typewriter::new("héllo")
+            .elapsed(Duration::from_millis(250))
+            .chars_per_second(10.0);
+        assert_eq!(typewriter.visible_text(), "hé");
+    }
+
+    #[test]
+    fn test_status_bar_drops_center_first() {
+        let bar = StatusBar::new()
+            .left(vec![Span::raw("NORMAL")])
+            .center(vec![Span::raw("main"), Span::raw(".rs")])
+            .right(vec![Span::raw("1:1")]);
+        let render = |width| {
+            let mut buffer = Buffer::empty(Rect::new(0, 0, width, 1));
+            bar.clone().render(buffer.area, &mut buffer);
+            strip_text(&buffer, 0)
+        };
+
+        assert_eq!(render(25), "NORMAL   main.rs      1:1");
+        // Pushed aside rather than overlapping the left group
+        assert_eq!(render(19), "NORMAL main.rs  1:1");
+        assert_eq!(render(16), "NORMAL main… 1:1");
+        assert_eq!(render(11), "NORMAL  1:1");
+        assert_eq!(render(8), "NORMAL …");
+        assert_eq!(render(4), "NOR…");
+    }
+
+    #[test]
+    fn test_error_view_shows_source_chain() {
+        #[derive(Debug, thiserror::Error)]
+        #[error("failed to load config")]
+        struct LoadError(#[source] std::io::Error);
+
+        let error = LoadError(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "settings.toml not found",
+        ));
+        let area = Rect::new(0, 0, 30, 5);
+        let mut buffer = Buffer::empty(area);
+        ErrorView::new(&error).render(area, &mut buffer);
+
+        assert_eq!(strip_text(&buffer, 1), "│failed to load config       │");
+        assert_eq!(strip_text(&buffer, 2), "│Caused by:                  │");
+        assert_eq!(strip_text(&buffer, 3), "│└ settings.toml not found   │");
+        assert!(strip_text(&buffer, 4).ends_with("Esc to dismiss┘"));
+        assert_eq!(buffer[(0, 0)].fg, Color::Red);
+    }
+
+    #[test]
+    fn test_markup() {
+        let line = markup("Press *q* to {color:red}quit _now_{/}, or `*literal*`");
+        let spans: Vec<(&str, Style)> = line
+            .spans
+            .iter()
+            .map(|span| (span.content.as_ref(), span.style))
+            .collect();
+        let red = Style::new().fg(Color::Red);
+        assert_eq!(
+            spans,
+            [
+                ("Press ", Style::new()),
+                ("q", Style::new().add_modifier(Modifier::BOLD)),
+                (" to ", Style::new()),
+                ("quit ", red),
+                ("now", red.add_modifier(Modifier::ITALIC)),
+                (", or ", Style::new()),
+                ("*literal*", Style::new().fg(Color::Cyan)),
+            ]
+        );
+
+        let line = markup(r"2 \* 3 = {six}");
+        assert_eq!(line.to_string(), "2 * 3 = {six}");
+        assert_eq!(line.spans.len(), 1);
     }
 }